@@ -39,7 +39,7 @@ impl App {
     /// desktop app:
     ///
     /// `ui_handlers`, `layout_handlers`, `input_handlers`,
-    /// `mouse_handlers`, `keyboard_handlers` and `drag_handlers`
+    /// `mouse_handlers`, `keyboard_handlers`, `drag_handlers` and `modal_handlers`
     pub fn new(window: Window, events_loop: glutin::EventsLoop) -> Self {
         event::queue_set_events_loop(&events_loop);
         let ui = Ui::new(window, &events_loop);
@@ -63,6 +63,7 @@ impl App {
         self.add_mouse_handlers();
         self.add_keyboard_handlers();
         self.add_drag_handlers();
+        self.add_modal_handlers();
     }
 
     fn handle_window_event(&mut self, event: glutin::Event) {
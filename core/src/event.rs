@@ -39,8 +39,11 @@
 
 use std::any::{Any, TypeId};
 use std::cell::{Cell, RefCell};
-use std::sync::Mutex;
-use std::collections::VecDeque;
+use std::cmp::Ordering;
+use std::sync::{Mutex, Condvar};
+use std::collections::{VecDeque, BinaryHeap};
+use std::thread;
+use std::time::{Duration, Instant};
 
 use glutin::{EventsLoop, EventsLoopProxy};
 
@@ -159,6 +162,7 @@ impl EventHandlerWrapper {
 lazy_static! {
     static ref FIRST_THREAD: Mutex<Cell<bool>> = Mutex::new(Cell::new(true));
     static ref GLOBAL_QUEUE: Mutex<GlobalQueue> = Mutex::new(GlobalQueue::new());
+    static ref TIMER: Timer = Timer::new();
 }
 
 thread_local! {
@@ -205,6 +209,94 @@ pub fn event_global<T: 'static + Send>(data: T) {
     GLOBAL_QUEUE.lock().unwrap().push(data);
 }
 
+/// Send message to UI from any thread, once `delay` has passed.
+pub fn event_global_after<T: 'static + Send>(delay: Duration, data: T) {
+    TIMER.push(Instant::now() + delay, TypeId::of::<T>(), Box::new(data));
+}
+
+/// Holds delayed events until their deadline, then passes them to the global queue.
+/// A single thread waits for every deadline, started when the first event is delayed.
+struct Timer {
+    queue: Mutex<TimerQueue>,
+    changed: Condvar,
+}
+
+struct TimerQueue {
+    events: BinaryHeap<TimedEvent>,
+    /// Number of events delayed so far, keeps events with the same deadline in order
+    count: usize,
+    started: bool,
+}
+
+struct TimedEvent {
+    deadline: Instant,
+    order: usize,
+    type_id: TypeId,
+    data: Box<Any + Send>,
+}
+
+impl Timer {
+    fn new() -> Self {
+        Timer {
+            queue: Mutex::new(TimerQueue {
+                events: BinaryHeap::new(),
+                count: 0,
+                started: false,
+            }),
+            changed: Condvar::new(),
+        }
+    }
+    fn push(&'static self, deadline: Instant, type_id: TypeId, data: Box<Any + Send>) {
+        let mut queue = self.queue.lock().unwrap();
+        if !queue.started {
+            queue.started = true;
+            thread::spawn(move || self.run());
+        }
+        let order = queue.count;
+        queue.count += 1;
+        queue.events.push(TimedEvent {
+            deadline: deadline,
+            order: order,
+            type_id: type_id,
+            data: data,
+        });
+        self.changed.notify_one();
+    }
+    fn run(&self) {
+        let mut queue = self.queue.lock().unwrap();
+        loop {
+            let now = Instant::now();
+            while queue.events.peek().map_or(false, |event| event.deadline <= now) {
+                let event = queue.events.pop().unwrap();
+                GLOBAL_QUEUE.lock().unwrap().push_any(event.type_id, event.data);
+            }
+            let timeout = queue.events.peek().map(|event| event.deadline - now);
+            queue = match timeout {
+                Some(timeout) => self.changed.wait_timeout(queue, timeout).unwrap().0,
+                None => self.changed.wait(queue).unwrap(),
+            };
+        }
+    }
+}
+
+// ordered so the `BinaryHeap` pops the earliest deadline first
+impl Ord for TimedEvent {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.deadline.cmp(&self.deadline).then(other.order.cmp(&self.order))
+    }
+}
+impl PartialOrd for TimedEvent {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl PartialEq for TimedEvent {
+    fn eq(&self, other: &Self) -> bool {
+        self.order == other.order
+    }
+}
+impl Eq for TimedEvent {}
+
 struct GlobalQueue {
     queue: VecDeque<(TypeId, Box<Any + Send>)>,
     events_loop_proxy: Option<EventsLoopProxy>,
@@ -222,8 +314,10 @@ impl GlobalQueue {
     }
     /// Push a new event on the queue and wake the window up if it is asleep
     pub fn push<T: 'static + Send>(&mut self, data: T) {
-        let type_id = TypeId::of::<T>();
-        self.queue.push_back((type_id, Box::new(data)));
+        self.push_any(TypeId::of::<T>(), Box::new(data));
+    }
+    fn push_any(&mut self, type_id: TypeId, data: Box<Any + Send>) {
+        self.queue.push_back((type_id, data));
         if let Some(ref events_loop_proxy) = self.events_loop_proxy {
             events_loop_proxy.wakeup().unwrap();
         }
@@ -3,7 +3,7 @@ use glutin;
 use event::{EventHandler, EventArgs};
 use widget::Widget;
 use input::mouse::{MouseMoved, MouseButton, WidgetMouseButton};
-use geometry::{Point, Vector};
+use geometry::{Point, Vector, Rect, RectExt};
 use app::App;
 
 #[derive(Debug, Copy, Clone)]
@@ -24,6 +24,19 @@ pub enum DragState {
     End,
 }
 
+/// Position a horizontally dragged item, currently at `dragging`, should move to with the cursor at `x`,
+/// given the bounds of all the items. Items only move once the cursor is past the middle of another
+/// item, so items of different widths don't swap back and forth.
+pub fn drop_target<I: IntoIterator<Item = Rect>>(bounds: I, dragging: usize, x: f32) -> Option<usize> {
+    bounds.into_iter().enumerate()
+        .find(|&(_, bounds)| x >= bounds.left() && x < bounds.right())
+        .and_then(|(target, bounds)| {
+            let center = bounds.center().x;
+            let past_center = if target > dragging { x > center } else { x < center };
+            if target != dragging && past_center { Some(target) } else { None }
+        })
+}
+
 #[derive(Debug, Clone)]
 struct DragInputHandler {
     widget: Option<Widget>,
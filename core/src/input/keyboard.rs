@@ -36,11 +36,53 @@ pub struct FocusHandler {
     focusable: TreeMap<usize, Widget>,
     focused: Option<Widget>,
     focus_index_max: usize,
+    /// Widgets that focus can't leave, eg. modal dialogs, with the widget that was focused
+    /// before each was added. Only the last is active.
+    focus_traps: Vec<(Widget, Option<Widget>)>,
 }
 impl FocusHandler {
     pub fn new() -> Self {
         Self::default()
     }
+    /// True if `widget` can receive focus, ie. it's inside the active focus trap, if any
+    fn can_focus(&self, widget: &Widget) -> bool {
+        match self.focus_traps.last() {
+            Some(&(ref trap, _)) => {
+                let mut ancestor = Some(widget.clone());
+                while let Some(widget) = ancestor {
+                    if widget == *trap {
+                        return true;
+                    }
+                    ancestor = widget.parent();
+                }
+                false
+            }
+            None => true,
+        }
+    }
+    /// The first focusable widget after `index`, or the first focusable widget if `None`
+    fn next_focusable(&self, index: Option<usize>) -> Option<Widget> {
+        let mut candidates = match index {
+            Some(index) => self.focusable.range(Excluded(&index), Unbounded),
+            None => self.focusable.range(Unbounded, Unbounded),
+        };
+        candidates.find(|&(_, widget)| self.can_focus(widget)).map(|(_, widget)| widget.clone())
+    }
+    /// The widget that receives keyboard input, the focused widget, or the active focus trap
+    /// if nothing inside it is focused
+    fn keyboard_target(&self) -> Option<Widget> {
+        self.focused.clone().or_else(|| self.focus_traps.last().map(|&(ref trap, _)| trap.clone()))
+    }
+    /// Moves focus to the next focusable widget, wrapping around to the first
+    fn focus_next(&mut self) {
+        let index = self.focused.as_ref().map(|focused| self.focusable_map[focused]);
+        let mut new_focus = self.next_focusable(index);
+        if new_focus.is_none() {
+            // focus on first, if any
+            new_focus = self.next_focusable(None);
+        }
+        self.set_focus(new_focus);
+    }
     fn set_focus(&mut self, new_focus: Option<Widget>) {
         if new_focus != self.focused {
             if let Some(ref mut focused) = self.focused {
@@ -60,7 +102,7 @@ impl EventHandler<KeyboardInputEvent> for FocusHandler {
                 self.focusable.insert(self.focus_index_max, widget_id.clone());
                 self.focusable_map.insert(widget_id.clone(), self.focus_index_max);
                 self.focus_index_max += 1;
-                if self.focused.is_none() {
+                if self.focused.is_none() && self.can_focus(widget_id) {
                     self.set_focus(Some(widget_id.clone()));
                 }
             }
@@ -74,40 +116,80 @@ impl EventHandler<KeyboardInputEvent> for FocusHandler {
                 self.focusable.remove(&index);
             }
             KeyboardInputEvent::FocusChange(ref new_focus) => {
-                self.set_focus(new_focus.clone());
+                let allowed = new_focus.as_ref().map_or(true, |new_focus| self.can_focus(new_focus));
+                if allowed {
+                    self.set_focus(new_focus.clone());
+                }
+            }
+            KeyboardInputEvent::PushFocusTrap(ref trap) => {
+                self.focus_traps.push((trap.clone(), self.focused.clone()));
+                let new_focus = self.next_focusable(None);
+                self.set_focus(new_focus);
+            }
+            KeyboardInputEvent::PopFocusTrap(ref trap) => {
+                if let Some(index) = self.focus_traps.iter().position(|&(ref widget, _)| widget == trap) {
+                    let (_, prev_focus) = self.focus_traps.remove(index);
+                    if index == self.focus_traps.len() {
+                        // restore the focus from before the trap was added, if the widget is still focusable
+                        let prev_focus = prev_focus.and_then(|prev_focus| {
+                            if self.focusable_map.contains_key(&prev_focus) { Some(prev_focus) } else { None }
+                        });
+                        self.set_focus(prev_focus);
+                    }
+                }
             }
             KeyboardInputEvent::KeyboardInput(ref key_input) => {
-                if let Some(ref focused) = self.focused {
-                    let &KeyboardInput(input) = key_input;
+                let &KeyboardInput(input) = key_input;
+                if let Some(target) = self.keyboard_target() {
                     let event = WidgetKeyboardInput(input);
-                    focused.event_subtree(event);
+                    if is_container_shortcut(&input) {
+                        // bubbles up, so containers like a tab view can handle it while a widget inside them is focused
+                        target.event_bubble_up(event);
+                    } else {
+                        target.event_subtree(event);
+                    }
+                }
+                // ctrl+Tab is left to containers, the tab character it produces is ignored below
+                let tab = input.virtual_keycode == Some(glutin::VirtualKeyCode::Tab);
+                if tab && input.state == glutin::ElementState::Pressed && !input.modifiers.ctrl {
+                    self.focus_next();
                 }
             }
             KeyboardInputEvent::ReceivedCharacter(ref received_char) => {
                 let &ReceivedCharacter(char) = received_char;
+                // focus is moved by the tab key press
                 if char == '\t' {
-                    let mut new_focus = self.focused.clone().and_then(|focused| {
-                        let index = &self.focusable_map[&focused];
-                        self.focusable.range(Excluded(index), Unbounded).next().map(|(_, v)| v.clone())
-                    });
-                    if new_focus.is_none() {
-                        // focus on first, if any
-                        new_focus = self.focusable.iter().next().map(|(_, v)| v.clone());
-                    }
-                    self.set_focus(new_focus);
-                } else if let Some(ref focused) = self.focused {
+                    return;
+                }
+                if let Some(target) = self.keyboard_target() {
                     let event = WidgetReceivedCharacter(char);
-                    focused.event_subtree(event);
+                    target.event_subtree(event);
                 }
             }
         }
     }
 }
 
+/// Ctrl+Tab, Ctrl+PageDown and Ctrl+PageUp, handled by containers of the focused widget
+fn is_container_shortcut(input: &glutin::KeyboardInput) -> bool {
+    if !input.modifiers.ctrl {
+        return false;
+    }
+    match input.virtual_keycode {
+        Some(glutin::VirtualKeyCode::Tab) |
+        Some(glutin::VirtualKeyCode::PageDown) |
+        Some(glutin::VirtualKeyCode::PageUp) => true,
+        _ => false,
+    }
+}
+
 pub enum KeyboardInputEvent {
     AddFocusable(Widget),
     RemoveFocusable(Widget),
     FocusChange(Option<Widget>),
+    /// Keep focus inside a widget and it's descendants, until it's removed with `PopFocusTrap`
+    PushFocusTrap(Widget),
+    PopFocusTrap(Widget),
     KeyboardInput(KeyboardInput),
     ReceivedCharacter(ReceivedCharacter),
 }
@@ -5,6 +5,10 @@ pub mod mouse;
 pub mod keyboard;
 pub mod drag;
 
+use std::fs::File;
+use std::io::Write;
+use std::path::PathBuf;
+
 use glutin;
 use glutin::ElementState;
 use webrender;
@@ -14,6 +18,7 @@ use input::mouse::{MouseMoved, MouseButton, MouseWheel, CursorLeftWindow};
 use input::keyboard::{KeyboardInput, ReceivedCharacter};
 use geometry::Point;
 use app::App;
+use layout::inspect::LayoutGraph;
 
 #[derive(Clone)]
 pub struct InputEvent(pub glutin::WindowEvent);
@@ -58,22 +63,29 @@ pub struct EscKeyCloseHandler;
 
 impl EventHandler<KeyboardInput> for EscKeyCloseHandler {
     fn handle(&mut self, event: &KeyboardInput, args: EventArgs) {
-        if let Some(glutin::VirtualKeyCode::Escape) = event.0.virtual_keycode {
-            args.ui.close();
+        // Escape closes the top modal dialog or popup instead, if there is one
+        let has_popup = args.ui.has_modal() || args.ui.has_popup();
+        if event.0.state == ElementState::Pressed && !has_popup {
+            if let Some(glutin::VirtualKeyCode::Escape) = event.0.virtual_keycode {
+                args.ui.close();
+            }
         }
     }
 }
 
 /// Triggers various debugging settings/events on function key presses.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Clone)]
 pub struct DebugSettingsHandler {
-    debug_on: bool
+    debug_on: bool,
+    /// Where F7 writes the layout graph, with `.json` and `.dot` extensions
+    layout_graph_path: PathBuf,
 }
 
 impl Default for DebugSettingsHandler {
     fn default() -> Self {
         DebugSettingsHandler {
             debug_on: false,
+            layout_graph_path: PathBuf::from("layout"),
         }
     }
 }
@@ -82,6 +94,20 @@ impl DebugSettingsHandler {
     pub fn new() -> Self {
         Self::default()
     }
+    /// Set where the layout graph is written, `layout.json` and `layout.dot` in the working directory by default
+    pub fn layout_graph_path<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.layout_graph_path = path.into();
+        self
+    }
+    fn write_layout_graph(&self, graph: LayoutGraph) {
+        for &(extension, ref contents) in &[("json", graph.to_json()), ("dot", graph.to_dot())] {
+            let path = self.layout_graph_path.with_extension(extension);
+            match File::create(&path).and_then(|mut file| file.write_all(contents.as_bytes())) {
+                Ok(()) => debug!("wrote layout graph to {:?}", path),
+                Err(err) => warn!("failed to write layout graph to {:?}: {}", path, err),
+            }
+        }
+    }
 }
 
 impl EventHandler<KeyboardInput> for DebugSettingsHandler {
@@ -99,6 +125,7 @@ impl EventHandler<KeyboardInput> for DebugSettingsHandler {
                 Some(glutin::VirtualKeyCode::F4) => ui.solver.debug_variables(),
                 Some(glutin::VirtualKeyCode::F5) => ui.render.toggle_flags(webrender::DebugFlags::PROFILER_DBG),
                 Some(glutin::VirtualKeyCode::F6) => ui.print_widgets(),
+                Some(glutin::VirtualKeyCode::F7) => self.write_layout_graph(ui.layout_graph()),
                 _ => {}
             }
         }
@@ -22,6 +22,9 @@ pub struct CursorLeftWindow;
 pub struct WidgetMouseWheel(pub glutin::MouseScrollDelta);
 #[derive(Debug, Copy, Clone)]
 pub struct WidgetMouseButton(pub glutin::ElementState, pub glutin::MouseButton);
+/// Sent, bubbling up, to the widget under the cursor whenever the mouse moves.
+#[derive(Debug, Copy, Clone)]
+pub struct WidgetMouseMoved(pub Point);
 
 #[derive(Debug, Copy, Clone)]
 pub struct ClickEvent {
@@ -56,6 +59,9 @@ impl MouseController {
         let &MouseMoved(mouse) = event;
         self.mouse = Some(mouse);
         self.check_widget_under_cursor(args);
+        if let Some(ref widget_under) = self.widget_under_mouse {
+            widget_under.event_bubble_up(WidgetMouseMoved(mouse));
+        }
     }
 
     fn mouse_left(&mut self, _: &CursorLeftWindow, args: EventArgs) {
@@ -1,11 +1,18 @@
+use std::collections::HashSet;
 use std::ops::DerefMut;
+use std::rc::Rc;
+use std::cell::Cell;
 
 use limn_layout::linear_layout::{LinearLayout, LinearLayoutSettings};
-use limn_layout::grid_layout::GridLayout;
+use limn_layout::grid_layout::{GridLayout, GridLayoutSettings};
+use limn_layout::flow_layout::{FlowLayout, FlowLayoutSettings};
+use limn_layout::stack_layout::{self, StackLayout};
+use limn_layout::direct::DirectContainer;
 
 use resources::WidgetId;
 
 use app::App;
+use ui::ChildrenUpdatedEvent;
 
 use widget::Widget;
 use event::EventArgs;
@@ -29,8 +36,88 @@ impl Widget {
         self.layout().set_container(container);
         self
     }
+
+    /// Set this widgets container to be a `GridLayout` with the given track sizes and gaps.
+    /// Children can be placed in specific cells by setting a `GridPlacement` as their
+    /// container params before they are added.
+    pub fn grid_layout(&mut self, settings: GridLayoutSettings) -> &mut Self {
+        let container = GridLayout::with_settings(self.layout().deref_mut(), settings);
+        self.layout().set_container(container);
+        self
+    }
+
+    /// Set this widgets container to be a `StackLayout`.
+    /// Children added to this widget will be placed on top of each other, only one of
+    /// them is shown at a time, selected with the `StackSelect` event.
+    pub fn stack_layout(&mut self) -> &mut Self {
+        let container = StackLayout::new(self.layout().deref_mut());
+        self.layout().set_container(container);
+        let selected = Rc::new(Cell::new(None));
+        {
+            let selected = selected.clone();
+            self.add_handler(move |_: &ChildrenUpdatedEvent, args: EventArgs| {
+                show_selected_child(&args.widget, selected.get());
+            });
+        }
+        self.add_handler(move |event: &StackSelect, args: EventArgs| {
+            selected.set(event.0);
+            show_selected_child(&args.widget, event.0);
+        });
+        self
+    }
+
+    /// Set this widgets container to be a `FlowLayout`.
+    /// Children added to this widget will be arranged in rows, wrapping when
+    /// the width of this widget is exceeded.
+    pub fn flow_layout(&mut self, settings: FlowLayoutSettings) -> &mut Self {
+        let container = FlowLayout::new(self.layout().deref_mut(), settings);
+        self.layout().set_container(container);
+        self
+    }
+
+    /// Set this widgets container to be a `DirectContainer`, eg. a `DirectLinearLayout`.
+    /// Children added to this widget are measured and arranged directly, rather than by the
+    /// constraint solver, unless they have constraints of their own.
+    pub fn direct_layout<T: DirectContainer + 'static>(&mut self, container: T) -> &mut Self {
+        self.layout().set_direct_container(container);
+        self
+    }
+
+    /// Set the layout direction of this widget and it's descendants, unless they set their own.
+    /// Leading and trailing constraints, and horizontal `LinearLayout`s are mirrored
+    /// in a right to left layout.
+    pub fn set_layout_direction(&mut self, direction: Direction) -> &mut Self {
+        self.layout().set_direction(direction);
+        for mut child in self.children() {
+            child.inherit_layout_direction(direction);
+        }
+        self
+    }
+
+    pub(crate) fn inherit_layout_direction(&mut self, direction: Direction) {
+        if self.layout().inherit_direction(direction) {
+            for mut child in self.children() {
+                child.inherit_layout_direction(direction);
+            }
+        }
+    }
 }
 
+/// Shows only the selected child of a widget with a `StackLayout`, or the last child added if `None`.
+#[derive(Debug, Copy, Clone)]
+pub struct StackSelect(pub Option<usize>);
+
+fn show_selected_child(widget: &Widget, selected: Option<usize>) {
+    let mut children = widget.children();
+    let mut guards: Vec<_> = children.iter_mut().map(|child| child.layout()).collect();
+    let mut layouts: Vec<&mut Layout> = guards.iter_mut().map(|guard| guard.deref_mut()).collect();
+    stack_layout::show_selected(&mut layouts, selected);
+}
+
+/// The most times in a row containers can change their constraints after a solve, eg. a `FlowLayout`
+/// moving children to a new row, before the layout is left as it is
+const MAX_REFLOWS: usize = 10;
+
 #[derive(Clone)]
 pub struct UpdateLayout(pub Widget);
 #[derive(Debug, Copy, Clone)]
@@ -49,17 +136,50 @@ impl App {
             let event = event.clone();
             let UpdateLayout(widget_ref) = event;
             let mut widget = widget_ref.widget_mut();
-            args.ui.solver.update_layout(&mut widget.layout);
+            // conflicting constraints are logged by the solver
+            args.ui.solver.update_layout(&mut widget.layout).ok();
             args.ui.check_layout_changes();
         });
-        self.add_handler(|event: &LayoutChanged, args: EventArgs| {
+        let mut reflows = 0;
+        self.add_handler(move |event: &LayoutChanged, args: EventArgs| {
             let changes = &event.0;
+            let mut changed_widgets = Vec::new();
             for &(widget_id, var, value) in changes {
                 let widget_id = WidgetId(widget_id);
                 if let Some(mut widget) = args.ui.get_widget(widget_id) {
                     debug!("{:?}: {:?} = {}", widget.name(), var, value);
                     widget.update_bounds(var, value as f32);
+                    if let Some(parent) = widget.parent() {
+                        changed_widgets.push(parent);
+                    }
+                    changed_widgets.push(widget);
+                }
+            }
+            // let containers that depend on the solved bounds update their constraints
+            let mut notified = HashSet::new();
+            let mut reflowed = Vec::new();
+            for widget in changed_widgets {
+                if notified.insert(widget.id()) {
+                    let pending_changes = {
+                        let layout = &mut widget.widget_mut().layout;
+                        layout.container_layout_changed(&args.ui.solver);
+                        layout.has_pending_changes()
+                    };
+                    if pending_changes {
+                        reflowed.push(widget);
+                    }
+                }
+            }
+            if reflowed.is_empty() {
+                reflows = 0;
+            } else if reflows < MAX_REFLOWS {
+                reflows += 1;
+                for widget in reflowed {
+                    args.ui.event(UpdateLayout(widget));
                 }
+            } else {
+                warn!("layout still changing after {} updates, leaving it as it is", MAX_REFLOWS);
+                reflows = 0;
             }
             // redraw everything when layout changes, for now
             args.ui.redraw();
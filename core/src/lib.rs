@@ -45,6 +45,8 @@ pub mod layout;
 
 pub mod app;
 pub mod ui;
+pub mod overlay;
+pub mod modal;
 pub mod geometry;
 /// Font, image and texture resources
 pub mod resources;
@@ -0,0 +1,145 @@
+//! Modal dialogs, shown in the overlay above a backdrop that dims the rest of the window.
+//!
+//! While a modal dialog is open, mouse input only reaches the dialog, and keyboard focus
+//! only moves between the focusable widgets inside it. A dialog is closed by sending it a `CloseModal`
+//! event, typically from one of it's buttons, after which it emits a `ModalClosed` event with the result.
+
+use webrender::api::PrimitiveInfo;
+
+use cassowary::strength::*;
+
+use app::App;
+use color::Color;
+use event::EventArgs;
+use geometry::Rect;
+use input::keyboard::{KeyboardInput, KeyboardInputEvent};
+use layout::constraint::*;
+use overlay::OverlayAnchor;
+use render::RenderBuilder;
+use style::Component;
+use ui::Ui;
+use widget::Widget;
+use widget::draw::Draw;
+
+use glutin;
+
+/// How a modal dialog was dismissed
+#[derive(Debug, Clone, PartialEq)]
+pub enum DialogResult {
+    Ok,
+    Cancel,
+    /// Any other outcome, eg. "Don't save"
+    Custom(String),
+}
+
+/// Closes the modal dialog that receives it, can be sent bubbling up from a widget inside the dialog
+#[derive(Debug, Clone)]
+pub struct CloseModal(pub DialogResult);
+
+/// Emitted by a modal dialog after it's closed
+#[derive(Debug, Clone)]
+pub struct ModalClosed(pub DialogResult);
+
+#[derive(Debug, Copy, Clone)]
+pub struct ModalSettings {
+    /// Close the dialog with `DialogResult::Cancel` when Escape is pressed
+    pub close_on_escape: bool,
+    /// Color drawn over the window behind the dialog
+    pub backdrop_color: Color,
+}
+
+impl Default for ModalSettings {
+    fn default() -> Self {
+        ModalSettings {
+            close_on_escape: true,
+            backdrop_color: Color(0x00000066),
+        }
+    }
+}
+
+pub(crate) struct Modal {
+    dialog: Widget,
+    backdrop: Widget,
+    settings: ModalSettings,
+}
+
+/// Fills the bounds of the backdrop widget with a translucent color
+#[derive(Debug, Clone)]
+struct BackdropState {
+    color: Color,
+}
+
+impl Component for BackdropState {
+    fn name() -> String {
+        "backdrop".to_owned()
+    }
+}
+
+impl Draw for BackdropState {
+    fn draw(&mut self, bounds: Rect, _: Rect, renderer: &mut RenderBuilder) {
+        renderer.builder.push_rect(&PrimitiveInfo::new(bounds), self.color.into());
+    }
+}
+
+impl Ui {
+    /// Shows `dialog` centered in the window, above everything else, with the default `ModalSettings`
+    pub fn show_modal(&mut self, dialog: Widget) {
+        self.show_modal_with(dialog, ModalSettings::default());
+    }
+
+    /// Shows `dialog` centered in the window, above everything else, and blocks input to all other widgets
+    /// until the dialog is closed
+    pub fn show_modal_with(&mut self, mut dialog: Widget, settings: ModalSettings) {
+        let overlay = self.get_overlay();
+        // the backdrop is under the cursor everywhere outside the dialog, so it receives the mouse input
+        let mut backdrop = Widget::new("modal_backdrop");
+        backdrop.set_draw_state(BackdropState { color: settings.backdrop_color });
+        self.add_overlay(backdrop.clone(), OverlayAnchor::Fill);
+
+        dialog.add_handler(|event: &CloseModal, mut args: EventArgs| {
+            args.ui.close_modal(&args.widget, event.0.clone());
+            *args.handled = true;
+        });
+        dialog.layout().add(center(&overlay));
+        dialog.layout().add(bound_by(&overlay).strength(STRONG));
+        self.add_overlay(dialog.clone(), OverlayAnchor::None);
+        self.event(KeyboardInputEvent::PushFocusTrap(dialog.clone()));
+        self.modals.push(Modal {
+            dialog: dialog,
+            backdrop: backdrop,
+            settings: settings,
+        });
+    }
+
+    /// Closes a modal dialog, if it's open, and emits `ModalClosed` from the dialog
+    pub fn close_modal(&mut self, dialog: &Widget, result: DialogResult) {
+        if let Some(index) = self.modals.iter().position(|modal| modal.dialog == *dialog) {
+            let mut modal = self.modals.remove(index);
+            modal.backdrop.remove_widget();
+            modal.dialog.remove_widget();
+            self.event(KeyboardInputEvent::PopFocusTrap(modal.dialog.clone()));
+            modal.dialog.event(ModalClosed(result));
+        }
+    }
+
+    /// True if any modal dialogs are open
+    pub fn has_modal(&self) -> bool {
+        !self.modals.is_empty()
+    }
+}
+
+impl App {
+    pub fn add_modal_handlers(&mut self) {
+        self.add_handler(|event: &KeyboardInput, args: EventArgs| {
+            let &KeyboardInput(input) = event;
+            if input.state == glutin::ElementState::Pressed && input.virtual_keycode == Some(glutin::VirtualKeyCode::Escape) {
+                if let Some(modal) = args.ui.modals.last() {
+                    if modal.settings.close_on_escape {
+                        // closed by an event, so other handlers of this key press still see the dialog as open
+                        modal.dialog.event(CloseModal(DialogResult::Cancel));
+                    }
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,211 @@
+//! An overlay layer above the widget tree, for popups, menus, tooltips and drag previews.
+//!
+//! Widgets added with `Ui::add_overlay` are children of the overlay widget, which covers the window
+//! and is drawn after, and hit tested before, the root widget. They aren't clipped by the bounds of any
+//! other widget. Each is positioned relative to an `OverlayAnchor`, and flipped to the other side of the anchor
+//! if it would extend past the edge of the window. Remove them with `Widget::remove_widget`.
+//!
+//! Popups that Escape closes, like menus, are added with `Ui::add_popup`, so Escape doesn't
+//! also close the window. Other overlay widgets, like tooltips, don't affect Escape.
+
+use cassowary::Constraint;
+use cassowary::strength::*;
+use cassowary::WeightedRelation::*;
+
+use event::{EventHandler, EventArgs};
+use geometry::{Point, Rect, RectExt, Size};
+use layout::{LayoutUpdated, LayoutVars};
+use ui::Ui;
+use widget::Widget;
+
+/// Side of the anchor widget an overlay is placed on
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Placement {
+    /// Below the anchor, aligned to it's left edge
+    Below,
+    /// Above the anchor, aligned to it's left edge
+    Above,
+    /// Left of the anchor, aligned to it's top edge
+    LeftOf,
+    /// Right of the anchor, aligned to it's top edge
+    RightOf,
+}
+
+impl Placement {
+    fn flipped(&self) -> Self {
+        match *self {
+            Placement::Below => Placement::Above,
+            Placement::Above => Placement::Below,
+            Placement::LeftOf => Placement::RightOf,
+            Placement::RightOf => Placement::LeftOf,
+        }
+    }
+}
+
+/// Position of a widget added to the overlay
+#[derive(Debug, Clone)]
+pub enum OverlayAnchor {
+    /// Next to a widget in the widget tree, eg. the popup list of a combo box.
+    /// Flipped to the opposite side of the widget if there isn't room on the given side.
+    Widget(Widget, Placement),
+    /// With it's top left corner at a point in window coordinates, eg. a context menu at the cursor.
+    /// Flipped horizontally and vertically, so the point is at one of it's other corners,
+    /// if there isn't room to the right or below the point.
+    Point(Point),
+    /// Covering the whole window
+    Fill,
+    /// No constraints are added, the widget is positioned by it's own constraints
+    None,
+}
+
+impl Ui {
+    /// Adds a widget to the overlay layer, above all other widgets, positioned relative to `anchor`
+    pub fn add_overlay(&mut self, mut widget: Widget, anchor: OverlayAnchor) {
+        let mut overlay = self.overlay.clone();
+        let mut placement = OverlayPlacement {
+            anchor: anchor,
+            overlay: overlay.clone(),
+            flipped: (false, false),
+            constraints: Vec::new(),
+        };
+        placement.place(&mut widget);
+        widget.add_handler(placement);
+        overlay.add_child(widget);
+    }
+
+    /// Adds a widget to the overlay layer like `add_overlay`, for popups that close when
+    /// clicked outside of, or on Escape, eg. an open menu or combo box list
+    pub fn add_popup(&mut self, widget: Widget, anchor: OverlayAnchor) {
+        let overlay = self.overlay.clone();
+        self.popups.retain(|id| overlay.children().iter().any(|child| child.id() == *id));
+        self.popups.insert(widget.id());
+        self.add_overlay(widget, anchor);
+    }
+
+    /// True if any widgets added with `add_popup` are still in the overlay
+    pub fn has_popup(&self) -> bool {
+        self.overlay.children().iter().any(|child| self.popups.contains(&child.id()))
+    }
+
+    /// The widget all overlay widgets are added to, it covers the window but is never under the cursor
+    pub fn get_overlay(&self) -> Widget {
+        self.overlay.clone()
+    }
+}
+
+/// Adds the constraints that position an overlay widget, and flips them when the widget is resized
+/// such that it no longer fits in the window
+struct OverlayPlacement {
+    anchor: OverlayAnchor,
+    overlay: Widget,
+    /// Whether the widget is flipped horizontally and vertically
+    flipped: (bool, bool),
+    constraints: Vec<Constraint>,
+}
+
+impl OverlayPlacement {
+    fn place(&mut self, widget: &mut Widget) {
+        let vars = widget.layout_vars();
+        let window = self.overlay.layout_vars();
+        let (flip_horizontal, flip_vertical) = self.flipped;
+        let mut constraints = Vec::new();
+        match self.anchor {
+            OverlayAnchor::Widget(ref anchor, placement) => {
+                let anchor = anchor.layout_vars();
+                let placement = if flip_horizontal || flip_vertical { placement.flipped() } else { placement };
+                match placement {
+                    Placement::Below => constraints.push(vars.top | EQ(STRONG) | anchor.bottom),
+                    Placement::Above => constraints.push(vars.bottom | EQ(STRONG) | anchor.top),
+                    Placement::LeftOf => constraints.push(vars.right | EQ(STRONG) | anchor.left),
+                    Placement::RightOf => constraints.push(vars.left | EQ(STRONG) | anchor.right),
+                }
+                match placement {
+                    Placement::Below | Placement::Above => {
+                        constraints.push(vars.left | EQ(MEDIUM) | anchor.left);
+                        constraints.extend(bound_horizontal(vars, window));
+                    }
+                    Placement::LeftOf | Placement::RightOf => {
+                        constraints.push(vars.top | EQ(MEDIUM) | anchor.top);
+                        constraints.extend(bound_vertical(vars, window));
+                    }
+                }
+            }
+            OverlayAnchor::Point(point) => {
+                let x = point.x as f64;
+                let y = point.y as f64;
+                constraints.push(if flip_horizontal { vars.right | EQ(MEDIUM) | x } else { vars.left | EQ(MEDIUM) | x });
+                constraints.push(if flip_vertical { vars.bottom | EQ(MEDIUM) | y } else { vars.top | EQ(MEDIUM) | y });
+                constraints.extend(bound_horizontal(vars, window));
+                constraints.extend(bound_vertical(vars, window));
+            }
+            OverlayAnchor::Fill => {
+                constraints.push(vars.left | EQ(REQUIRED) | window.left);
+                constraints.push(vars.top | EQ(REQUIRED) | window.top);
+                constraints.push(vars.right | EQ(REQUIRED) | window.right);
+                constraints.push(vars.bottom | EQ(REQUIRED) | window.bottom);
+            }
+            OverlayAnchor::None => (),
+        }
+        let mut layout = widget.layout();
+        layout.remove_constraints(::std::mem::replace(&mut self.constraints, Vec::new()));
+        layout.add(constraints.clone());
+        self.constraints = constraints;
+    }
+
+    /// Which axes the widget should be flipped on, given it's current size
+    fn should_flip(&self, size: Size) -> (bool, bool) {
+        let window = self.overlay.bounds();
+        match self.anchor {
+            OverlayAnchor::Widget(ref anchor, placement) => {
+                let anchor = anchor.bounds();
+                // only flip if it fits better on the other side
+                let fits = |placement| fits_beside(anchor, placement, size, window);
+                let flip = !fits(placement) && fits(placement.flipped());
+                match placement {
+                    Placement::Below | Placement::Above => (false, flip),
+                    Placement::LeftOf | Placement::RightOf => (flip, false),
+                }
+            }
+            OverlayAnchor::Point(point) => {
+                let flip_horizontal = point.x + size.width > window.right() && point.x - size.width >= window.left();
+                let flip_vertical = point.y + size.height > window.bottom() && point.y - size.height >= window.top();
+                (flip_horizontal, flip_vertical)
+            }
+            OverlayAnchor::Fill | OverlayAnchor::None => (false, false),
+        }
+    }
+}
+
+fn fits_beside(anchor: Rect, placement: Placement, size: Size, window: Rect) -> bool {
+    match placement {
+        Placement::Below => anchor.bottom() + size.height <= window.bottom(),
+        Placement::Above => anchor.top() - size.height >= window.top(),
+        Placement::LeftOf => anchor.left() - size.width >= window.left(),
+        Placement::RightOf => anchor.right() + size.width <= window.right(),
+    }
+}
+
+fn bound_horizontal(vars: LayoutVars, window: LayoutVars) -> Vec<Constraint> {
+    vec![
+        vars.left | GE(STRONG) | window.left,
+        vars.right | LE(STRONG) | window.right,
+    ]
+}
+
+fn bound_vertical(vars: LayoutVars, window: LayoutVars) -> Vec<Constraint> {
+    vec![
+        vars.top | GE(STRONG) | window.top,
+        vars.bottom | LE(STRONG) | window.bottom,
+    ]
+}
+
+impl EventHandler<LayoutUpdated> for OverlayPlacement {
+    fn handle(&mut self, _: &LayoutUpdated, mut args: EventArgs) {
+        // the size of the widget doesn't depend on which side it's on, so this doesn't oscillate
+        let flipped = self.should_flip(args.widget.bounds().size);
+        if flipped != self.flipped {
+            self.flipped = flipped;
+            self.place(&mut args.widget);
+        }
+    }
+}
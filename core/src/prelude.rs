@@ -22,6 +22,8 @@ pub use resources::id::{Id, IdGen};
 pub use resources::image::ImageSource;
 pub use ui::Ui;
 pub use ui::{WidgetAttachedEvent, WidgetDetachedEvent};
+pub use overlay::{OverlayAnchor, Placement};
+pub use modal::{DialogResult, CloseModal, ModalClosed, ModalSettings};
 pub use app::{App, FrameEvent};
 pub use window::Window;
 pub use color::*;
@@ -30,9 +32,13 @@ pub use layout::*;
 pub use layout::constraint::*;
 pub use layout::LAYOUT;
 pub use layout::linear_layout::{LinearLayoutSettings, Orientation, Spacing, ItemAlignment};
+pub use layout::flow_layout::{FlowLayoutSettings, RowAlignment};
+pub use layout::grid_layout::{GridLayoutSettings, GridPlacement, TrackSize};
+pub use layout::stack_layout::{StackItem, Anchor};
+pub use layout::direct::{DirectLinearLayout, DirectGridLayout, DirectStackLayout};
 pub use text_layout::{Align, Wrap};
 
-pub use input::mouse::{ClickEvent, WidgetMouseButton, WidgetMouseWheel};
+pub use input::mouse::{ClickEvent, WidgetMouseButton, WidgetMouseWheel, WidgetMouseMoved};
 pub use input::drag::{DragEvent, DragState};
 pub use input::keyboard::{WidgetReceivedCharacter, KeyboardInputEvent, KeyboardInput};
 
@@ -1,5 +1,7 @@
 use std::collections::HashMap;
-use std::io;
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
 
 use failure::Error;
 use rusttype;
@@ -8,19 +10,166 @@ use app_units;
 use webrender::api::{RenderApi, ResourceUpdates, FontKey, FontInstanceKey};
 
 use text_layout;
+use super::sfnt;
 
 pub type Font = rusttype::Font<'static>;
 
 pub struct FontInfo {
     pub key: FontKey,
     pub info: Font,
+    pub decorations: DecorationMetrics,
+}
+
+/// Position and thickness of line decorations, as a fraction of the font size.
+/// Positions are the distance of the top of the line above the baseline,
+/// so underline positions are usually negative.
+#[derive(Clone, Copy, Debug)]
+pub struct DecorationMetrics {
+    pub underline_position: f32,
+    pub underline_thickness: f32,
+    pub strikeout_position: f32,
+    pub strikeout_thickness: f32,
+}
+
+impl Default for DecorationMetrics {
+    fn default() -> Self {
+        DecorationMetrics {
+            underline_position: -0.1,
+            underline_thickness: 0.05,
+            strikeout_position: 0.3,
+            strikeout_thickness: 0.05,
+        }
+    }
+}
+
+/// Numeric weight of a font face, from 100 (thinnest) to 900 (heaviest)
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
+pub struct FontWeight(pub u16);
+
+impl FontWeight {
+    pub const THIN: FontWeight = FontWeight(100);
+    pub const EXTRA_LIGHT: FontWeight = FontWeight(200);
+    pub const LIGHT: FontWeight = FontWeight(300);
+    pub const NORMAL: FontWeight = FontWeight(400);
+    pub const MEDIUM: FontWeight = FontWeight(500);
+    pub const SEMI_BOLD: FontWeight = FontWeight(600);
+    pub const BOLD: FontWeight = FontWeight(700);
+    pub const EXTRA_BOLD: FontWeight = FontWeight(800);
+    pub const BLACK: FontWeight = FontWeight(900);
+
+    /// How far `self` is from the `desired` weight, following the CSS font matching rules:
+    /// for desired weights between 400 and 500, heavier weights up to 500 are tried first,
+    /// then lighter weights, then heavier. Lighter weights are preferred for desired weights
+    /// below 400, heavier weights for desired weights above 500.
+    fn distance(&self, desired: FontWeight) -> u32 {
+        let (weight, desired) = (u32::from(self.0), u32::from(desired.0));
+        if weight == desired {
+            0
+        } else if desired >= 400 && desired <= 500 {
+            if weight > desired && weight <= 500 {
+                weight - desired
+            } else if weight < desired {
+                1000 + desired - weight
+            } else {
+                2000 + weight - desired
+            }
+        } else if desired < 400 {
+            if weight < desired { desired - weight } else { 1000 + weight - desired }
+        } else if weight > desired {
+            weight - desired
+        } else {
+            1000 + desired - weight
+        }
+    }
+}
+
+impl Default for FontWeight {
+    fn default() -> Self {
+        FontWeight::NORMAL
+    }
+}
+
+/// Width of a font face, relative to it's normal width
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
+pub enum FontStretch {
+    UltraCondensed = 1,
+    ExtraCondensed = 2,
+    Condensed = 3,
+    SemiCondensed = 4,
+    Normal = 5,
+    SemiExpanded = 6,
+    Expanded = 7,
+    ExtraExpanded = 8,
+    UltraExpanded = 9,
+}
+
+impl FontStretch {
+    /// How far `self` is from the `desired` stretch, narrower faces are preferred when the
+    /// desired stretch is normal or condensed, wider faces otherwise.
+    fn distance(&self, desired: FontStretch) -> u32 {
+        let (stretch, desired) = (*self as u32, desired as u32);
+        if stretch == desired {
+            0
+        } else if desired <= FontStretch::Normal as u32 {
+            if stretch < desired { desired - stretch } else { 10 + stretch - desired }
+        } else if stretch > desired {
+            stretch - desired
+        } else {
+            10 + desired - stretch
+        }
+    }
+}
+
+impl Default for FontStretch {
+    fn default() -> Self {
+        FontStretch::Normal
+    }
+}
+
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum FontStyle {
+    Normal,
+    Italic,
+    Oblique,
+}
+
+impl FontStyle {
+    /// How far `self` is from the `desired` style, italic and oblique faces are used
+    /// in place of each other before falling back to a normal face.
+    fn distance(&self, desired: FontStyle) -> u32 {
+        match (desired, *self) {
+            (FontStyle::Normal, FontStyle::Normal) |
+            (FontStyle::Italic, FontStyle::Italic) |
+            (FontStyle::Oblique, FontStyle::Oblique) => 0,
+            (FontStyle::Normal, FontStyle::Oblique) |
+            (FontStyle::Italic, FontStyle::Oblique) |
+            (FontStyle::Oblique, FontStyle::Italic) => 1,
+            _ => 2,
+        }
+    }
+}
+
+impl Default for FontStyle {
+    fn default() -> Self {
+        FontStyle::Normal
+    }
 }
 
 /// Set of properties used to specify a font
+///
+/// If no font matches exactly, the nearest face of the same family is used,
+/// matching stretch first, then style, then weight.
 #[derive(Default, PartialEq, Eq, Hash, Clone, Debug)]
 pub struct FontDescriptor {
     pub family_name: String,
+    pub weight: FontWeight,
+    pub stretch: FontStretch,
+    pub style: FontStyle,
+    /// Selects an italic face if `style` is `FontStyle::Normal`
+    #[deprecated(note = "use `style` instead")]
     pub italic: bool,
+    /// Selects a bold face if `weight` is lighter than `FontWeight::BOLD`
+    #[deprecated(note = "use `weight` instead")]
     pub bold: bool,
 }
 
@@ -31,23 +180,67 @@ impl FontDescriptor {
             ..FontDescriptor::default()
         }
     }
+    pub fn weight(mut self, weight: FontWeight) -> Self {
+        self.weight = weight;
+        self
+    }
+    pub fn stretch(mut self, stretch: FontStretch) -> Self {
+        self.stretch = stretch;
+        self
+    }
+    pub fn style(mut self, style: FontStyle) -> Self {
+        self.style = style;
+        self
+    }
+    /// Is this a face of the same family as `other`
+    fn same_family(&self, other: &FontDescriptor) -> bool {
+        self.family_name.eq_ignore_ascii_case(&other.family_name)
+    }
+    /// The style to match, including the deprecated `italic` flag
+    #[allow(deprecated)]
+    fn matched_style(&self) -> FontStyle {
+        if self.italic && self.style == FontStyle::Normal { FontStyle::Italic } else { self.style }
+    }
+    /// The weight to match, including the deprecated `bold` flag
+    #[allow(deprecated)]
+    fn matched_weight(&self) -> FontWeight {
+        if self.bold && self.weight < FontWeight::BOLD { FontWeight::BOLD } else { self.weight }
+    }
+    /// How closely this face matches the `desired` face, lower is better
+    fn match_distance(&self, desired: &FontDescriptor) -> (u32, u32, u32) {
+        (self.stretch.distance(desired.stretch),
+         self.matched_style().distance(desired.matched_style()),
+         self.matched_weight().distance(desired.matched_weight()))
+    }
     fn property(&self) -> FontProperty {
         let mut builder = FontPropertyBuilder::new().family(&self.family_name);
-        if self.italic {
-            builder = builder.italic();
+        match self.matched_style() {
+            FontStyle::Italic => builder = builder.italic(),
+            FontStyle::Oblique => builder = builder.oblique(),
+            FontStyle::Normal => (),
         }
-        if self.bold {
+        // system font lookup only distinguishes regular and bold faces
+        if self.matched_weight() >= FontWeight::SEMI_BOLD {
             builder = builder.bold();
         }
         builder.build()
     }
 }
 
+/// Location of a font face that has been indexed, but not yet loaded
+#[derive(Clone, Debug)]
+pub struct FontSource {
+    pub path: PathBuf,
+    /// Index of the face within the file, non zero only for font collections
+    pub index: u32,
+}
+
 #[derive(Default)]
 pub struct FontLoader {
     pub render: Option<RenderApi>,
     pub font_info: HashMap<FontDescriptor, FontInfo>,
     pub bundled_font_info: HashMap<FontDescriptor, FontInfo>,
+    pub font_index: HashMap<FontDescriptor, FontSource>,
     pub font_instances: HashMap<(FontDescriptor, app_units::Au), FontInstanceKey>,
 }
 
@@ -56,13 +249,25 @@ impl FontLoader {
         FontLoader::default()
     }
 
+    /// Get the font matching `descriptor`, or the nearest match from the same family.
+    /// Bundled and indexed fonts are searched first, then system fonts.
     pub fn get_font(&mut self, descriptor: &FontDescriptor) -> Result<&FontInfo, Error> {
-        if self.bundled_font_info.contains_key(descriptor) {
-            Ok(&self.bundled_font_info[descriptor])
+        if let Some(bundled) = self.nearest_bundled_font(descriptor) {
+            if !self.bundled_font_info.contains_key(&bundled) {
+                let source = self.font_index[&bundled].clone();
+                let data = read_file(&source.path)?;
+                let font_info = self.load_font(data, source.index)?;
+                self.bundled_font_info.insert(bundled.clone(), font_info);
+            }
+            Ok(&self.bundled_font_info[&bundled])
         } else {
             if !self.font_info.contains_key(descriptor) {
-                if let Ok(data) = system_fonts_load_data(&descriptor.property()) {
-                    let font_info = self.load_font(data)?;
+                let data = system_fonts_load_data(&descriptor.property()).or_else(|_| {
+                    // fall back to the regular face of the family
+                    system_fonts_load_data(&FontDescriptor::from_family(&descriptor.family_name).property())
+                });
+                if let Ok(data) = data {
+                    let font_info = self.load_font(data, 0)?;
                     self.font_info.insert(descriptor.clone(), font_info);
                 } else {
                     return Err(io::Error::new(io::ErrorKind::InvalidData, "No system font found").into());
@@ -72,6 +277,17 @@ impl FontLoader {
         }
     }
 
+    /// Find the registered or indexed font that best matches `descriptor`
+    fn nearest_bundled_font(&self, descriptor: &FontDescriptor) -> Option<FontDescriptor> {
+        if self.bundled_font_info.contains_key(descriptor) || self.font_index.contains_key(descriptor) {
+            return Some(descriptor.clone());
+        }
+        self.bundled_font_info.keys().chain(self.font_index.keys())
+            .filter(|candidate| candidate.same_family(descriptor))
+            .min_by_key(|candidate| candidate.match_distance(descriptor))
+            .cloned()
+    }
+
     pub fn get_font_instance(&mut self, descriptor: &FontDescriptor, font_size: f32) -> Result<&FontInstanceKey, Error> {
         let font_key = self.get_font(descriptor)?.key;
         let size = app_units::Au::from_f32_px(text_layout::px_to_pt(font_size));
@@ -83,27 +299,91 @@ impl FontLoader {
         Ok(&self.font_instances[&key])
     }
 
-    fn load_font(&mut self, data: Vec<u8>) -> Result<FontInfo, Error> {
-        let font_info = rusttype_load_font_info(data.clone())?;
-        let key = webrender_load_font(self.render_api(), data)?;
-        Ok(FontInfo { key: key, info: font_info })
+    /// Load the face at `index` in the font data, `index` is non zero only for font collections
+    fn load_font(&mut self, data: Vec<u8>, index: u32) -> Result<FontInfo, Error> {
+        let font_info = rusttype_load_font_info(data.clone(), index)?;
+        let decorations = sfnt::face_offsets(&data).get(index as usize)
+            .map(|&offset| sfnt::decoration_metrics(&data, offset))
+            .unwrap_or_default();
+        let key = webrender_load_font(self.render_api(), data, index)?;
+        Ok(FontInfo { key: key, info: font_info, decorations: decorations })
     }
 
     pub fn register_font_data(&mut self, descriptor: FontDescriptor, data: Vec<u8>) -> Result<(), Error> {
-        let info = self.load_font(data)?;
+        let info = self.load_font(data, 0)?;
         self.bundled_font_info.insert(descriptor.clone(), info);
         Ok(())
     }
 
+    /// Index every face of the font files (.ttf, .otf) and font collections (.ttc, .otc)
+    /// in `dir` and it's subdirectories, by family, weight, stretch and style.
+    /// Faces are loaded the first time they're used. Files and subdirectories that can't be read
+    /// are logged and skipped. Returns the number of faces indexed.
+    pub fn register_font_dir<P: AsRef<Path>>(&mut self, dir: P) -> Result<usize, Error> {
+        let mut count = 0;
+        for entry in fs::read_dir(dir)? {
+            let path = match entry {
+                Ok(entry) => entry.path(),
+                Err(err) => {
+                    warn!("failed to read font directory entry: {}", err);
+                    continue;
+                }
+            };
+            let result = if path.is_dir() {
+                self.register_font_dir(&path)
+            } else if is_font_file(&path) {
+                self.register_font_file(&path)
+            } else {
+                continue;
+            };
+            match result {
+                Ok(faces) => count += faces,
+                Err(err) => warn!("skipping fonts in {:?}: {}", path, err),
+            }
+        }
+        Ok(count)
+    }
+
+    /// Index every face in a font file or font collection, returns the number of faces indexed.
+    pub fn register_font_file<P: AsRef<Path>>(&mut self, path: P) -> Result<usize, Error> {
+        let path = path.as_ref();
+        let data = read_file(path)?;
+        let mut count = 0;
+        for (index, offset) in sfnt::face_offsets(&data).into_iter().enumerate() {
+            if let Some(descriptor) = sfnt::face_descriptor(&data, offset) {
+                debug!("indexed font {:?} in {:?}", descriptor, path);
+                self.font_index.entry(descriptor).or_insert(FontSource {
+                    path: path.to_owned(),
+                    index: index as u32,
+                });
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
     fn render_api(&self) -> &RenderApi {
         self.render.as_ref().unwrap()
     }
 }
 
-fn webrender_load_font(render_api: &RenderApi, data: Vec<u8>) -> Result<FontKey, io::Error> {
+fn is_font_file(path: &Path) -> bool {
+    path.extension().and_then(|ext| ext.to_str()).map(|ext| {
+        let ext = ext.to_lowercase();
+        ext == "ttf" || ext == "otf" || ext == "ttc" || ext == "otc"
+    }).unwrap_or(false)
+}
+
+fn read_file(path: &Path) -> Result<Vec<u8>, io::Error> {
+    let mut data = Vec::new();
+    File::open(path)?.read_to_end(&mut data)?;
+    Ok(data)
+}
+
+fn webrender_load_font(render_api: &RenderApi, data: Vec<u8>, index: u32) -> Result<FontKey, io::Error> {
     let key = render_api.generate_font_key();
     let mut resources = ResourceUpdates::new();
-    resources.add_raw_font(key, data, 0);
+    resources.add_raw_font(key, data, index);
     render_api.update_resources(resources);
     Ok(key)
 }
@@ -124,8 +404,98 @@ fn system_fonts_load_data(property: &FontProperty) -> Result<Vec<u8>, io::Error>
 }
 
 /// Read font data to get font information, v_metrics, glyph info etc.
-fn rusttype_load_font_info(data: Vec<u8>) -> Result<Font, io::Error> {
+fn rusttype_load_font_info(data: Vec<u8>, index: u32) -> Result<Font, io::Error> {
     let collection = rusttype::FontCollection::from_bytes(data);
     let mut font_iter = collection.into_fonts();
-    font_iter.next().ok_or(io::Error::new(io::ErrorKind::InvalidData, "Bad font format"))
+    font_iter.nth(index as usize).ok_or(io::Error::new(io::ErrorKind::InvalidData, "Bad font format"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The face chosen for `desired`, from indexed faces of the "Test" family
+    fn nearest(faces: &[FontDescriptor], desired: FontDescriptor) -> FontDescriptor {
+        let mut loader = FontLoader::new();
+        for face in faces {
+            loader.font_index.insert(face.clone(), FontSource { path: PathBuf::new(), index: 0 });
+        }
+        loader.nearest_bundled_font(&desired).unwrap()
+    }
+
+    fn face() -> FontDescriptor {
+        FontDescriptor::from_family("Test")
+    }
+
+    fn weights(weights: &[u16]) -> Vec<FontDescriptor> {
+        weights.iter().map(|&weight| face().weight(FontWeight(weight))).collect()
+    }
+
+    #[test]
+    fn light_weight_prefers_lighter() {
+        let faces = weights(&[300, 400, 500]);
+        assert_eq!(nearest(&faces, face().weight(FontWeight(350))), face().weight(FontWeight(300)));
+        let faces = weights(&[400, 500]);
+        assert_eq!(nearest(&faces, face().weight(FontWeight(350))), face().weight(FontWeight(400)));
+    }
+
+    #[test]
+    fn normal_weight_prefers_up_to_500() {
+        let faces = weights(&[300, 500, 600]);
+        assert_eq!(nearest(&faces, face().weight(FontWeight(450))), face().weight(FontWeight(500)));
+        // then lighter, then heavier
+        let faces = weights(&[300, 600]);
+        assert_eq!(nearest(&faces, face().weight(FontWeight(450))), face().weight(FontWeight(300)));
+        let faces = weights(&[600, 700]);
+        assert_eq!(nearest(&faces, face().weight(FontWeight(400))), face().weight(FontWeight(600)));
+    }
+
+    #[test]
+    fn heavy_weight_prefers_heavier() {
+        let faces = weights(&[500, 900]);
+        assert_eq!(nearest(&faces, face().weight(FontWeight(600))), face().weight(FontWeight(900)));
+        let faces = weights(&[300, 500]);
+        assert_eq!(nearest(&faces, face().weight(FontWeight(600))), face().weight(FontWeight(500)));
+    }
+
+    #[test]
+    fn italic_falls_back_to_oblique() {
+        let faces = vec![face(), face().style(FontStyle::Oblique)];
+        assert_eq!(nearest(&faces, face().style(FontStyle::Italic)), face().style(FontStyle::Oblique));
+        let faces = vec![face(), face().style(FontStyle::Italic)];
+        assert_eq!(nearest(&faces, face().style(FontStyle::Oblique)), face().style(FontStyle::Italic));
+        let faces = vec![face().style(FontStyle::Italic), face().style(FontStyle::Oblique)];
+        assert_eq!(nearest(&faces, face()), face().style(FontStyle::Oblique));
+    }
+
+    #[test]
+    fn stretch_prefers_narrower_when_condensed() {
+        let faces = vec![face().stretch(FontStretch::UltraCondensed), face().stretch(FontStretch::SemiCondensed)];
+        assert_eq!(nearest(&faces, face().stretch(FontStretch::Condensed)),
+                   face().stretch(FontStretch::UltraCondensed));
+        let faces = vec![face().stretch(FontStretch::SemiExpanded), face().stretch(FontStretch::UltraExpanded)];
+        assert_eq!(nearest(&faces, face().stretch(FontStretch::Expanded)),
+                   face().stretch(FontStretch::UltraExpanded));
+    }
+
+    #[test]
+    fn stretch_then_style_then_weight() {
+        let faces = vec![
+            face().stretch(FontStretch::Condensed).style(FontStyle::Italic).weight(FontWeight::BOLD),
+            face().style(FontStyle::Normal).weight(FontWeight::BOLD),
+            face().style(FontStyle::Italic),
+        ];
+        let desired = face().style(FontStyle::Italic).weight(FontWeight::BOLD);
+        assert_eq!(nearest(&faces, desired), face().style(FontStyle::Italic));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn deprecated_flags() {
+        let faces = vec![face(), face().weight(FontWeight::BOLD), face().style(FontStyle::Italic)];
+        let bold = FontDescriptor { bold: true, ..face() };
+        assert_eq!(nearest(&faces, bold), face().weight(FontWeight::BOLD));
+        let italic = FontDescriptor { italic: true, ..face() };
+        assert_eq!(nearest(&faces, italic), face().style(FontStyle::Italic));
+    }
 }
@@ -2,6 +2,7 @@
 pub mod id;
 pub mod font;
 pub mod image;
+mod sfnt;
 
 use std::sync::{Mutex, MutexGuard};
 use std::collections::HashMap;
@@ -0,0 +1,239 @@
+//! Minimal parsing of TrueType/OpenType font data, enough to index the faces in a font file
+//! or collection by family, weight, stretch and style without loading them.
+
+use super::font::{FontDescriptor, FontWeight, FontStretch, FontStyle, DecorationMetrics};
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    data.get(offset..offset + 2).map(|bytes| (u16::from(bytes[0]) << 8) | u16::from(bytes[1]))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    let high = read_u16(data, offset)?;
+    let low = read_u16(data, offset + 2)?;
+    Some((u32::from(high) << 16) | u32::from(low))
+}
+
+fn read_i16(data: &[u8], offset: usize) -> Option<i16> {
+    read_u16(data, offset).map(|value| value as i16)
+}
+
+/// Offset of each face in the font data, every face for a collection (.ttc), otherwise just the one
+pub fn face_offsets(data: &[u8]) -> Vec<usize> {
+    if data.get(0..4) == Some(&b"ttcf"[..]) {
+        let num_fonts = read_u32(data, 8).unwrap_or(0) as usize;
+        (0..num_fonts).filter_map(|index| read_u32(data, 12 + index * 4))
+            .map(|offset| offset as usize).collect()
+    } else {
+        vec![0]
+    }
+}
+
+/// Find the offset of a table in the face starting at `face_offset`
+fn find_table(data: &[u8], face_offset: usize, tag: &[u8; 4]) -> Option<usize> {
+    let num_tables = read_u16(data, face_offset + 4)? as usize;
+    (0..num_tables).map(|index| face_offset + 12 + index * 16)
+        .find(|&record| data.get(record..record + 4) == Some(&tag[..]))
+        .and_then(|record| read_u32(data, record + 8))
+        .map(|offset| offset as usize)
+}
+
+/// Read the properties of the face starting at `face_offset`, `None` if it has no family name
+pub fn face_descriptor(data: &[u8], face_offset: usize) -> Option<FontDescriptor> {
+    let mut descriptor = FontDescriptor::from_family(&family_name(data, face_offset)?);
+    if let Some(os2) = find_table(data, face_offset, b"OS/2") {
+        if let Some(weight) = read_u16(data, os2 + 4) {
+            descriptor.weight = FontWeight(weight);
+        }
+        if let Some(width) = read_u16(data, os2 + 6) {
+            descriptor.stretch = stretch_from_width_class(width);
+        }
+        if let Some(selection) = read_u16(data, os2 + 62) {
+            descriptor.style = if selection & (1 << 9) != 0 {
+                FontStyle::Oblique
+            } else if selection & 1 != 0 {
+                FontStyle::Italic
+            } else {
+                FontStyle::Normal
+            };
+        }
+    }
+    Some(descriptor)
+}
+
+/// Read the underline and strikeout metrics of the face starting at `face_offset`
+/// from the `post` and `OS/2` tables, using defaults for any that are missing
+pub fn decoration_metrics(data: &[u8], face_offset: usize) -> DecorationMetrics {
+    let mut metrics = DecorationMetrics::default();
+    let units_per_em = find_table(data, face_offset, b"head")
+        .and_then(|head| read_u16(data, head + 18))
+        .map(f32::from);
+    let units_per_em = match units_per_em {
+        Some(units_per_em) if units_per_em > 0.0 => units_per_em,
+        _ => return metrics,
+    };
+    let read_em = |offset: usize| read_i16(data, offset).map(|value| f32::from(value) / units_per_em);
+    if let Some(post) = find_table(data, face_offset, b"post") {
+        if let (Some(position), Some(thickness)) = (read_em(post + 8), read_em(post + 10)) {
+            if thickness > 0.0 {
+                metrics.underline_position = position;
+                metrics.underline_thickness = thickness;
+            }
+        }
+    }
+    if let Some(os2) = find_table(data, face_offset, b"OS/2") {
+        if let (Some(thickness), Some(position)) = (read_em(os2 + 26), read_em(os2 + 28)) {
+            if thickness > 0.0 {
+                metrics.strikeout_position = position;
+                metrics.strikeout_thickness = thickness;
+            }
+        }
+    }
+    metrics
+}
+
+fn stretch_from_width_class(width: u16) -> FontStretch {
+    match width {
+        1 => FontStretch::UltraCondensed,
+        2 => FontStretch::ExtraCondensed,
+        3 => FontStretch::Condensed,
+        4 => FontStretch::SemiCondensed,
+        6 => FontStretch::SemiExpanded,
+        7 => FontStretch::Expanded,
+        8 => FontStretch::ExtraExpanded,
+        9 => FontStretch::UltraExpanded,
+        _ => FontStretch::Normal,
+    }
+}
+
+/// Read the family name from the `name` table, preferring the typographic family name,
+/// which groups faces of different weights under the same family, and english names.
+fn family_name(data: &[u8], face_offset: usize) -> Option<String> {
+    let name = find_table(data, face_offset, b"name")?;
+    let count = read_u16(data, name + 2)? as usize;
+    let storage = name + read_u16(data, name + 4)? as usize;
+    let mut best: Option<(u16, String)> = None;
+    for index in 0..count {
+        let record = name + 6 + index * 12;
+        let platform = read_u16(data, record)?;
+        let language = read_u16(data, record + 4)?;
+        let name_id = read_u16(data, record + 6)?;
+        let length = read_u16(data, record + 8)? as usize;
+        let offset = storage + read_u16(data, record + 10)? as usize;
+        if name_id != 1 && name_id != 16 {
+            continue;
+        }
+        let bytes = match data.get(offset..offset + length) {
+            Some(bytes) => bytes,
+            None => continue,
+        };
+        let text = match platform {
+            0 | 3 => decode_utf16_be(bytes),
+            1 => bytes.iter().map(|&byte| char::from(byte)).collect(),
+            _ => continue,
+        };
+        let mut priority = if name_id == 16 { 0 } else { 2 };
+        if platform == 3 && language != 0x409 {
+            priority += 1;
+        }
+        if !text.is_empty() && best.as_ref().map(|&(best, _)| priority < best).unwrap_or(true) {
+            best = Some((priority, text));
+        }
+    }
+    best.map(|(_, text)| text)
+}
+
+fn decode_utf16_be(bytes: &[u8]) -> String {
+    let units: Vec<u16> = bytes.chunks(2).filter(|chunk| chunk.len() == 2)
+        .map(|chunk| (u16::from(chunk[0]) << 8) | u16::from(chunk[1]))
+        .collect();
+    String::from_utf16_lossy(&units)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static HACK_REGULAR: &[u8] = include_bytes!("../../../assets/fonts/Hack/Hack-Regular.ttf");
+    static NOTO_SANS_BOLD_ITALIC: &[u8] = include_bytes!("../../../assets/fonts/NotoSans/NotoSans-BoldItalic.ttf");
+
+    fn write_u32(data: &mut Vec<u8>, value: u32) {
+        data.extend_from_slice(&[(value >> 24) as u8, (value >> 16) as u8, (value >> 8) as u8, value as u8]);
+    }
+
+    /// Build a font collection from single font files, table offsets in a collection
+    /// are from the start of the collection, so they are moved by the face's offset
+    fn collection(fonts: &[&[u8]]) -> Vec<u8> {
+        let mut data = b"ttcf".to_vec();
+        write_u32(&mut data, 0x0001_0000);
+        write_u32(&mut data, fonts.len() as u32);
+        let mut offset = 12 + fonts.len() * 4;
+        for font in fonts {
+            write_u32(&mut data, offset as u32);
+            offset += font.len();
+        }
+        for font in fonts {
+            let face_offset = data.len();
+            let mut face = font.to_vec();
+            let num_tables = read_u16(font, 4).unwrap() as usize;
+            for index in 0..num_tables {
+                let record = 12 + index * 16;
+                let table_offset = read_u32(font, record + 8).unwrap() + face_offset as u32;
+                let mut bytes = Vec::new();
+                write_u32(&mut bytes, table_offset);
+                face[record + 8..record + 12].copy_from_slice(&bytes);
+            }
+            data.extend(face);
+        }
+        data
+    }
+
+    #[test]
+    fn single_font_offsets() {
+        assert_eq!(face_offsets(HACK_REGULAR), vec![0]);
+    }
+
+    #[test]
+    fn collection_offsets() {
+        let data = collection(&[HACK_REGULAR, NOTO_SANS_BOLD_ITALIC]);
+        let header_len = 12 + 2 * 4;
+        assert_eq!(face_offsets(&data), vec![header_len, header_len + HACK_REGULAR.len()]);
+    }
+
+    #[test]
+    fn descriptor() {
+        let descriptor = face_descriptor(HACK_REGULAR, 0).unwrap();
+        assert_eq!(descriptor, FontDescriptor::from_family("Hack"));
+
+        let descriptor = face_descriptor(NOTO_SANS_BOLD_ITALIC, 0).unwrap();
+        assert_eq!(descriptor, FontDescriptor::from_family("Noto Sans")
+            .weight(FontWeight::BOLD)
+            .style(FontStyle::Italic));
+    }
+
+    #[test]
+    fn collection_descriptors() {
+        let data = collection(&[HACK_REGULAR, NOTO_SANS_BOLD_ITALIC]);
+        let families: Vec<String> = face_offsets(&data).into_iter()
+            .map(|offset| face_descriptor(&data, offset).unwrap().family_name)
+            .collect();
+        assert_eq!(families, vec!["Hack".to_owned(), "Noto Sans".to_owned()]);
+    }
+
+    #[test]
+    fn invalid_data() {
+        assert_eq!(face_descriptor(b"not a font", 0), None);
+        let metrics = decoration_metrics(b"not a font", 0);
+        assert_eq!(metrics.strikeout_position, DecorationMetrics::default().strikeout_position);
+    }
+
+    #[test]
+    fn decorations() {
+        // Hack has 2048 units per em, a 90 unit underline 220 units below the baseline
+        // and a 102 unit strikeout 530 units above it
+        let metrics = decoration_metrics(HACK_REGULAR, 0);
+        assert_eq!(metrics.underline_position, -220.0 / 2048.0);
+        assert_eq!(metrics.underline_thickness, 90.0 / 2048.0);
+        assert_eq!(metrics.strikeout_position, 530.0 / 2048.0);
+        assert_eq!(metrics.strikeout_thickness, 102.0 / 2048.0);
+    }
+}
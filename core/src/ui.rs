@@ -14,11 +14,13 @@ use window::Window;
 use app::App;
 use widget::Widget;
 use layout::{LimnSolver, LayoutChanged, LayoutVars, ExactFrame};
+use layout::inspect::LayoutGraph;
 use layout::constraint::*;
 use geometry::{Point, Rect, Size};
 use resources::WidgetId;
 use event::{Target, EventArgs};
 use render::WebRenderContext;
+use modal::Modal;
 
 /// If true, the constraint that matches the root layout size to the window size
 /// is required. This can be useful for debugging but can result in panics from resizing the window.
@@ -28,12 +30,17 @@ const WINDOW_CONSTRAINT_REQUIRED: bool = false;
 /// `Ui` is accessible to every event handler, so features helper methods that can be accessed at any time.
 pub struct Ui {
     pub(crate) root: Widget,
+    pub(crate) overlay: Widget,
+    /// Overlay widgets added with `add_popup`, some may have been removed since
+    pub(crate) popups: HashSet<WidgetId>,
+    pub(crate) modals: Vec<Modal>,
     widget_map: HashMap<WidgetId, Widget>,
     pub(crate) solver: LimnSolver,
     pub(crate) render: WebRenderContext,
     needs_redraw: bool,
     should_close: bool,
     debug_draw_bounds: bool,
+    cursor: glutin::MouseCursor,
     pub window: Rc<RefCell<Window>>,
     window_constraints: Vec<Constraint>,
 }
@@ -45,15 +52,23 @@ impl Ui {
         root.layout().add(top_left(Point::zero()));
         // x will crash if window size set to (0, 0)
         root.layout().add(min_size(Size::new(1.0, 1.0)));
+        // the overlay isn't part of the widget tree, it's drawn and hit tested separately, above the root
+        let mut overlay = Widget::new("overlay");
+        overlay.set_cursor_hit_fn(|_, _| false);
+        overlay.layout().add(match_layout(&root));
         let render = WebRenderContext::new(&mut window, events_loop);
         Ui {
             widget_map: HashMap::new(),
             root: root.into(),
+            overlay: overlay,
+            popups: HashSet::new(),
+            modals: Vec::new(),
             solver: LimnSolver::new(),
             render: render,
             needs_redraw: true,
             should_close: false,
             debug_draw_bounds: false,
+            cursor: glutin::MouseCursor::Default,
             window: Rc::new(RefCell::new(window)),
             window_constraints: Vec::new(),
         }
@@ -75,6 +90,17 @@ impl Ui {
         self.should_close = true;
     }
 
+    /// Set the mouse cursor icon shown over the window
+    pub fn set_cursor(&mut self, cursor: glutin::MouseCursor) {
+        self.cursor = cursor;
+        self.window.borrow_mut().set_cursor(cursor);
+    }
+
+    /// The mouse cursor icon last set with `set_cursor`
+    pub fn get_cursor(&self) -> glutin::MouseCursor {
+        self.cursor
+    }
+
     pub(super) fn should_close(&self) -> bool {
         self.should_close
     }
@@ -138,6 +164,7 @@ impl Ui {
             let mut renderer = self.render.render_builder(window_size);
             let crop_to = Rect::new(Point::zero(), Size::new(::std::f32::MAX, ::std::f32::MAX));
             self.root.draw(crop_to, &mut renderer, self.debug_draw_bounds);
+            self.overlay.draw(crop_to, &mut renderer, self.debug_draw_bounds);
             (renderer.builder, renderer.resources)
         };
         self.render.set_display_list(builder, resources, window_size);
@@ -156,10 +183,11 @@ impl Ui {
     }
 
     pub fn widgets_under_cursor(&mut self, point: Point) -> WidgetsUnderCursor {
-        WidgetsUnderCursor::new(point, self.get_root())
+        WidgetsUnderCursor::new(point, vec![self.get_root(), self.get_overlay()])
     }
 
-    /// Find the first widget under the cursor, ie. the last to be drawn that is under the cursor
+    /// Find the first widget under the cursor, ie. the last to be drawn that is under the cursor,
+    /// including widgets in the overlay
     pub fn widget_under_cursor(&mut self, point: Point) -> Option<Widget> {
         self.widgets_under_cursor(point).next()
     }
@@ -219,6 +247,11 @@ impl Ui {
         }
     }
 
+    /// Creates a snapshot of the current layout graph, that can be exported as JSON or Graphviz DOT
+    pub fn layout_graph(&self) -> LayoutGraph {
+        self.solver.layout_graph()
+    }
+
     pub fn print_widgets(&self) {
         for widget_ref in self.widgets_bfs() {
             let draw_state = &widget_ref.widget().draw_state;
@@ -232,6 +265,13 @@ pub struct RegisterWidget(pub Widget);
 #[derive(Clone)]
 pub struct RemoveWidget(pub Widget);
 
+/// An event sent with `Widget::event_after`, that is forwarded to the widget once it reaches the root
+pub(crate) struct DelayedEvent {
+    pub widget_id: WidgetId,
+    pub type_id: TypeId,
+    pub data: Box<Any + Send>,
+}
+
 impl App {
     pub fn add_ui_handlers(&mut self) {
         self.add_handler(|event: &RegisterWidget, args: EventArgs| {
@@ -246,6 +286,11 @@ impl App {
             args.ui.check_layout_changes();
             args.ui.widget_map.remove(&widget_ref.id());
         });
+        self.add_handler(|event: &DelayedEvent, args: EventArgs| {
+            if let Some(widget_ref) = args.ui.get_widget(event.widget_id) {
+                args.ui.handle_widget_event(widget_ref, event.type_id, event.data.as_ref());
+            }
+        });
     }
 }
 
@@ -267,10 +312,11 @@ pub struct WidgetsUnderCursor {
     dfs: WidgetsDfsPostReverse,
 }
 impl WidgetsUnderCursor {
-    fn new(point: Point, root: Widget) -> Self {
+    /// Iterates over the widgets in each of `roots`, the last of the roots first
+    fn new(point: Point, roots: Vec<Widget>) -> Self {
         WidgetsUnderCursor {
             point: point,
-            dfs: WidgetsDfsPostReverse::new(root),
+            dfs: WidgetsDfsPostReverse::new(roots),
         }
     }
 }
@@ -296,9 +342,9 @@ struct WidgetsDfsPostReverse {
 }
 
 impl WidgetsDfsPostReverse {
-    fn new(root: Widget) -> Self {
+    fn new(roots: Vec<Widget>) -> Self {
         WidgetsDfsPostReverse {
-            stack: vec![root],
+            stack: roots,
             discovered: HashSet::new(),
             finished: HashSet::new(),
         }
@@ -16,12 +16,16 @@ pub mod filter;
 
 use std::any::{TypeId, Any};
 use std::collections::HashMap;
+use std::cmp;
 use std::rc::{Rc, Weak};
 use std::cell::{RefCell, Ref, RefMut};
 use std::hash::{Hash, Hasher};
 use std::ops::{Deref, DerefMut};
 use std::fmt;
 use std::fmt::Debug;
+use std::time::Duration;
+
+use cassowary::Variable;
 
 use render::RenderBuilder;
 use event::{self, EventHandler, EventArgs, EventHandlerWrapper};
@@ -213,12 +217,21 @@ impl Widget {
     }
 
     pub fn add_child<U: Into<Widget>>(&mut self, child: U) -> &mut Self {
+        let index = self.widget().children.len();
+        self.insert_child(index, child)
+    }
+
+    /// Adds a child before the child currently at `index`, eg. to add an item in the middle of a list
+    pub fn insert_child<U: Into<Widget>>(&mut self, index: usize, child: U) -> &mut Self {
         let mut child = child.into();
         event::event(Target::Root, ::layout::UpdateLayout(child.clone()));
         child.widget_mut().parent = Some(self.downgrade());
         child.widget_mut().props.extend(self.props().iter().cloned());
-        self.widget_mut().children.push(child.clone());
-        self.layout().add_child(child.layout().deref_mut());
+        let index = cmp::min(index, self.widget().children.len());
+        self.widget_mut().children.insert(index, child.clone());
+        let direction = self.layout().direction();
+        child.inherit_layout_direction(direction);
+        self.layout().insert_child(index, child.layout().deref_mut());
         self.event(::ui::WidgetAttachedEvent);
         self.event(::ui::ChildAttachedEvent(self.id(), child.layout().vars));
         self.event(::ui::ChildrenUpdatedEvent::Added(child));
@@ -264,6 +277,16 @@ impl Widget {
     pub fn event_bubble_up<T: 'static>(&self, data: T) {
         event::event(Target::BubbleUp(self.clone()), data);
     }
+    /// Send an event to this widget after `delay` has passed, eg. to implement timeouts.
+    /// The event is dropped if the widget has been removed by then.
+    pub fn event_after<T: 'static + Send>(&self, delay: Duration, data: T) {
+        let event = ::ui::DelayedEvent {
+            widget_id: self.id(),
+            type_id: TypeId::of::<T>(),
+            data: Box::new(data),
+        };
+        event::event_global_after(delay, event);
+    }
     pub fn trigger_event(&self, ui: &mut Ui, type_id: TypeId, event: &Any) -> bool {
         let handlers = {
             let mut widget = self.0.borrow_mut();
@@ -381,6 +404,12 @@ impl LayoutRef for Widget {
     fn layout_ref(&self) -> LayoutVars {
         self.layout_vars()
     }
+    fn baseline_ref(&self) -> Option<Variable> {
+        if let Some(baseline) = self.layout_vars().baseline {
+            return Some(baseline);
+        }
+        Some(self.clone().layout().baseline())
+    }
 }
 
 pub struct LayoutGuardMut<'a> {
@@ -8,6 +8,8 @@ pub enum Property {
     Pressed,
     Inactive,
     Focused,
+    /// Neither on nor off, eg. a tri-state `Checkbox` that is partially checked
+    Indeterminate,
 }
 pub type PropSet = BTreeSet<Property>;
 
@@ -21,5 +23,6 @@ pub mod states {
         pub static ref SELECTED: PropSet = btreeset!{Property::Selected};
         pub static ref INACTIVE: PropSet = btreeset!{Property::Inactive};
         pub static ref FOCUSED: PropSet = btreeset!{Property::Focused};
+        pub static ref INDETERMINATE: PropSet = btreeset!{Property::Indeterminate};
     }
 }
@@ -55,4 +55,7 @@ impl Window {
     pub fn show(&self) {
         self.window.show()
     }
+    pub fn set_cursor(&self, cursor: glutin::MouseCursor) {
+        self.window.set_cursor(cursor)
+    }
 }
@@ -16,6 +16,7 @@ fn main() {
     let mut button = ToggleButtonStyle::default();
     button.toggle_text("ON", "OFF");
     let mut button = Widget::from_modifier_style(button);
+    button.set_tooltip("Click to switch between ON and OFF");
     button.layout().add(constraints![
         center(&root),
         bound_by(&root).padding(50.0).strength(WEAK),
@@ -0,0 +1,57 @@
+#[allow(unused_imports)]
+#[macro_use]
+extern crate limn;
+
+mod util;
+
+use limn::prelude::*;
+
+fn main() {
+    let window_builder = glutin::WindowBuilder::new()
+        .with_title("Limn checkbox demo")
+        .with_min_dimensions(100, 100);
+    let app = util::init(window_builder);
+    let mut root = Widget::new("root");
+
+    let mut settings = LinearLayoutSettings::new(Orientation::Vertical);
+    settings.padding = 20.0;
+    root.linear_layout(settings);
+    root.layout().add(shrink());
+
+    let status = Widget::from_modifier_style(StaticTextStyle::from_text("Nothing selected"));
+
+    let mut checkbox = Widget::from_modifier_style(CheckboxStyle::from_text("Checkbox"));
+    {
+        let status = status.clone();
+        checkbox.add_handler(move |event: &CheckStateChanged, _: EventArgs| {
+            status.event(TextUpdated(format!("Checkbox {:?}", event.0)));
+        });
+    }
+    let mut tri_state = Widget::from_modifier_style(CheckboxStyle::from_text("Tri-state checkbox").tri_state());
+    {
+        let status = status.clone();
+        tri_state.add_handler(move |event: &CheckStateChanged, _: EventArgs| {
+            status.event(TextUpdated(format!("Tri-state checkbox {:?}", event.0)));
+        });
+    }
+
+    let mut radio_group = Widget::from_modifier_style(RadioGroupStyle::default());
+    for text in &["Small", "Medium", "Large"] {
+        radio_group.add_child(Widget::from_modifier_style(RadioButtonStyle::from_text(text)));
+    }
+    {
+        let status = status.clone();
+        radio_group.add_handler(move |event: &RadioSelected, _: EventArgs| {
+            status.event(TextUpdated(format!("Radio button {} selected", event.0)));
+        });
+    }
+    radio_group.event(SelectRadio(Some(0)));
+
+    for mut widget in vec![checkbox, tri_state, radio_group, status] {
+        widget.layout().add(bound_left(&root).padding(50.0));
+        root.add_child(widget);
+    }
+    root.layout().add(min_size(Size::new(300.0, 300.0)));
+
+    app.main_loop(root);
+}
@@ -0,0 +1,42 @@
+#[allow(unused_imports)]
+#[macro_use]
+extern crate limn;
+
+mod util;
+
+use limn::prelude::*;
+
+fn main() {
+    let window_builder = glutin::WindowBuilder::new()
+        .with_title("Limn combo box demo")
+        .with_min_dimensions(100, 100);
+    let app = util::init(window_builder);
+    let mut root = Widget::new("root");
+    root.layout().add(min_size(Size::new(300.0, 300.0)));
+
+    let colors = vec!["Red", "Orange", "Yellow", "Green", "Blue", "Indigo", "Violet"];
+    let mut style = ComboBoxStyle::from_items(colors.clone());
+    style.selected = Some(Some(0));
+    let mut combo_box = Widget::from_modifier_style(style);
+
+    let mut status = Widget::from_modifier_style(StaticTextStyle::from_text("Selected Red"));
+    {
+        let status = status.clone();
+        combo_box.add_handler(move |event: &ComboBoxSelected, _: EventArgs| {
+            status.event(TextUpdated(format!("Selected {}", colors[event.0])));
+        });
+    }
+    combo_box.layout().add(constraints![
+        align_top(&root).padding(50.0),
+        center_horizontal(&root),
+        width(200.0),
+    ]);
+    status.layout().add(constraints![
+        align_bottom(&root).padding(50.0),
+        center_horizontal(&root),
+    ]);
+    root.add_child(status);
+    root.add_child(combo_box);
+
+    app.main_loop(root);
+}
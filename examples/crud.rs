@@ -34,6 +34,7 @@ impl Person {
 enum PeopleEvent {
     Add,
     Update,
+    ConfirmDelete,
     Delete,
     PersonSelected(Option<PersonId>),
     ChangeFirstName(String),
@@ -114,8 +115,48 @@ impl PeopleHandler {
         self.widgets.list_widget.add_child(list_item_widget);
     }
 }
+
+fn confirm_dialog(message: &str) -> Widget {
+    let mut dialog = Widget::new("confirm_dialog");
+    dialog.set_draw_style(style!(RectStyle {
+        background_color: WHITE,
+        corner_radius: Some(5.0),
+        border: Some((1.0, GRAY_40)),
+    }));
+    dialog.layout().add(shrink());
+
+    let mut message = Widget::from_modifier_style(StaticTextStyle::from_text(message));
+    message.layout().add(constraints![
+        align_top(&dialog).padding(20.0),
+        bound_left(&dialog).padding(20.0),
+        bound_right(&dialog).padding(20.0),
+    ]);
+
+    let mut ok_button = Widget::from_modifier_style(ButtonStyle::from_text("Delete"));
+    ok_button.add_handler(|_: &ClickEvent, args: EventArgs| {
+        args.widget.event_bubble_up(CloseModal(DialogResult::Ok));
+    });
+    let mut cancel_button = Widget::from_modifier_style(ButtonStyle::from_text("Cancel"));
+    cancel_button.add_handler(|_: &ClickEvent, args: EventArgs| {
+        args.widget.event_bubble_up(CloseModal(DialogResult::Cancel));
+    });
+    ok_button.layout().add(constraints![
+        below(&message).padding(20.0),
+        align_bottom(&dialog).padding(20.0),
+        to_left_of(&cancel_button).padding(20.0),
+    ]);
+    cancel_button.layout().add(constraints![
+        align_top(&ok_button),
+        align_right(&dialog).padding(20.0),
+    ]);
+    dialog
+        .add_child(message)
+        .add_child(ok_button)
+        .add_child(cancel_button);
+    dialog
+}
 impl EventHandler<PeopleEvent> for PeopleHandler {
-    fn handle(&mut self, event: &PeopleEvent, _: EventArgs) {
+    fn handle(&mut self, event: &PeopleEvent, args: EventArgs) {
 
         let was_valid = self.person.is_valid();
         match event.clone() {
@@ -132,6 +173,18 @@ impl EventHandler<PeopleEvent> for PeopleHandler {
                     self.people_widgets[&selected_id].event_subtree(TextUpdated(self.person.name()));
                 }
             },
+            PeopleEvent::ConfirmDelete => {
+                if let Some(selected_id) = self.selected_item {
+                    let message = format!("Delete {}?", self.people[&selected_id].name());
+                    let mut dialog = confirm_dialog(&message);
+                    dialog.add_handler(|event: &ModalClosed, args: EventArgs| {
+                        if event.0 == DialogResult::Ok {
+                            args.ui.event(PeopleEvent::Delete);
+                        }
+                    });
+                    args.ui.show_modal(dialog);
+                }
+            }
             PeopleEvent::Delete => {
                 if let Some(selected_id) = self.selected_item {
                     self.people.remove(&selected_id);
@@ -222,7 +275,7 @@ fn main() {
     let mut delete_button = Widget::from_modifier_style(ButtonStyle::from_text("Delete"));
     delete_button.add_prop(Property::Inactive);
     delete_button.add_handler(|_: &ClickEvent, args: EventArgs| {
-        args.ui.event(PeopleEvent::Delete);
+        args.ui.event(PeopleEvent::ConfirmDelete);
     });
     update_button.layout().add(to_right_of(&create_button).padding(20.0));
     delete_button.layout().add(to_right_of(&update_button).padding(20.0));
@@ -0,0 +1,72 @@
+#[allow(unused_imports)]
+#[macro_use]
+extern crate limn;
+
+mod util;
+
+use limn::prelude::*;
+
+fn show_activated(widget: &mut Widget, status: Widget) {
+    widget.add_handler(move |event: &MenuItemActivated, _: EventArgs| {
+        let text = match event.checked {
+            Some(checked) => format!("{} {}", event.id, if checked { "checked" } else { "unchecked" }),
+            None => format!("Activated {}", event.id),
+        };
+        status.event(TextUpdated(text));
+    });
+}
+
+fn main() {
+    let window_builder = glutin::WindowBuilder::new()
+        .with_title("Limn menu demo")
+        .with_min_dimensions(100, 100);
+    let app = util::init(window_builder);
+    let mut root = Widget::new("root");
+    root.layout().add(min_size(Size::new(400.0, 300.0)));
+
+    let file_menu = Menu::new()
+        .item(MenuItem::new("New").accelerator("Ctrl+N"))
+        .item(MenuItem::new("Open").accelerator("Ctrl+O"))
+        .item(MenuItem::new("Open Recent").submenu(Menu::new()
+            .item(MenuItem::new("notes.txt"))
+            .item(MenuItem::new("todo.txt"))))
+        .item(MenuItem::new("Save").accelerator("Ctrl+S").disabled())
+        .separator()
+        .item(MenuItem::new("Quit").accelerator("Ctrl+Q"));
+    let view_menu = Menu::new()
+        .item(MenuItem::new("Show Toolbar").checkable(true))
+        .item(MenuItem::new("Show Status Bar").checkable(false));
+    let menu = Menu::new()
+        .item(MenuItem::new("File").submenu(file_menu))
+        .item(MenuItem::new("View").submenu(view_menu));
+    let mut menu_bar = Widget::from_modifier_style(MenuBarStyle::from_menu(menu));
+
+    let mut status = Widget::from_modifier_style(StaticTextStyle::from_text("Right click for a context menu"));
+    let context_menu = Menu::new()
+        .item(MenuItem::new("Cut").accelerator("Ctrl+X"))
+        .item(MenuItem::new("Copy").accelerator("Ctrl+C"))
+        .item(MenuItem::new("Paste").accelerator("Ctrl+V").disabled())
+        .separator()
+        .item(MenuItem::new("Select All").accelerator("Ctrl+A"));
+    let mut content = Widget::new("content");
+    content.set_context_menu(context_menu);
+
+    show_activated(&mut menu_bar, status.clone());
+    show_activated(&mut content, status.clone());
+    menu_bar.layout().add(constraints![
+        align_top(&root),
+        match_width(&root),
+    ]);
+    content.layout().add(constraints![
+        below(&menu_bar),
+        align_left(&root),
+        align_right(&root),
+        align_bottom(&root),
+    ]);
+    status.layout().add(center(&content));
+    content.add_child(status);
+    root.add_child(menu_bar);
+    root.add_child(content);
+
+    app.main_loop(root);
+}
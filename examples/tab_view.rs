@@ -0,0 +1,50 @@
+#[allow(unused_imports)]
+#[macro_use]
+extern crate limn;
+
+mod util;
+
+use limn::prelude::*;
+
+fn page(text: &str) -> Widget {
+    let mut page = Widget::new("page");
+    let mut text = Widget::from_modifier_style(StaticTextStyle::from_text(text));
+    text.layout().add(center(&page));
+    page.add_child(text);
+    page
+}
+
+fn main() {
+    let window_builder = glutin::WindowBuilder::new()
+        .with_title("Limn tab view demo")
+        .with_min_dimensions(100, 100);
+    let app = util::init(window_builder);
+    let mut root = Widget::new("root");
+    root.layout().add(min_size(Size::new(400.0, 300.0)));
+
+    let mut tab_view = Widget::from_modifier_style(TabViewStyle::default().closable());
+    for &(title, text) in &[("General", "General settings"), ("Appearance", "Appearance settings"), ("Advanced", "Advanced settings")] {
+        tab_view.event(AddTab(title.to_owned(), page(text)));
+    }
+    let mut status = Widget::from_modifier_style(StaticTextStyle::from_text("Drag tabs to reorder them, ctrl+Tab to switch"));
+    {
+        let status = status.clone();
+        tab_view.add_handler(move |event: &TabSelected, _: EventArgs| {
+            status.event(TextUpdated(format!("Selected tab {}", event.0)));
+        });
+    }
+    tab_view.layout().add(constraints![
+        align_top(&root).padding(10.0),
+        align_left(&root).padding(10.0),
+        align_right(&root).padding(10.0),
+    ]);
+    status.layout().add(constraints![
+        below(&tab_view).padding(10.0),
+        align_bottom(&root).padding(10.0),
+        center_horizontal(&root),
+    ]);
+    root.add_child(tab_view);
+    root.add_child(status);
+
+    app.main_loop(root);
+}
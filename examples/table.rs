@@ -0,0 +1,62 @@
+#[allow(unused_imports)]
+#[macro_use]
+extern crate limn;
+
+mod util;
+
+use limn::prelude::*;
+
+struct Planet {
+    name: &'static str,
+    moons: u32,
+    radius: f32,
+}
+
+fn main() {
+    let window_builder = glutin::WindowBuilder::new()
+        .with_title("Limn table demo")
+        .with_min_dimensions(100, 100);
+    let app = util::init(window_builder);
+    let mut root = Widget::new("root");
+    root.layout().add(min_size(Size::new(500.0, 400.0)));
+
+    let mut table = Table::new();
+    table
+        .add_column(TableColumn::text("Planet", |planet: &Planet| planet.name.to_owned()).width(150.0))
+        .add_column(TableColumn::text("Moons", |planet: &Planet| planet.moons.to_string())
+            .sort_by(|a, b| a.moons.cmp(&b.moons)))
+        .add_column(TableColumn::text("Radius (km)", |planet: &Planet| format!("{:.0}", planet.radius))
+            .width(150.0)
+            .sort_by(|a, b| a.radius.partial_cmp(&b.radius).unwrap()))
+        .add_rows(vec![
+            Planet { name: "Mercury", moons: 0, radius: 2439.7 },
+            Planet { name: "Venus", moons: 0, radius: 6051.8 },
+            Planet { name: "Earth", moons: 1, radius: 6371.0 },
+            Planet { name: "Mars", moons: 2, radius: 3389.5 },
+            Planet { name: "Jupiter", moons: 79, radius: 69911.0 },
+            Planet { name: "Saturn", moons: 82, radius: 58232.0 },
+            Planet { name: "Uranus", moons: 27, radius: 25362.0 },
+            Planet { name: "Neptune", moons: 14, radius: 24622.0 },
+        ]);
+    let mut table = Widget::from_modifier(table);
+
+    let mut status = Widget::from_modifier_style(StaticTextStyle::from_text("Click a header to sort, drag it to reorder"));
+    {
+        let status = status.clone();
+        table.add_handler(move |event: &TableRowSelected, _: EventArgs| {
+            status.event(TextUpdated(format!("Selected row {}", event.0)));
+        });
+    }
+    table.layout().add(constraints![
+        align_top(&root).padding(20.0),
+        align_left(&root).padding(20.0),
+    ]);
+    status.layout().add(constraints![
+        below(&table).padding(20.0),
+        center_horizontal(&root),
+    ]);
+    root.add_child(table);
+    root.add_child(status);
+
+    app.main_loop(root);
+}
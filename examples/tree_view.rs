@@ -0,0 +1,70 @@
+#[allow(unused_imports)]
+#[macro_use]
+extern crate limn;
+
+mod util;
+
+use limn::prelude::*;
+
+fn main() {
+    let window_builder = glutin::WindowBuilder::new()
+        .with_title("Limn tree view demo")
+        .with_min_dimensions(100, 100);
+    let app = util::init(window_builder);
+    let mut root = Widget::new("root");
+    root.layout().add(min_size(Size::new(400.0, 400.0)));
+
+    let nodes = vec![
+        TreeNode::new("src", "src").children(vec![
+            TreeNode::new("src/lib.rs", "lib.rs"),
+            TreeNode::new("src/widgets", "widgets").children(vec![
+                TreeNode::new("src/widgets/list.rs", "list.rs"),
+                TreeNode::new("src/widgets/tree_view.rs", "tree_view.rs"),
+            ]),
+        ]),
+        TreeNode::new("examples", "examples").lazy(),
+        TreeNode::new("Cargo.toml", "Cargo.toml"),
+    ];
+    let mut tree_view = Widget::from_modifier_style(TreeViewStyle::from_nodes(nodes));
+    // children of lazy nodes are only created once the node is expanded
+    tree_view.add_handler(|event: &LoadTreeNodeChildren, args: EventArgs| {
+        let children = (1..4).map(|index| {
+            let id = format!("{}/example_{}.rs", event.0, index);
+            TreeNode::new(&id, &format!("example_{}.rs", index))
+        }).collect();
+        args.widget.event(SetTreeNodeChildren(event.0.clone(), children));
+    });
+
+    let mut status = Widget::from_modifier_style(StaticTextStyle::from_text("Select a node"));
+    {
+        let status = status.clone();
+        tree_view.add_handler(move |event: &TreeNodeSelected, _: EventArgs| {
+            status.event(TextUpdated(format!("Selected {}", event.0)));
+        });
+    }
+    {
+        let status = status.clone();
+        tree_view.add_handler(move |event: &TreeNodeActivated, _: EventArgs| {
+            status.event(TextUpdated(format!("Opened {}", event.0)));
+        });
+    }
+
+    let mut scroll_widget = ScrollContainer::default();
+    scroll_widget.add_content(tree_view.clone());
+    let mut scroll_widget = Widget::from_modifier(scroll_widget);
+    tree_view.layout().add(constraints![shrink(), match_width(&scroll_widget)]);
+    scroll_widget.layout().add(constraints![
+        align_top(&root).padding(20.0),
+        align_left(&root).padding(20.0),
+        align_right(&root).padding(20.0),
+        height(300.0),
+    ]);
+    status.layout().add(constraints![
+        below(&scroll_widget).padding(20.0),
+        center_horizontal(&root),
+    ]);
+    root.add_child(scroll_widget);
+    root.add_child(status);
+
+    app.main_loop(root);
+}
@@ -3,7 +3,7 @@ extern crate env_logger;
 use limn::prelude::*;
 use limn::input::{EscKeyCloseHandler, DebugSettingsHandler};
 use limn::resources;
-use limn::resources::font::FontDescriptor;
+use limn::resources::font::{FontDescriptor, FontWeight, FontStyle};
 use limn::draw::rect::RectStyle;
 use limn::draw::text::TextStyle;
 use limn::draw::ellipse::EllipseStyle;
@@ -13,6 +13,9 @@ pub fn default_style() {
     let mut res = resources::resources();
 
     res.font_loader.register_font_data(FontDescriptor::from_family("NotoSans"), include_bytes!("../../assets/fonts/NotoSans/NotoSans-Regular.ttf").to_vec()).unwrap();
+    res.font_loader.register_font_data(FontDescriptor::from_family("NotoSans").weight(FontWeight::BOLD), include_bytes!("../../assets/fonts/NotoSans/NotoSans-Bold.ttf").to_vec()).unwrap();
+    res.font_loader.register_font_data(FontDescriptor::from_family("NotoSans").style(FontStyle::Italic), include_bytes!("../../assets/fonts/NotoSans/NotoSans-Italic.ttf").to_vec()).unwrap();
+    res.font_loader.register_font_data(FontDescriptor::from_family("NotoSans").weight(FontWeight::BOLD).style(FontStyle::Italic), include_bytes!("../../assets/fonts/NotoSans/NotoSans-BoldItalic.ttf").to_vec()).unwrap();
 
     res.theme.register_type_style(EllipseStyle::default());
     res.theme.register_type_style(RectStyle::default());
@@ -60,6 +63,152 @@ pub fn default_style() {
     res.theme.register_class_prop_style("button_rect", MOUSEOVER.clone(), style!(RectStyle {
         background_color: GRAY_90,
     }));
+    res.theme.register_class_style("checkbox_rect", style!(RectStyle {
+        background_color: WHITE,
+        corner_radius: Some(3.0),
+        border: Some((1.0, GRAY_40)),
+    }));
+    res.theme.register_class_prop_style("checkbox_rect", MOUSEOVER.clone(), style!(RectStyle {
+        background_color: GRAY_90,
+    }));
+    res.theme.register_class_style("radio_button_circle", style!(EllipseStyle {
+        background_color: WHITE,
+        border: Some((1.0, GRAY_40)),
+    }));
+    res.theme.register_class_prop_style("radio_button_circle", MOUSEOVER.clone(), style!(EllipseStyle {
+        background_color: GRAY_90,
+    }));
+    res.theme.register_class_style("combo_box_rect", style!(RectStyle {
+        background_color: WHITE,
+        corner_radius: Some(3.0),
+        border: Some((1.0, GRAY_40)),
+    }));
+    res.theme.register_class_prop_style("combo_box_rect", FOCUSED.clone(), style!(RectStyle {
+        border: Some((1.0, BLUE)),
+    }));
+    res.theme.register_class_prop_style("combo_box_rect", MOUSEOVER.clone(), style!(RectStyle {
+        background_color: GRAY_90,
+    }));
+    res.theme.register_class_style("tooltip_rect", style!(RectStyle {
+        background_color: GRAY_30,
+        corner_radius: Some(3.0),
+    }));
+    res.theme.register_class_style("tooltip_text", style!(TextStyle {
+        text_color: WHITE,
+        font_size: 16.0,
+    }));
+    res.theme.register_class_style("menu_rect", style!(RectStyle {
+        background_color: WHITE,
+        border: Some((1.0, GRAY_60)),
+    }));
+    res.theme.register_class_style("menu_item_rect", style!(RectStyle {
+        background_color: TRANSPARENT,
+    }));
+    res.theme.register_class_prop_style("menu_item_rect", SELECTED.clone(), style!(RectStyle {
+        background_color: GRAY_90,
+    }));
+    res.theme.register_class_style("menu_item_text", style!(TextStyle {
+        font_size: 18.0,
+    }));
+    res.theme.register_class_prop_style("menu_item_text", INACTIVE.clone(), style!(TextStyle {
+        text_color: GRAY_60,
+    }));
+    res.theme.register_class_style("menu_item_accelerator", style!(TextStyle {
+        font_size: 18.0,
+        text_color: GRAY_50,
+    }));
+    res.theme.register_class_style("menu_item_arrow", style!(TextStyle {
+        font_size: 18.0,
+    }));
+    res.theme.register_class_style("menu_separator", style!(RectStyle {
+        background_color: GRAY_80,
+    }));
+    res.theme.register_class_style("menu_bar_rect", style!(RectStyle {
+        background_color: GRAY_90,
+    }));
+    res.theme.register_class_style("menu_bar_item_rect", style!(RectStyle {
+        background_color: TRANSPARENT,
+    }));
+    res.theme.register_class_prop_style("menu_bar_item_rect", MOUSEOVER.clone(), style!(RectStyle {
+        background_color: GRAY_80,
+    }));
+    res.theme.register_class_prop_style("menu_bar_item_rect", ACTIVATED.clone(), style!(RectStyle {
+        background_color: GRAY_80,
+    }));
+    res.theme.register_class_style("menu_bar_item_text", style!(TextStyle {
+        font_size: 18.0,
+    }));
+    res.theme.register_class_style("tab_view_strip_rect", style!(RectStyle {
+        background_color: GRAY_70,
+    }));
+    res.theme.register_class_style("tab_view_tab_rect", style!(RectStyle {
+        background_color: GRAY_80,
+        border: Some((1.0, GRAY_60)),
+    }));
+    res.theme.register_class_prop_style("tab_view_tab_rect", MOUSEOVER.clone(), style!(RectStyle {
+        background_color: GRAY_90,
+    }));
+    res.theme.register_class_prop_style("tab_view_tab_rect", ACTIVATED.clone(), style!(RectStyle {
+        background_color: WHITE,
+    }));
+    res.theme.register_class_style("tab_view_tab_text", style!(TextStyle {
+        font_size: 18.0,
+    }));
+    res.theme.register_class_style("tab_view_tab_close", style!(TextStyle {
+        font_size: 18.0,
+        text_color: GRAY_50,
+    }));
+    res.theme.register_class_prop_style("tab_view_tab_close", MOUSEOVER.clone(), style!(TextStyle {
+        text_color: BLACK,
+    }));
+    res.theme.register_class_style("tab_view_content_rect", style!(RectStyle {
+        background_color: WHITE,
+    }));
+    res.theme.register_class_style("tree_view_row_rect", style!(RectStyle {
+        background_color: TRANSPARENT,
+    }));
+    res.theme.register_class_prop_style("tree_view_row_rect", MOUSEOVER.clone(), style!(RectStyle {
+        background_color: GRAY_90,
+    }));
+    res.theme.register_class_prop_style("tree_view_row_rect", SELECTED.clone(), style!(RectStyle {
+        background_color: GRAY_80,
+    }));
+    res.theme.register_class_style("tree_view_text", style!(TextStyle {
+        font_size: 18.0,
+    }));
+    res.theme.register_class_style("tree_view_disclosure", style!(TextStyle {
+        font_size: 12.0,
+        text_color: GRAY_40,
+    }));
+    res.theme.register_class_style("table_header_rect", style!(RectStyle {
+        background_color: GRAY_80,
+        border: Some((1.0, GRAY_70)),
+    }));
+    res.theme.register_class_prop_style("table_header_rect", MOUSEOVER.clone(), style!(RectStyle {
+        background_color: GRAY_90,
+    }));
+    res.theme.register_class_style("table_header_text", style!(TextStyle {
+        font_size: 18.0,
+    }));
+    res.theme.register_class_style("table_header_sort", style!(TextStyle {
+        font_size: 12.0,
+        text_color: GRAY_40,
+    }));
+    res.theme.register_class_style("table_header_resize", style!(RectStyle {
+        background_color: TRANSPARENT,
+    }));
+    res.theme.register_class_style("table_row_rect", style!(RectStyle {
+        background_color: TRANSPARENT,
+    }));
+    res.theme.register_class_prop_style("table_row_rect", MOUSEOVER.clone(), style!(RectStyle {
+        background_color: GRAY_90,
+    }));
+    res.theme.register_class_prop_style("table_row_rect", SELECTED.clone(), style!(RectStyle {
+        background_color: GRAY_80,
+    }));
+    res.theme.register_class_style("table_cell_text", style!(TextStyle {
+        font_size: 16.0,
+    }));
     res.theme.register_modifier_class_style("scrollbar_slider", style!(SliderStyle {
         variable_handle_size: true,
         handle_style: HandleStyle::Square,
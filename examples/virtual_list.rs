@@ -0,0 +1,48 @@
+#[allow(unused_imports)]
+#[macro_use]
+extern crate limn;
+
+mod util;
+
+use std::rc::Rc;
+
+use limn::prelude::*;
+
+fn main() {
+    let window_builder = glutin::WindowBuilder::new()
+        .with_title("Limn virtual list demo")
+        .with_min_dimensions(100, 300);
+    let app = util::init(window_builder);
+    let mut root = Widget::new("root");
+
+    // only the visible lines get widgets, the rest are just strings
+    let lines: Vec<String> = (0..50_000).map(|line| format!("{:05} log message", line)).collect();
+    let mut list_widget = Widget::from_modifier(VirtualList::new(Rc::new(lines), 25.0));
+
+    let mut status = Widget::from_modifier_style(StaticTextStyle::from_text("Select a line"));
+    {
+        let status = status.clone();
+        list_widget.add_handler(move |event: &VirtualListItemSelected, _: EventArgs| {
+            status.event(TextUpdated(format!("Selected line {}", event.0)));
+        });
+    }
+
+    let mut scroll_widget = ScrollContainer::default();
+    scroll_widget.add_content(list_widget.clone()).add_scrollbar();
+    let mut scroll_widget = Widget::from_modifier(scroll_widget);
+    scroll_widget.layout().add(constraints![
+        bound_left(&root).padding(20.0),
+        bound_top(&root).padding(20.0),
+        bound_right(&root).padding(20.0),
+        size(Size::new(300.0, 300.0)),
+    ]);
+    status.layout().add(constraints![
+        below(&scroll_widget).padding(20.0),
+        align_bottom(&root).padding(20.0),
+        center_horizontal(&root),
+    ]);
+    root.add_child(scroll_widget);
+    root.add_child(status);
+
+    app.main_loop(root);
+}
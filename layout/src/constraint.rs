@@ -4,7 +4,7 @@ use cassowary::strength::*;
 
 use euclid::{TypedPoint2D, TypedSize2D};
 
-use super::{LAYOUT, LayoutRef, LayoutVars, Size, Point};
+use super::{LAYOUT, Direction, LayoutRef, LayoutVars, Size, Point};
 
 pub fn width(width: f32) -> WidgetConstraintBuilder {
     WidgetConstraint::Width(width).builder(REQUIRED)
@@ -24,6 +24,22 @@ pub fn size<T>(size: TypedSize2D<f32, T>) -> WidgetConstraintBuilder {
 pub fn min_size<T>(size: TypedSize2D<f32, T>) -> WidgetConstraintBuilder {
     WidgetConstraint::MinSize(size.to_untyped()).builder(REQUIRED)
 }
+/// Constrain the width of a widget to be at most `width`, less any padding
+pub fn max_width(width: f32) -> PaddableConstraintBuilder {
+    PaddableConstraint::MaxWidth(width).builder(REQUIRED)
+}
+/// Constrain the height of a widget to be at most `height`, less any padding
+pub fn max_height(height: f32) -> PaddableConstraintBuilder {
+    PaddableConstraint::MaxHeight(height).builder(REQUIRED)
+}
+/// Constrain the size of a widget to be at most `size`, less any padding
+pub fn max_size<T>(size: TypedSize2D<f32, T>) -> PaddableConstraintBuilder {
+    PaddableConstraint::MaxSize(size.to_untyped()).builder(REQUIRED)
+}
+/// Constrain the size of a widget to be at least `min` and at most `max`, less any padding
+pub fn between<T>(min: TypedSize2D<f32, T>, max: TypedSize2D<f32, T>) -> PaddableConstraintBuilder {
+    PaddableConstraint::Between(min.to_untyped(), max.to_untyped()).builder(REQUIRED)
+}
 pub fn aspect_ratio(aspect_ratio: f32) -> WidgetConstraintBuilder {
     WidgetConstraint::AspectRatio(aspect_ratio).builder(REQUIRED)
 }
@@ -50,6 +66,11 @@ pub fn center_vertical<T: LayoutRef>(widget: &T) -> WidgetConstraintBuilder {
     let widget = widget.layout_ref();
     WidgetConstraint::CenterVertical(widget.top, widget.bottom).builder(REQUIRED)
 }
+/// Place the center of a widget at an offset from the center of another widget.
+/// Doesn't take padding, since the offset already sets the distance between the centers.
+pub fn offset_from_center<T: LayoutRef>(widget: &T, x: f32, y: f32) -> WidgetConstraintBuilder {
+    WidgetConstraint::OffsetFromCenter(widget.layout_ref(), Point::new(x, y)).builder(REQUIRED)
+}
 
 pub fn align_top<T: LayoutRef>(widget: &T) -> PaddableConstraintBuilder {
     let widget = widget.layout_ref();
@@ -68,6 +89,26 @@ pub fn align_right<T: LayoutRef>(widget: &T) -> PaddableConstraintBuilder {
     PaddableConstraint::AlignRight(widget.right).builder(REQUIRED)
 }
 
+/// Align the baseline of the first line of text in a widget with another widget's baseline.
+/// If the other layout has no baseline, and can't create one, it's bottom is used instead.
+pub fn align_baseline<T: LayoutRef>(widget: &T) -> PaddableConstraintBuilder {
+    let baseline = widget.baseline_ref().unwrap_or_else(|| widget.layout_ref().bottom);
+    PaddableConstraint::AlignBaseline(baseline).builder(REQUIRED)
+}
+
+/// Align the leading edge of a widget with another widget's leading edge,
+/// the left edge in a left to right layout, or the right edge in a right to left layout
+pub fn align_leading<T: LayoutRef>(widget: &T) -> PaddableConstraintBuilder {
+    let widget = widget.layout_ref();
+    PaddableConstraint::AlignLeading(widget.left, widget.right).builder(REQUIRED)
+}
+/// Align the trailing edge of a widget with another widget's trailing edge,
+/// the right edge in a left to right layout, or the left edge in a right to left layout
+pub fn align_trailing<T: LayoutRef>(widget: &T) -> PaddableConstraintBuilder {
+    let widget = widget.layout_ref();
+    PaddableConstraint::AlignTrailing(widget.left, widget.right).builder(REQUIRED)
+}
+
 pub fn align_above<T: LayoutRef>(widget: &T) -> PaddableConstraintBuilder {
     let widget = widget.layout_ref();
     PaddableConstraint::AlignAbove(widget.top).builder(REQUIRED)
@@ -85,6 +126,15 @@ pub fn align_to_right_of<T: LayoutRef>(widget: &T) -> PaddableConstraintBuilder
     PaddableConstraint::AlignToRightOf(widget.right).builder(REQUIRED)
 }
 
+pub fn align_to_leading_of<T: LayoutRef>(widget: &T) -> PaddableConstraintBuilder {
+    let widget = widget.layout_ref();
+    PaddableConstraint::AlignToLeadingOf(widget.left, widget.right).builder(REQUIRED)
+}
+pub fn align_to_trailing_of<T: LayoutRef>(widget: &T) -> PaddableConstraintBuilder {
+    let widget = widget.layout_ref();
+    PaddableConstraint::AlignToTrailingOf(widget.left, widget.right).builder(REQUIRED)
+}
+
 pub fn above<T: LayoutRef>(widget: &T) -> PaddableConstraintBuilder {
     let widget = widget.layout_ref();
     PaddableConstraint::Above(widget.top).builder(REQUIRED)
@@ -102,6 +152,15 @@ pub fn to_right_of<T: LayoutRef>(widget: &T) -> PaddableConstraintBuilder {
     PaddableConstraint::ToRightOf(widget.right).builder(REQUIRED)
 }
 
+pub fn to_leading_of<T: LayoutRef>(widget: &T) -> PaddableConstraintBuilder {
+    let widget = widget.layout_ref();
+    PaddableConstraint::ToLeadingOf(widget.left, widget.right).builder(REQUIRED)
+}
+pub fn to_trailing_of<T: LayoutRef>(widget: &T) -> PaddableConstraintBuilder {
+    let widget = widget.layout_ref();
+    PaddableConstraint::ToTrailingOf(widget.left, widget.right).builder(REQUIRED)
+}
+
 pub fn bound_left<T: LayoutRef>(outer: &T) -> PaddableConstraintBuilder {
     let outer = outer.layout_ref();
     PaddableConstraint::BoundLeft(outer.left).builder(REQUIRED)
@@ -118,6 +177,14 @@ pub fn bound_bottom<T: LayoutRef>(outer: &T) -> PaddableConstraintBuilder {
     let outer = outer.layout_ref();
     PaddableConstraint::BoundBottom(outer.bottom).builder(REQUIRED)
 }
+pub fn bound_leading<T: LayoutRef>(outer: &T) -> PaddableConstraintBuilder {
+    let outer = outer.layout_ref();
+    PaddableConstraint::BoundLeading(outer.left, outer.right).builder(REQUIRED)
+}
+pub fn bound_trailing<T: LayoutRef>(outer: &T) -> PaddableConstraintBuilder {
+    let outer = outer.layout_ref();
+    PaddableConstraint::BoundTrailing(outer.left, outer.right).builder(REQUIRED)
+}
 
 pub fn bound_by<T: LayoutRef>(outer: &T) -> PaddableConstraintBuilder {
     let outer = outer.layout_ref();
@@ -136,6 +203,16 @@ pub fn match_height<T: LayoutRef>(widget: &T) -> PaddableConstraintBuilder {
     let widget = widget.layout_ref();
     PaddableConstraint::MatchHeight(widget.height).builder(REQUIRED)
 }
+/// Match the width of a widget to a fraction of another widget's width, eg. 0.3 for 30%
+pub fn width_fraction_of<T: LayoutRef>(widget: &T, fraction: f32) -> PaddableConstraintBuilder {
+    let widget = widget.layout_ref();
+    PaddableConstraint::WidthFractionOf(widget.width, fraction).builder(REQUIRED)
+}
+/// Match the height of a widget to a fraction of another widget's height, eg. 0.3 for 30%
+pub fn height_fraction_of<T: LayoutRef>(widget: &T, fraction: f32) -> PaddableConstraintBuilder {
+    let widget = widget.layout_ref();
+    PaddableConstraint::HeightFractionOf(widget.height, fraction).builder(REQUIRED)
+}
 
 #[derive(Debug, Copy, Clone)]
 pub enum WidgetConstraint {
@@ -153,6 +230,7 @@ pub enum WidgetConstraint {
     Center(LayoutVars),
     CenterHorizontal(Variable, Variable),
     CenterVertical(Variable, Variable),
+    OffsetFromCenter(LayoutVars, Point),
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -161,6 +239,7 @@ pub enum PaddableConstraint {
     AlignBottom(Variable),
     AlignLeft(Variable),
     AlignRight(Variable),
+    AlignBaseline(Variable),
     AlignAbove(Variable),
     AlignBelow(Variable),
     AlignToLeftOf(Variable),
@@ -177,6 +256,21 @@ pub enum PaddableConstraint {
     MatchLayout(LayoutVars),
     MatchWidth(Variable),
     MatchHeight(Variable),
+    WidthFractionOf(Variable, f32),
+    HeightFractionOf(Variable, f32),
+    MaxWidth(f32),
+    MaxHeight(f32),
+    MaxSize(Size),
+    Between(Size, Size),
+    // Constraints that depend on the layout direction, with the left and right of the other widget
+    AlignLeading(Variable, Variable),
+    AlignTrailing(Variable, Variable),
+    AlignToLeadingOf(Variable, Variable),
+    AlignToTrailingOf(Variable, Variable),
+    ToLeadingOf(Variable, Variable),
+    ToTrailingOf(Variable, Variable),
+    BoundLeading(Variable, Variable),
+    BoundTrailing(Variable, Variable),
 }
 
 impl WidgetConstraint {
@@ -189,6 +283,48 @@ impl WidgetConstraint {
 }
 
 impl PaddableConstraint {
+    /// True if the constraint is relative to the leading or trailing edge of a widget
+    pub fn is_directional(&self) -> bool {
+        match *self {
+            PaddableConstraint::AlignLeading(..) | PaddableConstraint::AlignTrailing(..) |
+            PaddableConstraint::AlignToLeadingOf(..) | PaddableConstraint::AlignToTrailingOf(..) |
+            PaddableConstraint::ToLeadingOf(..) | PaddableConstraint::ToTrailingOf(..) |
+            PaddableConstraint::BoundLeading(..) | PaddableConstraint::BoundTrailing(..) => true,
+            _ => false,
+        }
+    }
+    /// Replaces leading and trailing constraints with the equivalent left or right constraint
+    /// for the given direction
+    pub fn directed(self, direction: Direction) -> Self {
+        let left_to_right = direction == Direction::LeftToRight;
+        match self {
+            PaddableConstraint::AlignLeading(left, right) => {
+                if left_to_right { PaddableConstraint::AlignLeft(left) } else { PaddableConstraint::AlignRight(right) }
+            }
+            PaddableConstraint::AlignTrailing(left, right) => {
+                if left_to_right { PaddableConstraint::AlignRight(right) } else { PaddableConstraint::AlignLeft(left) }
+            }
+            PaddableConstraint::AlignToLeadingOf(left, right) => {
+                if left_to_right { PaddableConstraint::AlignToLeftOf(left) } else { PaddableConstraint::AlignToRightOf(right) }
+            }
+            PaddableConstraint::AlignToTrailingOf(left, right) => {
+                if left_to_right { PaddableConstraint::AlignToRightOf(right) } else { PaddableConstraint::AlignToLeftOf(left) }
+            }
+            PaddableConstraint::ToLeadingOf(left, right) => {
+                if left_to_right { PaddableConstraint::ToLeftOf(left) } else { PaddableConstraint::ToRightOf(right) }
+            }
+            PaddableConstraint::ToTrailingOf(left, right) => {
+                if left_to_right { PaddableConstraint::ToRightOf(right) } else { PaddableConstraint::ToLeftOf(left) }
+            }
+            PaddableConstraint::BoundLeading(left, right) => {
+                if left_to_right { PaddableConstraint::BoundLeft(left) } else { PaddableConstraint::BoundRight(right) }
+            }
+            PaddableConstraint::BoundTrailing(left, right) => {
+                if left_to_right { PaddableConstraint::BoundRight(right) } else { PaddableConstraint::BoundLeft(left) }
+            }
+            constraint => constraint,
+        }
+    }
     pub fn builder(self, default_strength: f64) -> PaddableConstraintBuilder {
         PaddableConstraintBuilder {
             constraint: self,
@@ -231,6 +367,19 @@ impl PaddableConstraintBuilder {
 
 pub trait ConstraintBuilder {
     fn build(&self, widget: &LayoutVars) -> Vec<Constraint>;
+    /// Builds the constraints for a layout with the given direction.
+    /// Only leading and trailing constraints depend on the direction.
+    fn build_directed(&self, widget: &LayoutVars, _: Direction) -> Vec<Constraint> {
+        self.build(widget)
+    }
+    /// True if `build_directed` gives different constraints for each direction
+    fn is_directional(&self) -> bool {
+        false
+    }
+    /// True if the built constraints use the layout's baseline, so it has to be created first
+    fn uses_baseline(&self) -> bool {
+        false
+    }
 }
 
 impl ConstraintBuilder for Constraint {
@@ -334,15 +483,24 @@ impl ConstraintBuilder for WidgetConstraintBuilder {
             WidgetConstraint::CenterVertical(top, bottom) => {
                 vec![ widget.top - top | EQ(REQUIRED) | bottom - widget.bottom ]
             }
+            WidgetConstraint::OffsetFromCenter(other, offset) => {
+                vec![
+                    widget.left + widget.right - other.left - other.right | EQ(strength) | 2.0 * offset.x,
+                    widget.top + widget.bottom - other.top - other.bottom | EQ(strength) | 2.0 * offset.y,
+                ]
+            }
         }
     }
 }
 
 impl ConstraintBuilder for PaddableConstraintBuilder {
     fn build(&self, widget: &LayoutVars) -> Vec<Constraint> {
+        self.build_directed(widget, Direction::LeftToRight)
+    }
+    fn build_directed(&self, widget: &LayoutVars, direction: Direction) -> Vec<Constraint> {
         let strength = self.strength;
         let padding = self.padding;
-        match self.constraint.clone() {
+        match self.constraint.directed(direction) {
             PaddableConstraint::AlignTop(top) => {
                 vec![ widget.top - top | EQ(strength) | padding ]
             }
@@ -355,6 +513,11 @@ impl ConstraintBuilder for PaddableConstraintBuilder {
             PaddableConstraint::AlignRight(right) => {
                 vec![ right - widget.right | EQ(strength) | padding ]
             }
+            PaddableConstraint::AlignBaseline(baseline) => {
+                // `Layout::add` creates the baseline first, see `uses_baseline`
+                let widget_baseline = widget.baseline.unwrap_or(widget.bottom);
+                vec![ widget_baseline - baseline | EQ(strength) | padding ]
+            }
             PaddableConstraint::AlignAbove(top) => {
                 vec![ top - widget.bottom | EQ(strength) | padding ]
             }
@@ -413,6 +576,47 @@ impl ConstraintBuilder for PaddableConstraintBuilder {
             PaddableConstraint::MatchHeight(height) => {
                 vec![ height - widget.height | EQ(strength) | padding ]
             }
+            PaddableConstraint::WidthFractionOf(width, fraction) => {
+                vec![ width * fraction - widget.width | EQ(strength) | padding ]
+            }
+            PaddableConstraint::HeightFractionOf(height, fraction) => {
+                vec![ height * fraction - widget.height | EQ(strength) | padding ]
+            }
+            PaddableConstraint::MaxWidth(width) => {
+                vec![ widget.width | LE(strength) | width - padding ]
+            }
+            PaddableConstraint::MaxHeight(height) => {
+                vec![ widget.height | LE(strength) | height - padding ]
+            }
+            PaddableConstraint::MaxSize(size) => {
+                vec![
+                    widget.width | LE(strength) | size.width - padding,
+                    widget.height | LE(strength) | size.height - padding,
+                ]
+            }
+            PaddableConstraint::Between(min, max) => {
+                vec![
+                    widget.width | GE(strength) | min.width - padding,
+                    widget.height | GE(strength) | min.height - padding,
+                    widget.width | LE(strength) | max.width - padding,
+                    widget.height | LE(strength) | max.height - padding,
+                ]
+            }
+            PaddableConstraint::AlignLeading(..) | PaddableConstraint::AlignTrailing(..) |
+            PaddableConstraint::AlignToLeadingOf(..) | PaddableConstraint::AlignToTrailingOf(..) |
+            PaddableConstraint::ToLeadingOf(..) | PaddableConstraint::ToTrailingOf(..) |
+            PaddableConstraint::BoundLeading(..) | PaddableConstraint::BoundTrailing(..) => {
+                unreachable!("directional constraints are replaced by `directed`")
+            }
+        }
+    }
+    fn is_directional(&self) -> bool {
+        self.constraint.is_directional()
+    }
+    fn uses_baseline(&self) -> bool {
+        match self.constraint {
+            PaddableConstraint::AlignBaseline(..) => true,
+            _ => false,
         }
     }
 }
@@ -425,10 +629,32 @@ impl <C: ConstraintBuilder> ConstraintBuilder for Vec<C> {
         }
         constraints
     }
+    fn build_directed(&self, widget: &LayoutVars, direction: Direction) -> Vec<Constraint> {
+        let mut constraints = Vec::new();
+        for builder in self {
+            constraints.extend(builder.build_directed(widget, direction));
+        }
+        constraints
+    }
+    fn is_directional(&self) -> bool {
+        self.iter().any(|builder| builder.is_directional())
+    }
+    fn uses_baseline(&self) -> bool {
+        self.iter().any(|builder| builder.uses_baseline())
+    }
 }
 
 impl ConstraintBuilder for Box<ConstraintBuilder> {
     fn build(&self, widget: &LayoutVars) -> Vec<Constraint> {
         self.as_ref().build(widget)
     }
+    fn build_directed(&self, widget: &LayoutVars, direction: Direction) -> Vec<Constraint> {
+        self.as_ref().build_directed(widget, direction)
+    }
+    fn is_directional(&self) -> bool {
+        self.as_ref().is_directional()
+    }
+    fn uses_baseline(&self) -> bool {
+        self.as_ref().uses_baseline()
+    }
 }
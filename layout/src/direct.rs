@@ -0,0 +1,753 @@
+//! Layout without the constraint solver, for large trees of simple layouts.
+//!
+//! A `DirectContainer` measures it's children from the bottom up, then arranges them from
+//! the top down within it's own bounds, so the children don't need any variables or constraints
+//! in the solver. Children with a fixed width or height, or that match the width or height of the
+//! container, are sized by the container. Children that do have other constraints of their own,
+//! or children outside of a `DirectContainer`, fall back to the solver, with the bounds given to
+//! them by the container suggested to the solver.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+use cassowary::{self, Variable, Constraint};
+use cassowary::strength::REQUIRED;
+
+use super::{LayoutId, LayoutVars, Layout, Direction, VarType, Rect, Point, Size};
+use super::linear_layout::{LinearLayoutSettings, Orientation, Spacing, ItemAlignment, Flex};
+use super::grid_layout::{GridLayoutSettings, GridPlacement, TrackSize};
+use super::stack_layout::{StackItem, Anchor};
+
+/// A child of a layout with a `DirectContainer`
+pub struct DirectChild<'a> {
+    pub id: LayoutId,
+    /// The size the child wants, set with `Layout::set_measured_size`, measured by it's own
+    /// `DirectContainer`, or the size solved by the solver
+    pub size: Size,
+    params: Option<&'a Any>,
+}
+
+impl<'a> DirectChild<'a> {
+    /// Gets the parameters set with `Layout::set_container_params`, if they are of type `T`
+    pub fn container_params<T: 'static>(&self) -> Option<&T> {
+        self.params.and_then(|params| params.downcast_ref::<T>())
+    }
+}
+
+/// Measures and arranges the children of a layout without the solver,
+/// set with `Layout::set_direct_container`
+pub trait DirectContainer {
+    /// The size the layout wants, given the sizes it's children want
+    fn measure(&self, children: &[DirectChild]) -> Size;
+    /// The bounds of each child within `bounds`, in the same order as `children`
+    fn arrange(&self, bounds: Rect, direction: Direction, children: &[DirectChild]) -> Vec<Rect>;
+}
+
+/// Arranges children along one axis, like a `LinearLayout` with the same settings.
+/// `Flex` params are supported, `ItemAlignment::Baseline` isn't, since baselines aren't measured.
+#[derive(Debug, Copy, Clone)]
+pub struct DirectLinearLayout {
+    settings: LinearLayoutSettings,
+}
+
+impl DirectLinearLayout {
+    pub fn new(settings: LinearLayoutSettings) -> Self {
+        if let ItemAlignment::Baseline = settings.item_align {
+            panic!("can't align baselines without the solver, use a `LinearLayout` instead");
+        }
+        DirectLinearLayout {
+            settings: settings,
+        }
+    }
+    /// Size of a child along the axis, before growing or shrinking
+    fn basis(&self, child: &DirectChild) -> f32 {
+        match child.container_params::<Flex>() {
+            Some(flex) => flex.basis,
+            None => axis_size(self.settings.orientation, child.size),
+        }
+    }
+    /// Total padding between, and for `Spacing::Around` around, `len` children
+    fn padding(&self, len: usize) -> f32 {
+        let gaps = match self.settings.spacing {
+            _ if len == 0 => 0,
+            Spacing::Around => len + 1,
+            _ => len - 1,
+        };
+        gaps as f32 * self.settings.padding
+    }
+}
+
+impl DirectContainer for DirectLinearLayout {
+    fn measure(&self, children: &[DirectChild]) -> Size {
+        let orientation = self.settings.orientation;
+        let sizes = children.iter().map(|child| self.basis(child));
+        let length = if self.settings.fill_equal {
+            sizes.fold(0.0, f32::max) * children.len() as f32
+        } else {
+            sizes.sum()
+        };
+        let cross = children.iter().map(|child| cross_size(orientation, child.size)).fold(0.0, f32::max);
+        make_size(orientation, length + self.padding(children.len()), cross)
+    }
+    fn arrange(&self, bounds: Rect, direction: Direction, children: &[DirectChild]) -> Vec<Rect> {
+        let settings = &self.settings;
+        let orientation = settings.orientation;
+        let len = children.len();
+        if len == 0 {
+            return Vec::new();
+        }
+        let length = axis_size(orientation, bounds.size);
+        let cross_length = cross_size(orientation, bounds.size);
+        let padding = self.padding(len);
+
+        let mut sizes: Vec<f32> = if settings.fill_equal {
+            vec![((length - padding) / len as f32).max(0.0); len]
+        } else {
+            children.iter().map(|child| self.basis(child)).collect()
+        };
+        let mut free = length - padding - sizes.iter().sum::<f32>();
+        let flex: Vec<Option<Flex>> = children.iter().map(|child| child.container_params::<Flex>().cloned()).collect();
+        if !settings.fill_equal {
+            let grow: f32 = flex.iter().filter_map(|flex| flex.map(|flex| flex.grow)).sum();
+            let shrink: f32 = flex.iter().filter_map(|flex| flex.map(|flex| flex.shrink)).sum();
+            if free > 0.0 && grow > 0.0 {
+                for (size, flex) in sizes.iter_mut().zip(&flex) {
+                    if let Some(flex) = *flex {
+                        *size += free * flex.grow / grow;
+                    }
+                }
+                free = 0.0;
+            } else if free < 0.0 && shrink > 0.0 {
+                for (size, flex) in sizes.iter_mut().zip(&flex) {
+                    if let Some(flex) = *flex {
+                        *size = (*size + free * flex.shrink / shrink).max(0.0);
+                    }
+                }
+                free = 0.0;
+            }
+        }
+        let (mut position, gap) = match settings.spacing {
+            Spacing::End => (0.0, settings.padding),
+            Spacing::Start => (free, settings.padding),
+            Spacing::Between if len > 1 => (0.0, settings.padding + free.max(0.0) / (len - 1) as f32),
+            Spacing::Between => (0.0, settings.padding),
+            Spacing::Around => {
+                let gap = settings.padding + free.max(0.0) / (len + 1) as f32;
+                (gap, gap)
+            }
+        };
+        let mut rects = Vec::new();
+        for (child, size) in children.iter().zip(sizes) {
+            let cross = cross_size(orientation, child.size).min(cross_length);
+            let (cross_position, cross) = match settings.item_align {
+                ItemAlignment::Fill => (0.0, cross_length),
+                ItemAlignment::Center => ((cross_length - cross) / 2.0, cross),
+                ItemAlignment::Bottom | ItemAlignment::Right => (cross_length - cross, cross),
+                _ => (0.0, cross),
+            };
+            let rect = match orientation {
+                Orientation::Horizontal => {
+                    let x = match direction {
+                        Direction::LeftToRight => bounds.min_x() + position,
+                        Direction::RightToLeft => bounds.max_x() - position - size,
+                    };
+                    Rect::new(Point::new(x, bounds.min_y() + cross_position), Size::new(size, cross))
+                }
+                Orientation::Vertical => {
+                    Rect::new(Point::new(bounds.min_x() + cross_position, bounds.min_y() + position), Size::new(cross, size))
+                }
+            };
+            rects.push(rect);
+            position += size + gap;
+        }
+        rects
+    }
+}
+
+/// Arranges children in a grid, like a `GridLayout` with the same settings and `GridPlacement` params.
+/// Children fill their cells, and `Auto` tracks fit the largest child that spans only that track.
+#[derive(Debug, Clone)]
+pub struct DirectGridLayout {
+    settings: GridLayoutSettings,
+}
+
+impl DirectGridLayout {
+    pub fn new(settings: GridLayoutSettings) -> Self {
+        assert!(settings.columns.len() > 0, "can't create grid layout with no columns");
+        DirectGridLayout {
+            settings: settings,
+        }
+    }
+    fn placements(&self, children: &[DirectChild]) -> Vec<GridPlacement> {
+        let num_columns = self.settings.columns.len();
+        let mut next_cell = (0, 0);
+        children.iter().map(|child| {
+            let mut placement = match child.container_params::<GridPlacement>() {
+                Some(placement) => *placement,
+                None => {
+                    let (row, column) = next_cell;
+                    next_cell = if column + 1 < num_columns { (row, column + 1) } else { (row + 1, 0) };
+                    GridPlacement::new(row, column)
+                }
+            };
+            placement.row_span = placement.row_span.max(1);
+            placement.column_span = placement.column_span.max(1);
+            placement
+        }).collect()
+    }
+    /// Sizes of the columns and rows, fractional tracks share `available` space if it's known
+    fn tracks(&self, children: &[DirectChild], placements: &[GridPlacement], available: Option<Size>) -> (Vec<f32>, Vec<f32>) {
+        let settings = &self.settings;
+        let columns = placements.iter().map(|placement| placement.column + placement.column_span)
+            .fold(settings.columns.len(), usize::max);
+        let rows = placements.iter().map(|placement| placement.row + placement.row_span)
+            .fold(settings.rows.len(), usize::max);
+        let column_items: Vec<_> = children.iter().zip(placements).map(|(child, placement)| {
+            (placement.column, placement.column_span, child.size.width)
+        }).collect();
+        let row_items: Vec<_> = children.iter().zip(placements).map(|(child, placement)| {
+            (placement.row, placement.row_span, child.size.height)
+        }).collect();
+        let column_sizes = track_sizes(&settings.columns, settings.implicit_column, columns, settings.column_gap,
+                                       available.map(|size| size.width), &column_items);
+        let row_sizes = track_sizes(&settings.rows, settings.implicit_row, rows, settings.row_gap,
+                                    available.map(|size| size.height), &row_items);
+        (column_sizes, row_sizes)
+    }
+}
+
+impl DirectContainer for DirectGridLayout {
+    fn measure(&self, children: &[DirectChild]) -> Size {
+        let placements = self.placements(children);
+        let (columns, rows) = self.tracks(children, &placements, None);
+        Size::new(tracks_length(&columns, self.settings.column_gap), tracks_length(&rows, self.settings.row_gap))
+    }
+    fn arrange(&self, bounds: Rect, _: Direction, children: &[DirectChild]) -> Vec<Rect> {
+        let placements = self.placements(children);
+        let (columns, rows) = self.tracks(children, &placements, Some(bounds.size));
+        let column_starts = track_starts(&columns, bounds.min_x(), self.settings.column_gap);
+        let row_starts = track_starts(&rows, bounds.min_y(), self.settings.row_gap);
+        placements.iter().map(|placement| {
+            let (left, right) = span(&column_starts, &columns, placement.column, placement.column_span);
+            let (top, bottom) = span(&row_starts, &rows, placement.row, placement.row_span);
+            Rect::new(Point::new(left, top), Size::new(right - left, bottom - top))
+        }).collect()
+    }
+}
+
+/// Sizes of `count` tracks along one axis of a grid, `items` are the start, span and size of each child
+fn track_sizes(sizes: &[TrackSize], implicit_size: TrackSize, count: usize, gap: f32,
+               available: Option<f32>, items: &[(usize, usize, f32)]) -> Vec<f32> {
+    let track = |index: usize| sizes.get(index).cloned().unwrap_or(implicit_size);
+    // largest child that spans only the given track
+    let content = |index: usize| {
+        items.iter().filter(|&&(start, span, _)| start == index && span == 1)
+            .map(|&(_, _, size)| size).fold(0.0, f32::max)
+    };
+    let mut result: Vec<f32> = (0..count).map(|index| match track(index) {
+        TrackSize::Fixed(size) => size,
+        TrackSize::Auto => content(index),
+        TrackSize::Fraction(_) => 0.0,
+    }).collect();
+    let weights: Vec<(usize, f32)> = (0..count).filter_map(|index| match track(index) {
+        TrackSize::Fraction(weight) => Some((index, weight)),
+        _ => None,
+    }).collect();
+    let total_weight: f32 = weights.iter().map(|&(_, weight)| weight).sum();
+    if total_weight <= 0.0 {
+        return result;
+    }
+    let unit = match available {
+        Some(available) => {
+            let used = tracks_length(&result, gap);
+            (available - used).max(0.0) / total_weight
+        }
+        // without a size to fill, fractional tracks are sized so every child fits
+        None => weights.iter().map(|&(index, weight)| content(index) / weight).fold(0.0, f32::max),
+    };
+    for (index, weight) in weights {
+        result[index] = unit * weight;
+    }
+    result
+}
+
+fn tracks_length(tracks: &[f32], gap: f32) -> f32 {
+    tracks.iter().sum::<f32>() + gap * tracks.len().saturating_sub(1) as f32
+}
+
+fn track_starts(tracks: &[f32], start: f32, gap: f32) -> Vec<f32> {
+    let mut position = start;
+    tracks.iter().map(|size| {
+        let track_start = position;
+        position += size + gap;
+        track_start
+    }).collect()
+}
+
+/// Start of the first and end of the last track in a span
+fn span(starts: &[f32], sizes: &[f32], index: usize, span: usize) -> (f32, f32) {
+    let last = index + span - 1;
+    (starts[index], starts[last] + sizes[last])
+}
+
+/// Places children on top of each other, like a `StackLayout` with the same `StackItem` params
+#[derive(Debug, Copy, Clone)]
+pub struct DirectStackLayout {
+    default_item: StackItem,
+}
+
+impl DirectStackLayout {
+    pub fn new() -> Self {
+        DirectStackLayout::with_default(StackItem::default())
+    }
+    /// Creates a `DirectStackLayout` that aligns children without a `StackItem` using `default_item`
+    pub fn with_default(default_item: StackItem) -> Self {
+        DirectStackLayout {
+            default_item: default_item,
+        }
+    }
+}
+
+impl DirectContainer for DirectStackLayout {
+    fn measure(&self, children: &[DirectChild]) -> Size {
+        children.iter().fold(Size::zero(), |size, child| {
+            let margin = child.container_params::<StackItem>().unwrap_or(&self.default_item).margin;
+            Size::new(size.width.max(child.size.width + margin * 2.0),
+                      size.height.max(child.size.height + margin * 2.0))
+        })
+    }
+    fn arrange(&self, bounds: Rect, _: Direction, children: &[DirectChild]) -> Vec<Rect> {
+        children.iter().map(|child| {
+            let item = child.container_params::<StackItem>().cloned().unwrap_or(self.default_item);
+            let margin = item.margin;
+            let inner = Rect::new(
+                Point::new(bounds.min_x() + margin, bounds.min_y() + margin),
+                Size::new((bounds.size.width - margin * 2.0).max(0.0), (bounds.size.height - margin * 2.0).max(0.0)));
+            // position within the free space on each axis, 0 for the start, 1 for the end
+            let (horizontal, vertical) = match item.anchor {
+                Anchor::TopLeft => (0.0, 0.0),
+                Anchor::Top => (0.5, 0.0),
+                Anchor::TopRight => (1.0, 0.0),
+                Anchor::Left => (0.0, 0.5),
+                Anchor::Center => (0.5, 0.5),
+                Anchor::Right => (1.0, 0.5),
+                Anchor::BottomLeft => (0.0, 1.0),
+                Anchor::Bottom => (0.5, 1.0),
+                Anchor::BottomRight => (1.0, 1.0),
+                Anchor::Fill => return inner,
+            };
+            let size = Size::new(child.size.width.min(inner.size.width), child.size.height.min(inner.size.height));
+            let origin = Point::new(inner.min_x() + (inner.size.width - size.width) * horizontal,
+                                    inner.min_y() + (inner.size.height - size.height) * vertical);
+            Rect::new(origin, size)
+        }).collect()
+    }
+}
+
+fn axis_size(orientation: Orientation, size: Size) -> f32 {
+    match orientation {
+        Orientation::Horizontal => size.width,
+        Orientation::Vertical => size.height,
+    }
+}
+fn cross_size(orientation: Orientation, size: Size) -> f32 {
+    match orientation {
+        Orientation::Horizontal => size.height,
+        Orientation::Vertical => size.width,
+    }
+}
+fn make_size(orientation: Orientation, length: f32, cross: f32) -> Size {
+    match orientation {
+        Orientation::Horizontal => Size::new(length, cross),
+        Orientation::Vertical => Size::new(cross, length),
+    }
+}
+
+/// A length set by a constraint that a `DirectContainer` applies itself, rather than the solver
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum DirectLength {
+    Fixed(f32),
+    /// The length of the parent, plus an offset
+    MatchParent(f32),
+}
+
+/// The width and height set by the constraints of a layout arranged by a `DirectContainer`
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+struct DirectSize {
+    width: Option<DirectLength>,
+    height: Option<DirectLength>,
+}
+
+impl DirectSize {
+    /// Finds the lengths set by the current constraints of an arranged layout, returns `None` if
+    /// the layout isn't arranged, or has constraints or edit variables that need the solver
+    fn from_layout(layout: &Layout) -> Option<Self> {
+        let parent = match layout.arranged_by {
+            Some(parent) => parent,
+            None => return None,
+        };
+        if !layout.edit_vars.is_empty() {
+            return None;
+        }
+        let mut size = DirectSize::default();
+        let constraints = layout.constraints.iter().chain(layout.new_constraints.iter())
+            .filter(|constraint| !layout.removed_constraints.contains(constraint))
+            .filter(|constraint| !layout.vars.is_intrinsic(constraint));
+        for constraint in constraints {
+            match direct_length(constraint, &layout.vars, &parent) {
+                Some((VarType::Width, length)) => size.width = Some(length),
+                Some((_, length)) => size.height = Some(length),
+                None => return None,
+            }
+        }
+        Some(size)
+    }
+    /// The size a layout wants, with fixed lengths replacing the measured ones
+    fn measure(&self, size: Size) -> Size {
+        let length = |length, measured| match length {
+            Some(DirectLength::Fixed(length)) => length,
+            _ => measured,
+        };
+        Size::new(length(self.width, size.width), length(self.height, size.height))
+    }
+    /// The size of a layout arranged by a parent with the size `parent`
+    fn arrange(&self, size: Size, parent: Size) -> Size {
+        let length = |length, arranged, parent: f32| match length {
+            Some(DirectLength::Fixed(length)) => length,
+            Some(DirectLength::MatchParent(offset)) => parent + offset,
+            None => arranged,
+        };
+        Size::new(length(self.width, size.width, parent.width),
+                  length(self.height, size.height, parent.height))
+    }
+}
+
+/// Matches a required constraint that sets the width or height of a layout to a fixed length,
+/// or to the width or height of it's parent, like the ones built by `width` or `match_width`
+fn direct_length(constraint: &Constraint, vars: &LayoutVars, parent: &LayoutVars) -> Option<(VarType, DirectLength)> {
+    if constraint.strength() != REQUIRED || constraint.op() != cassowary::RelationalOperator::Equal {
+        return None;
+    }
+    let constant = constraint.expr().constant;
+    match constraint.expr().terms.as_slice() {
+        &[ref term] => {
+            let var_type = vars.var_type(term.variable);
+            match var_type {
+                VarType::Width | VarType::Height => {
+                    Some((var_type, DirectLength::Fixed((-constant / term.coefficient) as f32)))
+                }
+                _ => None,
+            }
+        }
+        &[ref first, ref second] => {
+            for &(own, other) in &[(first, second), (second, first)] {
+                let var_type = vars.var_type(own.variable);
+                let matched = match var_type {
+                    VarType::Width => parent.width,
+                    VarType::Height => parent.height,
+                    _ => continue,
+                };
+                if other.variable == matched && other.coefficient == -own.coefficient {
+                    return Some((var_type, DirectLength::MatchParent((-constant / own.coefficient) as f32)));
+                }
+            }
+            None
+        }
+        _ => None,
+    }
+}
+
+/// A layout with, or in, a `DirectContainer`
+struct DirectNode {
+    vars: LayoutVars,
+    parent: Option<LayoutId>,
+    children: Vec<LayoutId>,
+    container: Option<Rc<RefCell<DirectContainer>>>,
+    params: Option<Rc<Any>>,
+    measured_size: Option<Size>,
+    /// Lengths set by the layout's constraints, for layouts that aren't solved
+    direct_size: DirectSize,
+    direction: Direction,
+    hidden: bool,
+    /// True if the layout's parent has a `DirectContainer`
+    arranged: bool,
+    /// True if the layout's bounds are solved by the solver, rather than set by the parent's container
+    solved: bool,
+    /// The bounds last set by the parent's container, for layouts that aren't solved
+    rect: Option<Rect>,
+    /// The solved bounds last used to arrange the layout, or it's parent, for layouts that are solved
+    solved_rect: Option<Rect>,
+    /// Associated variables of a layout that isn't solved, kept until it is
+    associated_vars: Vec<(Variable, String)>,
+}
+
+/// Tracks the layouts with, or in, a `DirectContainer` for a `LimnSolver`
+pub(crate) struct DirectLayouts {
+    nodes: HashMap<LayoutId, DirectNode>,
+    /// Layouts that changed since they were last arranged
+    dirty: HashSet<LayoutId>,
+    /// Layouts that are solved by the solver
+    solved: HashSet<LayoutId>,
+}
+
+impl DirectLayouts {
+    pub(crate) fn new() -> Self {
+        DirectLayouts {
+            nodes: HashMap::new(),
+            dirty: HashSet::new(),
+            solved: HashSet::new(),
+        }
+    }
+
+    /// Records the current state of a layout, returns false if the layout is arranged
+    /// by it's parent and doesn't need to be passed to the solver.
+    /// `referenced` is true if the solver has constraints or edit variables for the layout,
+    /// other than it's own constraints.
+    pub(crate) fn update_layout(&mut self, layout: &mut Layout, referenced: bool) -> bool {
+        let id = layout.id;
+        if layout.arranged_by.is_none() && layout.direct_container.is_none() && !self.nodes.contains_key(&id) {
+            return true;
+        }
+        let was_solved = self.nodes.get(&id).map(|node| node.solved);
+        let direct_size = DirectSize::from_layout(layout);
+        // children outside of a `DirectContainer` can have constraints relative to this layout
+        let has_solved_children = layout.direct_container.is_none() && !layout.children.is_empty();
+        let solved = direct_size.is_none() || has_solved_children || referenced;
+
+        let vars = layout.vars;
+        let node = self.nodes.entry(id).or_insert_with(|| DirectNode {
+            vars: vars,
+            parent: None,
+            children: Vec::new(),
+            container: None,
+            params: None,
+            measured_size: None,
+            direct_size: DirectSize::default(),
+            direction: Direction::default(),
+            hidden: false,
+            arranged: false,
+            solved: false,
+            rect: None,
+            solved_rect: None,
+            associated_vars: Vec::new(),
+        });
+        node.parent = layout.parent;
+        node.children = layout.children.clone();
+        node.container = layout.direct_container.clone();
+        node.params = layout.container_params.clone();
+        node.measured_size = layout.measured_size;
+        node.direct_size = direct_size.unwrap_or_default();
+        node.direction = layout.direction;
+        node.hidden = layout.hidden;
+        node.arranged = layout.arranged_by.is_some();
+        node.solved = solved;
+        if solved {
+            if was_solved == Some(false) {
+                // passed to the solver for the first time, along with everything held back until now
+                layout.requeue_constraints();
+                layout.associated_vars.extend(node.associated_vars.drain(..));
+            }
+            node.rect = None;
+            self.solved.insert(id);
+        } else {
+            layout.get_constraints();
+            layout.get_removed_constraints();
+            node.associated_vars.extend(layout.get_associated_vars());
+            node.solved_rect = None;
+            self.solved.remove(&id);
+        }
+        self.dirty.insert(id);
+        solved
+    }
+
+    /// Keeps the associated variables of a layout that was removed from the solver,
+    /// until it's passed to the solver again
+    pub(crate) fn hold_associated_vars(&mut self, id: LayoutId, associated_vars: Vec<(Variable, String)>) {
+        if let Some(node) = self.nodes.get_mut(&id) {
+            node.associated_vars.extend(associated_vars);
+        }
+    }
+
+    /// Stops tracking a layout, returns it's variables if it was tracked
+    pub(crate) fn remove_layout(&mut self, id: LayoutId) -> Option<LayoutVars> {
+        self.dirty.remove(&id);
+        self.solved.remove(&id);
+        match self.nodes.remove(&id) {
+            Some(node) => {
+                // the parent's remaining children are arranged again
+                if let Some(parent) = node.parent {
+                    self.dirty.insert(parent);
+                }
+                Some(node.vars)
+            }
+            None => None,
+        }
+    }
+
+    /// Marks layouts in the solver as changed if the solved bounds that affect how they're arranged
+    /// moved since they were last arranged, `solved_rect` gives the bounds of layouts in the solver
+    pub(crate) fn mark_moved<F>(&mut self, solved_rect: &F) where F: Fn(&LayoutVars) -> Rect {
+        for id in &self.solved {
+            let node = self.nodes.get_mut(id).expect("solved layout isn't tracked");
+            if node.container.is_none() && node.measured_size.is_some() {
+                continue;
+            }
+            let mut rect = solved_rect(&node.vars);
+            if node.container.is_none() {
+                // only the size of a solved child affects how it's parent is arranged
+                rect.origin = Point::zero();
+            }
+            if node.solved_rect != Some(rect) {
+                node.solved_rect = Some(rect);
+                self.dirty.insert(*id);
+            }
+        }
+    }
+
+    /// Takes the layouts in the solver with a `DirectContainer` that have to be measured and
+    /// arranged again, because they, or layouts they arrange, changed
+    pub(crate) fn dirty_roots(&mut self) -> Vec<LayoutId> {
+        let mut roots = HashSet::new();
+        let mut visited = HashSet::new();
+        for id in self.dirty.drain() {
+            // every container above a layout can depend on it's measured size
+            let mut current = Some(id);
+            while let Some(id) = current {
+                if !visited.insert(id) {
+                    break;
+                }
+                current = match self.nodes.get(&id) {
+                    Some(node) => {
+                        if node.solved && node.container.is_some() {
+                            roots.insert(id);
+                        }
+                        node.parent
+                    }
+                    None => None,
+                };
+            }
+        }
+        let mut roots: Vec<LayoutId> = roots.into_iter().collect();
+        roots.sort();
+        roots
+    }
+
+    /// Records the solved bounds a root was arranged within
+    pub(crate) fn set_solved_rect(&mut self, id: LayoutId, rect: Rect) {
+        if let Some(node) = self.nodes.get_mut(&id) {
+            node.solved_rect = Some(rect);
+        }
+    }
+
+    pub(crate) fn vars(&self, id: LayoutId) -> LayoutVars {
+        self.nodes[&id].vars
+    }
+    pub(crate) fn is_solved(&self, id: LayoutId) -> bool {
+        self.nodes[&id].solved
+    }
+
+    /// Children of a layout that are arranged by it's container
+    fn visible_children(&self, id: LayoutId) -> Vec<LayoutId> {
+        self.nodes[&id].children.iter().cloned().filter(|child| {
+            self.nodes.get(child).map_or(false, |child| child.arranged && !child.hidden)
+        }).collect()
+    }
+
+    fn direct_children<'a>(&'a self, children: &[LayoutId], sizes: &HashMap<LayoutId, Size>) -> Vec<DirectChild<'a>> {
+        children.iter().map(|child| DirectChild {
+            id: *child,
+            size: sizes[child],
+            params: self.nodes[child].params.as_ref().map(|params| &**params),
+        }).collect()
+    }
+
+    /// Measures a layout and it's arranged children, `solved_size` gives the size of layouts in the solver
+    pub(crate) fn measure<F>(&self, id: LayoutId, solved_size: &F, sizes: &mut HashMap<LayoutId, Size>) -> Size
+        where F: Fn(&LayoutVars) -> Size
+    {
+        if let Some(size) = sizes.get(&id) {
+            return *size;
+        }
+        let node = &self.nodes[&id];
+        let container_size = node.container.as_ref().map(|container| {
+            let visible = self.visible_children(id);
+            for child in &visible {
+                self.measure(*child, solved_size, sizes);
+            }
+            let children = self.direct_children(&visible, sizes);
+            container.borrow().measure(&children)
+        });
+        let size = node.measured_size.or(container_size).unwrap_or_else(|| {
+            if node.solved { solved_size(&node.vars) } else { Size::zero() }
+        });
+        let size = node.direct_size.measure(size);
+        sizes.insert(id, size);
+        size
+    }
+
+    /// Arranges the children of a layout within `bounds`, and their children if they aren't solved.
+    /// The bounds of each arranged layout are added to `rects`.
+    pub(crate) fn arrange(&self, id: LayoutId, bounds: Rect, sizes: &HashMap<LayoutId, Size>,
+                          rects: &mut Vec<(LayoutId, Rect)>) {
+        let node = &self.nodes[&id];
+        let container = match node.container {
+            Some(ref container) => container,
+            None => return,
+        };
+        let visible = if node.hidden { Vec::new() } else { self.visible_children(id) };
+        let mut children = self.direct_children(&visible, sizes);
+        for child in &mut children {
+            child.size = self.nodes[&child.id].direct_size.arrange(child.size, bounds.size);
+        }
+        let child_rects = container.borrow().arrange(bounds, node.direction, &children);
+        for (child, rect) in visible.iter().zip(child_rects) {
+            // lengths set by the child's constraints override the container's
+            let rect = Rect::new(rect.origin, self.nodes[child].direct_size.arrange(rect.size, bounds.size));
+            rects.push((*child, rect));
+            if !self.nodes[child].solved {
+                self.arrange(*child, rect, sizes, rects);
+            }
+        }
+        // hidden layouts take up no space, layouts in the solver are hidden by the solver
+        for child in &node.children {
+            if !visible.contains(child) {
+                self.collapse(*child, rects);
+            }
+        }
+    }
+    fn collapse(&self, id: LayoutId, rects: &mut Vec<(LayoutId, Rect)>) {
+        if let Some(node) = self.nodes.get(&id) {
+            if node.arranged && !node.solved {
+                rects.push((id, Rect::zero()));
+                for child in &node.children {
+                    self.collapse(*child, rects);
+                }
+            }
+        }
+    }
+
+    /// Stores the arranged bounds of layouts that aren't solved, returns the bounds that changed.
+    /// Layouts arranged more than once keep the bounds they were arranged with last.
+    pub(crate) fn update_rects(&mut self, rects: Vec<(LayoutId, Rect)>) -> Vec<(LayoutId, Rect)> {
+        let mut order = Vec::new();
+        let mut latest = HashMap::new();
+        for (id, rect) in rects {
+            if latest.insert(id, rect).is_none() {
+                order.push(id);
+            }
+        }
+        let mut changed = Vec::new();
+        for id in order {
+            let rect = latest[&id];
+            if let Some(node) = self.nodes.get_mut(&id) {
+                if node.rect != Some(rect) {
+                    node.rect = Some(rect);
+                    changed.push((id, rect));
+                }
+            }
+        }
+        changed
+    }
+}
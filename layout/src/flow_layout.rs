@@ -0,0 +1,219 @@
+use std::cmp;
+use std::mem;
+
+use cassowary::strength::*;
+use cassowary::WeightedRelation::*;
+use cassowary::{Variable, Constraint};
+
+use super::{LayoutId, LayoutVars, Layout, LayoutContainer, LimnSolver};
+
+/// Weaker than any constraint added to the parent, so it only decides the parent's size
+/// when nothing else does, eg. a parent that shrinks to fit, where `shrink` and the `Frame`
+/// constraints are equally weak
+const HUG_ROWS: f64 = WEAK * 0.5;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum RowAlignment {
+    /// Items in each row packed against the parent's left bound
+    Start,
+    /// Items in each row centered within the parent's width
+    Center,
+    /// Items in each row packed against the parent's right bound
+    End,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct FlowLayoutSettings {
+    /// Horizontal space between items in the same row
+    pub item_spacing: f32,
+    /// Vertical space between rows
+    pub row_spacing: f32,
+    /// Alignment of the items within each row
+    pub row_align: RowAlignment,
+}
+
+impl FlowLayoutSettings {
+
+    /// Creates a default `FlowLayoutSettings`, with no spacing and
+    /// rows aligned to the start of the parent
+    pub fn new() -> Self {
+        FlowLayoutSettings {
+            item_spacing: 0.0,
+            row_spacing: 0.0,
+            row_align: RowAlignment::Start,
+        }
+    }
+}
+
+impl Default for FlowLayoutSettings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Arranges children from left to right, starting a new row whenever the next child
+/// would extend past the right edge of the parent.
+///
+/// Which row a child belongs to depends on the solved widths of the parent and the children,
+/// so rows are recalculated in `layout_changed`, after each solve, and the constraints
+/// are only rebuilt when children move to a different row.
+///
+/// If the parent's width depends on it's children, eg. a parent that shrinks to fit,
+/// moving children to a different row changes the parent's width, which could move them back.
+/// To prevent re-flowing indefinitely, the solve that follows a re-flow only re-flows again
+/// if the children changed size, not if only the parent's width changed.
+pub struct FlowLayout {
+    settings: FlowLayoutSettings,
+    parent: LayoutVars,
+    children: Vec<(LayoutId, LayoutVars)>,
+    /// Number of children in each row
+    rows: Vec<usize>,
+    /// Top and bottom of each row, reused when the children are re-flowed
+    row_vars: Vec<(Variable, Variable)>,
+    constraints: Vec<Constraint>,
+    /// Widths of the children when they were last re-flowed in `layout_changed`,
+    /// until the layout has been solved with the new rows
+    reflowed_widths: Option<Vec<f32>>,
+}
+
+impl FlowLayout {
+    pub fn new(parent: &mut Layout, settings: FlowLayoutSettings) -> Self {
+        FlowLayout {
+            settings: settings,
+            parent: parent.vars,
+            children: Vec::new(),
+            rows: Vec::new(),
+            row_vars: Vec::new(),
+            constraints: Vec::new(),
+            reflowed_widths: None,
+        }
+    }
+
+    fn child_widths(&self, solver: &LimnSolver) -> Vec<f32> {
+        self.children.iter().map(|&(_, ref vars)| solver.get_value(vars.width) as f32).collect()
+    }
+
+    /// Split the children into rows, based on their current solved widths
+    fn calculate_rows(&self, solver: &LimnSolver) -> Vec<usize> {
+        let max_width = solver.get_value(self.parent.width) as f32;
+        let spacing = self.settings.item_spacing;
+        let mut rows = Vec::new();
+        let mut row_len = 0;
+        let mut row_width = 0.0;
+        for &(_, ref vars) in &self.children {
+            let width = solver.get_value(vars.width) as f32;
+            // small tolerance so rounding errors in the solver don't cause items to wrap
+            if row_len > 0 && row_width + spacing + width > max_width + 0.001 {
+                rows.push(row_len);
+                row_len = 0;
+            }
+            row_width = if row_len == 0 { width } else { row_width + spacing + width };
+            row_len += 1;
+        }
+        if row_len > 0 {
+            rows.push(row_len);
+        }
+        rows
+    }
+
+    /// Replace the current constraints with constraints for the given rows
+    fn flow(&mut self, parent: &mut Layout, rows: Vec<usize>) {
+        parent.remove_constraints(mem::replace(&mut self.constraints, Vec::new()));
+        while self.row_vars.len() < rows.len() {
+            let (top, bottom) = (Variable::new(), Variable::new());
+            let row = self.row_vars.len();
+            parent.add_associated_var(top, &format!("row_{}.top", row));
+            parent.add_associated_var(bottom, &format!("row_{}.bottom", row));
+            self.row_vars.push((top, bottom));
+        }
+        let settings = self.settings;
+        let parent_vars = self.parent;
+        let mut constraints = Vec::new();
+        let mut children = self.children.iter().map(|&(_, vars)| vars);
+        let mut last_bottom: Option<Variable> = None;
+        for (row, &row_len) in rows.iter().enumerate() {
+            let (top, bottom) = self.row_vars[row];
+            if let Some(last_bottom) = last_bottom {
+                constraints.push(top | EQ(REQUIRED) | last_bottom + settings.row_spacing);
+            } else {
+                constraints.push(top | EQ(REQUIRED) | parent_vars.top);
+            }
+            constraints.push(bottom | GE(REQUIRED) | top);
+            // rows shrink to fit the tallest item
+            constraints.push(bottom | EQ(MEDIUM) | top);
+
+            let items: Vec<LayoutVars> = children.by_ref().take(row_len).collect();
+            for (index, item) in items.iter().enumerate() {
+                constraints.push(item.top | EQ(REQUIRED) | top);
+                constraints.push(item.bottom | LE(REQUIRED) | bottom);
+                if index > 0 {
+                    let prev = items[index - 1];
+                    constraints.push(item.left | EQ(REQUIRED) | prev.right + settings.item_spacing);
+                }
+            }
+            if let (Some(first), Some(last)) = (items.first(), items.last()) {
+                constraints.push(match settings.row_align {
+                    RowAlignment::Start => first.left | EQ(REQUIRED) | parent_vars.left,
+                    RowAlignment::Center => first.left - parent_vars.left | EQ(REQUIRED) | parent_vars.right - last.right,
+                    RowAlignment::End => last.right | EQ(REQUIRED) | parent_vars.right,
+                });
+                constraints.push(parent_vars.right - last.right | EQ(HUG_ROWS) | 0.0);
+            }
+            last_bottom = Some(bottom);
+        }
+        if let Some(last_bottom) = last_bottom {
+            constraints.push(parent_vars.bottom | GE(STRONG) | last_bottom);
+            constraints.push(parent_vars.bottom | EQ(HUG_ROWS) | last_bottom);
+        }
+        parent.add(constraints.clone());
+        self.constraints = constraints;
+        self.rows = rows;
+    }
+}
+
+impl LayoutContainer for FlowLayout {
+    fn add_child(&mut self, parent: &mut Layout, child: &mut Layout) {
+        let index = parent.get_children().iter().position(|id| *id == child.id).unwrap_or(self.children.len());
+        let index = cmp::min(index, self.children.len());
+        self.children.insert(index, (child.id, child.vars));
+        // new children are added to the last row until the layout is solved
+        let mut rows = self.rows.clone();
+        if let Some(last_row) = rows.last_mut() {
+            *last_row += 1;
+        }
+        if rows.is_empty() {
+            rows.push(1);
+        }
+        self.flow(parent, rows);
+    }
+    fn remove_child(&mut self, parent: &mut Layout, child: &mut Layout) {
+        if let Some(pos) = self.children.iter().position(|&(id, _)| id == child.id) {
+            self.children.remove(pos);
+            let mut rows = self.rows.clone();
+            let mut start = 0;
+            for row_len in &mut rows {
+                if pos < start + *row_len {
+                    *row_len -= 1;
+                    break;
+                }
+                start += *row_len;
+            }
+            rows.retain(|&row_len| row_len > 0);
+            self.flow(parent, rows);
+        }
+    }
+    fn layout_changed(&mut self, parent: &mut Layout, solver: &LimnSolver) {
+        let widths = self.child_widths(solver);
+        // moving children to new rows always moves them, so this is called after every re-flow
+        if let Some(reflowed_widths) = self.reflowed_widths.take() {
+            if reflowed_widths == widths {
+                return;
+            }
+        }
+        let rows = self.calculate_rows(solver);
+        if rows != self.rows {
+            self.flow(parent, rows);
+            self.reflowed_widths = Some(widths);
+        }
+    }
+}
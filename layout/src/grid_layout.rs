@@ -1,86 +1,236 @@
+use std::cmp;
+
 use cassowary::strength::*;
 use cassowary::WeightedRelation::*;
+use cassowary::{Variable, Constraint};
+
+use super::{Layout, LayoutContainer};
+
+/// Size of a single row or column in a `GridLayout`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum TrackSize {
+    /// A fixed size, in pixels
+    Fixed(f32),
+    /// Sized to fit the children placed in the track
+    Auto,
+    /// A share of the remaining space, relative to the other fractional tracks,
+    /// eg. a `Fraction(2.0)` track is twice the size of a `Fraction(1.0)` track
+    Fraction(f32),
+}
+
+#[derive(Debug, Clone)]
+pub struct GridLayoutSettings {
+    /// Size of each column
+    pub columns: Vec<TrackSize>,
+    /// Size of each row, rows past the end of this list use `implicit_row`
+    pub rows: Vec<TrackSize>,
+    /// Space between columns
+    pub column_gap: f32,
+    /// Space between rows
+    pub row_gap: f32,
+    /// Size of rows created when children are placed past the last explicit row
+    pub implicit_row: TrackSize,
+    /// Size of columns created when children are placed past the last explicit column
+    pub implicit_column: TrackSize,
+}
+
+impl GridLayoutSettings {
+
+    /// Creates a `GridLayoutSettings` with the given columns, no gaps and
+    /// rows sized to fit their children
+    pub fn new(columns: Vec<TrackSize>) -> Self {
+        GridLayoutSettings {
+            columns: columns,
+            rows: Vec::new(),
+            column_gap: 0.0,
+            row_gap: 0.0,
+            implicit_row: TrackSize::Auto,
+            implicit_column: TrackSize::Auto,
+        }
+    }
+}
+
+/// Position of a child in a `GridLayout`, set with `Layout::set_container_params`
+/// before the child is added. Children without a `GridPlacement` fill the next cell,
+/// row by row, without regard to explicitly placed children.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct GridPlacement {
+    pub row: usize,
+    pub column: usize,
+    pub row_span: usize,
+    pub column_span: usize,
+}
+
+impl GridPlacement {
+    pub fn new(row: usize, column: usize) -> Self {
+        GridPlacement {
+            row: row,
+            column: column,
+            row_span: 1,
+            column_span: 1,
+        }
+    }
+    /// Extend this placement over multiple rows and columns
+    pub fn span(mut self, row_span: usize, column_span: usize) -> Self {
+        self.row_span = row_span;
+        self.column_span = column_span;
+        self
+    }
+}
+
+/// The rows or columns of a grid, along one axis
+#[derive(Debug, Clone)]
+struct Tracks {
+    name: &'static str,
+    sizes: Vec<TrackSize>,
+    implicit_size: TrackSize,
+    gap: f32,
+    parent_start: Variable,
+    parent_end: Variable,
+    /// Size of one unit of a fractional track
+    fraction: Variable,
+    /// Start and end of each track
+    tracks: Vec<(Variable, Variable)>,
+    end_constraint: Option<Constraint>,
+}
+
+impl Tracks {
+    fn new(parent: &mut Layout, name: &'static str, sizes: Vec<TrackSize>, implicit_size: TrackSize,
+           gap: f32, parent_start: Variable, parent_end: Variable) -> Self {
+        let fraction = Variable::new();
+        parent.add(fraction | GE(REQUIRED) | 0.0);
+        parent.add_associated_var(fraction, &format!("{}_fraction", name));
+        let num_tracks = sizes.len();
+        let mut tracks = Tracks {
+            name: name,
+            sizes: sizes,
+            implicit_size: implicit_size,
+            gap: gap,
+            parent_start: parent_start,
+            parent_end: parent_end,
+            fraction: fraction,
+            tracks: Vec::new(),
+            end_constraint: None,
+        };
+        tracks.extend(parent, num_tracks);
+        tracks
+    }
 
-use super::{LayoutVars, Layout, Constraint, LayoutContainer};
-use super::constraint::*;
+    fn size(&self, index: usize) -> TrackSize {
+        self.sizes.get(index).cloned().unwrap_or(self.implicit_size)
+    }
+
+    /// Add tracks until there are at least `len` tracks
+    fn extend(&mut self, parent: &mut Layout, len: usize) {
+        if len <= self.tracks.len() {
+            return;
+        }
+        let mut constraints = Vec::new();
+        while self.tracks.len() < len {
+            let index = self.tracks.len();
+            let (start, end) = (Variable::new(), Variable::new());
+            if let Some(&(_, last_end)) = self.tracks.last() {
+                constraints.push(start | EQ(REQUIRED) | last_end + self.gap);
+            } else {
+                constraints.push(start | EQ(REQUIRED) | self.parent_start);
+            }
+            constraints.push(end - start | GE(REQUIRED) | 0.0);
+            constraints.push(match self.size(index) {
+                TrackSize::Fixed(size) => end - start | EQ(REQUIRED) | size,
+                TrackSize::Auto => end - start | EQ(WEAK) | 0.0,
+                TrackSize::Fraction(weight) => end - start | EQ(REQUIRED) | self.fraction * weight,
+            });
+            parent.add_associated_var(start, &format!("{}_{}.start", self.name, index));
+            parent.add_associated_var(end, &format!("{}_{}.end", self.name, index));
+            self.tracks.push((start, end));
+        }
+        if let Some(end_constraint) = self.end_constraint.take() {
+            parent.remove_constraint(end_constraint);
+        }
+        // fractional tracks expand to fill the parent, otherwise the tracks only need to fit
+        let has_fraction = (0..self.tracks.len()).any(|index| {
+            if let TrackSize::Fraction(_) = self.size(index) { true } else { false }
+        });
+        let last_end = self.tracks[self.tracks.len() - 1].1;
+        let end_constraint = if has_fraction {
+            last_end | EQ(STRONG) | self.parent_end
+        } else {
+            last_end | LE(STRONG) | self.parent_end
+        };
+        self.end_constraint = Some(end_constraint.clone());
+        constraints.push(end_constraint);
+        parent.add(constraints);
+    }
+
+    /// Start of the first and end of the last track in a span
+    fn span(&self, index: usize, span: usize) -> (Variable, Variable) {
+        (self.tracks[index].0, self.tracks[index + span - 1].1)
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct GridLayout {
     num_columns: usize,
-    column: usize,
-    rows: Vec<LayoutVars>,
-    columns: Vec<LayoutVars>,
-    row_end: Option<Constraint>,
+    rows: Tracks,
+    columns: Tracks,
+    /// The next cell filled by a child without a `GridPlacement`, as (row, column)
+    next_cell: (usize, usize),
 }
 
 impl GridLayout {
+    /// Creates a grid with `num_columns` equal width columns, and equal height rows
+    /// that fill the parent
     pub fn new(parent: &mut Layout, num_columns: usize) -> Self {
-        assert!(num_columns > 0, "can't create grid layout with no columns");
-        let mut columns = Vec::new();
-        for col in 0..num_columns {
-            let column = LayoutVars::new();
-            let mut constraints = vec![
-                column.top | EQ(REQUIRED) | parent.vars.top,
-                column.bottom | EQ(REQUIRED) | parent.vars.bottom,
-                column.right - column.left | EQ(REQUIRED) | column.width,
-                column.bottom - column.top | EQ(REQUIRED) | column.height,
-            ];
-            if let Some(last_column) = columns.last() {
-                let last_column: &LayoutVars = last_column;
-                constraints.push(column.left | EQ(REQUIRED) | last_column.right);
-                constraints.push(column.width | EQ(REQUIRED) | last_column.width);
-            } else {
-                constraints.push(column.left | EQ(REQUIRED) | parent.vars.left);
-            }
-            if col == num_columns - 1 {
-                constraints.push(column.right | EQ(REQUIRED) | parent.vars.right);
-            }
-            parent.add(constraints);
-            parent.add_associated_vars(&column, &format!("column_{}", col));
-            columns.push(column);
-        }
+        let mut settings = GridLayoutSettings::new(vec![TrackSize::Fraction(1.0); num_columns]);
+        settings.implicit_row = TrackSize::Fraction(1.0);
+        GridLayout::with_settings(parent, settings)
+    }
+    pub fn with_settings(parent: &mut Layout, settings: GridLayoutSettings) -> Self {
+        assert!(settings.columns.len() > 0, "can't create grid layout with no columns");
+        let vars = parent.vars;
+        let num_columns = settings.columns.len();
+        let columns = Tracks::new(parent, "column", settings.columns, settings.implicit_column,
+                                  settings.column_gap, vars.left, vars.right);
+        let rows = Tracks::new(parent, "row", settings.rows, settings.implicit_row,
+                               settings.row_gap, vars.top, vars.bottom);
         GridLayout {
             num_columns: num_columns,
-            column: 0,
-            rows: Vec::new(),
+            rows: rows,
             columns: columns,
-            row_end: None,
+            next_cell: (0, 0),
         }
     }
+    fn next_placement(&mut self) -> GridPlacement {
+        let (row, column) = self.next_cell;
+        self.next_cell = if column + 1 < self.num_columns { (row, column + 1) } else { (row + 1, 0) };
+        GridPlacement::new(row, column)
+    }
 }
+
 impl LayoutContainer for GridLayout {
     fn add_child(&mut self, parent: &mut Layout, child: &mut Layout) {
-        if self.column == self.num_columns || self.rows.len() == 0 {
-            let row = LayoutVars::new();
-            let mut constraints = vec![
-                row.left | EQ(REQUIRED) | parent.vars.left,
-                row.right | EQ(REQUIRED) | parent.vars.right,
-                row.right - row.left | EQ(REQUIRED) | row.width,
-                row.bottom - row.top | EQ(REQUIRED) | row.height,
-            ];
-            if let Some(last_row) = self.rows.last() {
-                constraints.push(row.top | EQ(REQUIRED) | last_row.bottom);
-                constraints.push(row.height | EQ(REQUIRED) | last_row.height);
-            } else {
-                constraints.push(row.top | EQ(REQUIRED) | parent.vars.top);
-            }
-            if let Some(row_end) = self.row_end.take() {
-                parent.remove_constraint(row_end);
-            }
-            let row_end = row.bottom | EQ(REQUIRED) | parent.vars.bottom;
-            self.row_end = Some(row_end.clone());
-            constraints.push(row_end);
-            parent.add(constraints);
-            parent.add_associated_vars(&row, &format!("row_{}", self.rows.len()));
-            self.rows.push(row);
-            self.column = 0;
-        }
-        let (row, col) = (self.rows.last().unwrap(), self.columns.get(self.column).unwrap());
-        child.add(constraints![
-            bound_by(row),
-            bound_by(col),
-            match_layout(&parent).strength(STRONG),
+        let placement = match child.container_params::<GridPlacement>() {
+            Some(placement) => *placement,
+            None => self.next_placement(),
+        };
+        let row_span = cmp::max(placement.row_span, 1);
+        let column_span = cmp::max(placement.column_span, 1);
+        self.rows.extend(parent, placement.row + row_span);
+        self.columns.extend(parent, placement.column + column_span);
+
+        let (top, bottom) = self.rows.span(placement.row, row_span);
+        let (left, right) = self.columns.span(placement.column, column_span);
+        let vars = child.vars;
+        // children fill their cell, children with a smaller fixed size are aligned to it's top left
+        child.add(vec![
+            vars.left | GE(REQUIRED) | left,
+            vars.right | LE(REQUIRED) | right,
+            vars.top | GE(REQUIRED) | top,
+            vars.bottom | LE(REQUIRED) | bottom,
+            vars.left | EQ(STRONG) | left,
+            vars.top | EQ(STRONG) | top,
+            vars.right | EQ(MEDIUM) | right,
+            vars.bottom | EQ(MEDIUM) | bottom,
         ]);
-        self.column += 1;
     }
 }
@@ -0,0 +1,169 @@
+//! A snapshot of the layout graph held by a `LimnSolver`, for inspecting and comparing layouts.
+//!
+//! Created with `LimnSolver::layout_graph`, and can be exported as JSON or as a Graphviz DOT graph.
+
+use std::fmt::Write;
+
+use super::{LayoutId, Rect};
+
+/// Every layout registered with the solver and the constraints between them
+#[derive(Debug, Clone)]
+pub struct LayoutGraph {
+    /// Layouts, ordered by id
+    pub layouts: Vec<LayoutNode>,
+    /// All constraints that refer to the layouts, ordered by description
+    pub constraints: Vec<ConstraintNode>,
+}
+
+#[derive(Debug, Clone)]
+pub struct LayoutNode {
+    pub id: LayoutId,
+    pub name: String,
+    /// Bounds of the layout, as currently solved
+    pub rect: Rect,
+    pub hidden: bool,
+    pub children: Vec<LayoutId>,
+    pub edit_vars: Vec<EditVarNode>,
+    pub associated_vars: Vec<VariableNode>,
+    /// Indices of the constraints in `LayoutGraph::constraints` that refer to this layout
+    pub constraints: Vec<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct EditVarNode {
+    pub name: String,
+    /// The value suggested for the variable
+    pub value: f64,
+    pub strength: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct VariableNode {
+    pub name: String,
+    /// The current solved value
+    pub value: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConstraintNode {
+    /// The constraint, formatted with the names of the layouts it refers to
+    pub description: String,
+    pub strength: f64,
+    /// False if the constraint isn't currently in the solver, eg. if it refers to a hidden layout
+    pub active: bool,
+    /// Ids of the layouts the constraint refers to
+    pub layouts: Vec<LayoutId>,
+}
+
+impl LayoutGraph {
+    /// Formats the graph as a JSON object
+    pub fn to_json(&self) -> String {
+        let mut out = String::new();
+        writeln!(out, "{{").unwrap();
+        writeln!(out, "  \"layouts\": [").unwrap();
+        for (index, layout) in self.layouts.iter().enumerate() {
+            writeln!(out, "    {{").unwrap();
+            writeln!(out, "      \"id\": {},", layout.id).unwrap();
+            writeln!(out, "      \"name\": {},", json_string(&layout.name)).unwrap();
+            writeln!(out, "      \"rect\": {{ \"x\": {}, \"y\": {}, \"width\": {}, \"height\": {} }},",
+                json_number(layout.rect.origin.x as f64), json_number(layout.rect.origin.y as f64),
+                json_number(layout.rect.size.width as f64), json_number(layout.rect.size.height as f64)).unwrap();
+            writeln!(out, "      \"hidden\": {},", layout.hidden).unwrap();
+            writeln!(out, "      \"children\": [{}],", join(layout.children.iter().map(|id| id.to_string()))).unwrap();
+            writeln!(out, "      \"edit_vars\": [{}],", join(layout.edit_vars.iter().map(|edit_var| {
+                format!("{{ \"name\": {}, \"value\": {}, \"strength\": {} }}",
+                    json_string(&edit_var.name), json_number(edit_var.value), json_number(edit_var.strength))
+            }))).unwrap();
+            writeln!(out, "      \"associated_vars\": [{}],", join(layout.associated_vars.iter().map(|var| {
+                format!("{{ \"name\": {}, \"value\": {} }}", json_string(&var.name), json_number(var.value))
+            }))).unwrap();
+            writeln!(out, "      \"constraints\": [{}]", join(layout.constraints.iter().map(|index| index.to_string()))).unwrap();
+            writeln!(out, "    }}{}", if index + 1 < self.layouts.len() { "," } else { "" }).unwrap();
+        }
+        writeln!(out, "  ],").unwrap();
+        writeln!(out, "  \"constraints\": [").unwrap();
+        for (index, constraint) in self.constraints.iter().enumerate() {
+            writeln!(out, "    {{ \"description\": {}, \"strength\": {}, \"active\": {}, \"layouts\": [{}] }}{}",
+                json_string(&constraint.description), json_number(constraint.strength), constraint.active,
+                join(constraint.layouts.iter().map(|id| id.to_string())),
+                if index + 1 < self.constraints.len() { "," } else { "" }).unwrap();
+        }
+        writeln!(out, "  ]").unwrap();
+        writeln!(out, "}}").unwrap();
+        out
+    }
+
+    /// Formats the graph as a Graphviz DOT graph. Solid edges connect parents to their children,
+    /// dashed edges connect layouts that share a constraint.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::new();
+        writeln!(out, "digraph layout {{").unwrap();
+        writeln!(out, "    node [shape=box];").unwrap();
+        for layout in &self.layouts {
+            let rect = layout.rect;
+            let label = format!("{}\n{}, {} {}x{}", layout.name,
+                rect.origin.x, rect.origin.y, rect.size.width, rect.size.height);
+            let style = if layout.hidden { ", style=dotted" } else { "" };
+            writeln!(out, "    layout_{} [label={}{}];", layout.id, dot_string(&label), style).unwrap();
+        }
+        for layout in &self.layouts {
+            for child in &layout.children {
+                writeln!(out, "    layout_{} -> layout_{};", layout.id, child).unwrap();
+            }
+        }
+        for constraint in &self.constraints {
+            if let Some((first, rest)) = constraint.layouts.split_first() {
+                for other in rest.iter().filter(|other| *other != first) {
+                    writeln!(out, "    layout_{} -> layout_{} [style=dashed, dir=none, tooltip={}];",
+                        first, other, dot_string(&constraint.description)).unwrap();
+                }
+            }
+        }
+        writeln!(out, "}}").unwrap();
+        out
+    }
+}
+
+fn join<I: Iterator<Item = String>>(items: I) -> String {
+    items.collect::<Vec<_>>().join(", ")
+}
+
+fn json_number(value: f64) -> String {
+    if !value.is_finite() {
+        "null".to_owned()
+    } else if value == 0.0 {
+        // avoid "-0"
+        "0".to_owned()
+    } else {
+        value.to_string()
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut out = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn dot_string(value: &str) -> String {
+    let mut out = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
@@ -40,7 +40,9 @@ extern crate lazy_static;
 extern crate cassowary;
 extern crate euclid;
 
+use std::any::Any;
 use std::collections::HashSet;
+use std::cmp;
 use std::ops::Drop;
 use std::mem;
 use std::rc::Rc;
@@ -54,6 +56,7 @@ use euclid::{Point2D, Size2D, UnknownUnit};
 
 use self::constraint::ConstraintBuilder;
 use self::constraint::*;
+use self::direct::DirectContainer;
 
 pub type Length = euclid::Length<f32, UnknownUnit>;
 pub type Size = Size2D<f32>;
@@ -72,6 +75,9 @@ pub struct LayoutVars {
     pub bottom: Variable,
     pub width: Variable,
     pub height: Variable,
+    /// The baseline of the first line of text in a layout, if it has any text.
+    /// Only created once something uses it, see `Layout::baseline`.
+    pub baseline: Option<Variable>,
 }
 
 impl LayoutVars {
@@ -83,6 +89,7 @@ impl LayoutVars {
             bottom: Variable::new(),
             width: Variable::new(),
             height: Variable::new(),
+            baseline: None,
         }
     }
 
@@ -104,11 +111,45 @@ impl LayoutVars {
         else if var == self.bottom { VarType::Bottom }
         else if var == self.width { VarType::Width }
         else if var == self.height { VarType::Height }
+        else if Some(var) == self.baseline { VarType::Baseline }
         else { VarType::Other }
     }
+
+    /// Checks if a constraint is one of those added to every `Layout` with these variables,
+    /// that relate it's edges to it's size, or keep it's size positive
+    pub fn is_intrinsic(&self, constraint: &Constraint) -> bool {
+        if constraint.expr().constant != 0.0 {
+            return false;
+        }
+        let var_terms: Vec<(VarType, f64)> = constraint.expr().terms.iter()
+            .map(|term| (self.var_type(term.variable), term.coefficient)).collect();
+        match constraint.op() {
+            cassowary::RelationalOperator::GreaterOrEqual => match var_terms.as_slice() {
+                &[(VarType::Width, coefficient)] | &[(VarType::Height, coefficient)] => coefficient > 0.0,
+                _ => false,
+            },
+            cassowary::RelationalOperator::Equal => {
+                is_size_relation(&var_terms, VarType::Left, VarType::Right, VarType::Width) ||
+                is_size_relation(&var_terms, VarType::Top, VarType::Bottom, VarType::Height)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Checks if `terms` make up the expression `end - start - size`, or it's negation
+fn is_size_relation(terms: &[(VarType, f64)], start: VarType, end: VarType, size: VarType) -> bool {
+    if terms.len() != 3 {
+        return false;
+    }
+    let coefficient = |var_type| terms.iter().find(|&&(other, _)| other == var_type).map(|&(_, coefficient)| coefficient);
+    match (coefficient(start), coefficient(end), coefficient(size)) {
+        (Some(start), Some(end), Some(size)) => (end == 1.0 || end == -1.0) && start == -end && size == -end,
+        _ => false,
+    }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum VarType {
     Left,
     Top,
@@ -116,11 +157,53 @@ pub enum VarType {
     Bottom,
     Width,
     Height,
+    Baseline,
     Other,
 }
 
+/// The direction leading and trailing constraints, and horizontal `LinearLayout`s, are laid out in
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Direction {
+    LeftToRight,
+    RightToLeft,
+}
+
+impl Default for Direction {
+    fn default() -> Self {
+        Direction::LeftToRight
+    }
+}
+
+/// Constraints that depend on the direction of a layout.
+/// Only the constraints for the layout's current direction are passed to the solver.
+#[derive(Debug, Clone)]
+pub struct DirectionalConstraints {
+    pub left_to_right: Vec<Constraint>,
+    pub right_to_left: Vec<Constraint>,
+}
+
+impl DirectionalConstraints {
+    pub fn new(left_to_right: Vec<Constraint>, right_to_left: Vec<Constraint>) -> Self {
+        DirectionalConstraints {
+            left_to_right: left_to_right,
+            right_to_left: right_to_left,
+        }
+    }
+    pub fn get(&self, direction: Direction) -> &Vec<Constraint> {
+        match direction {
+            Direction::LeftToRight => &self.left_to_right,
+            Direction::RightToLeft => &self.right_to_left,
+        }
+    }
+}
+
 pub trait LayoutRef {
     fn layout_ref(&self) -> LayoutVars;
+    /// The baseline of the referenced layout, if it has one. Implementations that can modify
+    /// the layout should create it if it doesn't exist yet.
+    fn baseline_ref(&self) -> Option<Variable> {
+        self.layout_ref().baseline
+    }
 }
 
 impl<'a> LayoutRef for &'a mut Layout {
@@ -159,6 +242,17 @@ pub struct Layout {
     removed_constraints: Vec<Constraint>,
     removed_children: Vec<LayoutId>,
     associated_vars: Vec<(Variable, String)>,
+    container_params: Option<Rc<Any>>,
+    /// Measures and arranges the children of this layout without the solver, if set
+    direct_container: Option<Rc<RefCell<DirectContainer>>>,
+    /// The size this layout wants when arranged by a `DirectContainer`
+    measured_size: Option<Size>,
+    /// The variables of this layout's parent, if the parent arranges it with a `DirectContainer`
+    arranged_by: Option<LayoutVars>,
+    direction: Direction,
+    /// True if the direction was set on this layout, rather than inherited from it's parent
+    direction_set: bool,
+    directional_constraints: Vec<DirectionalConstraints>,
     pub hidden: bool,
 }
 
@@ -185,6 +279,13 @@ impl Layout {
             removed_constraints: Vec::new(),
             removed_children: Vec::new(),
             associated_vars: Vec::new(),
+            container_params: None,
+            direct_container: None,
+            measured_size: None,
+            arranged_by: None,
+            direction: Direction::default(),
+            direction_set: false,
+            directional_constraints: Vec::new(),
             hidden: false,
         }
     }
@@ -204,6 +305,33 @@ impl Layout {
     pub fn set_container<T>(&mut self, container: T) where T: LayoutContainer + 'static {
         self.container = Some(Rc::new(RefCell::new(container)));
     }
+    /// Sets parameters used by the parent's container when this layout is added to it,
+    /// eg. `Flex` for a `LinearLayout`. Must be set before this layout is added to it's parent.
+    pub fn set_container_params<T: 'static>(&mut self, params: T) {
+        self.container_params = Some(Rc::new(params));
+    }
+    /// Replaces the container of the current layout with a `DirectContainer`, that measures
+    /// and arranges the children itself rather than adding constraints to the solver.
+    /// Must be set before any children are added.
+    ///
+    /// Children without constraints of their own, or children of their own outside of a
+    /// `DirectContainer`, aren't added to the solver at all, which is much faster for large trees.
+    pub fn set_direct_container<T>(&mut self, container: T) where T: DirectContainer + 'static {
+        self.container = None;
+        self.direct_container = Some(Rc::new(RefCell::new(container)));
+    }
+    /// Sets the size this layout wants when it's arranged by a `DirectContainer`
+    pub fn set_measured_size(&mut self, size: Size) {
+        self.measured_size = Some(size);
+    }
+    /// True if this layout's parent arranges it with a `DirectContainer`
+    pub fn is_arranged(&self) -> bool {
+        self.arranged_by.is_some()
+    }
+    /// Gets the parameters set by `set_container_params`, if they are of type `T`
+    pub fn container_params<T: 'static>(&self) -> Option<&T> {
+        self.container_params.as_ref().and_then(|params| params.downcast_ref::<T>())
+    }
     pub fn edit_left(&mut self) -> VariableEditable {
         let var = self.vars.left;
         VariableEditable::new(self, var)
@@ -229,11 +357,74 @@ impl Layout {
         VariableEditable::new(self, var)
     }
     pub fn create_constraint<B: ConstraintBuilder>(&self, builder: B) -> Vec<Constraint> {
-        builder.build(&self.vars)
+        builder.build_directed(&self.vars, self.direction)
     }
     pub fn add<B: ConstraintBuilder>(&mut self, builder: B) {
-        let new_constraints = builder.build(&self.vars);
-        self.new_constraints.extend(new_constraints);
+        if builder.uses_baseline() {
+            self.baseline();
+        }
+        if builder.is_directional() {
+            let constraints = DirectionalConstraints::new(
+                builder.build_directed(&self.vars, Direction::LeftToRight),
+                builder.build_directed(&self.vars, Direction::RightToLeft));
+            self.add_directional(constraints);
+        } else {
+            let new_constraints = builder.build(&self.vars);
+            self.new_constraints.extend(new_constraints);
+        }
+    }
+    /// Adds constraints that depend on the direction of this layout,
+    /// they are replaced when the direction changes.
+    pub fn add_directional(&mut self, constraints: DirectionalConstraints) {
+        self.new_constraints.extend(constraints.get(self.direction).iter().cloned());
+        self.directional_constraints.push(constraints);
+    }
+    pub fn remove_directional(&mut self, constraints: DirectionalConstraints) {
+        let direction = self.direction;
+        if let Some(pos) = self.directional_constraints.iter().position(|existing| {
+            existing.get(direction) == constraints.get(direction)
+        }) {
+            self.directional_constraints.remove(pos);
+        }
+        self.remove_constraints(constraints.get(direction).clone());
+    }
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+    /// Sets the direction of this layout. Unlike an inherited direction,
+    /// it isn't replaced by the direction of this layout's parent.
+    pub fn set_direction(&mut self, direction: Direction) {
+        self.direction_set = true;
+        self.apply_direction(direction);
+    }
+    /// Sets the direction inherited from this layout's parent, if this layout
+    /// has no direction of it's own. Returns true if the direction changed.
+    pub fn inherit_direction(&mut self, direction: Direction) -> bool {
+        if self.direction_set || self.direction == direction {
+            return false;
+        }
+        self.apply_direction(direction);
+        true
+    }
+    fn apply_direction(&mut self, direction: Direction) {
+        let previous = mem::replace(&mut self.direction, direction);
+        if previous == direction {
+            return;
+        }
+        for constraints in self.directional_constraints.clone() {
+            let (old, new) = (constraints.get(previous), constraints.get(direction));
+            for constraint in old.iter().filter(|constraint| !new.contains(constraint)) {
+                self.remove_constraint(constraint.clone());
+            }
+            for constraint in new.iter().filter(|constraint| !old.contains(constraint)) {
+                // constraint might still be in the solver, if the direction was changed back
+                if let Some(pos) = self.removed_constraints.iter().position(|removed| removed == constraint) {
+                    self.removed_constraints.remove(pos);
+                } else {
+                    self.new_constraints.insert(constraint.clone());
+                }
+            }
+        }
     }
     pub fn remove_constraint(&mut self, constraint: Constraint) {
         if !self.new_constraints.remove(&constraint) {
@@ -273,8 +464,17 @@ impl Layout {
         mem::replace(&mut self.edit_vars, Vec::new())
     }
     pub fn add_child(&mut self, child: &mut Layout) {
+        let index = self.children.len();
+        self.insert_child(index, child);
+    }
+    /// Adds a child before the child currently at `index`, for containers that place
+    /// their children in order, like a `LinearLayout`
+    pub fn insert_child(&mut self, index: usize, child: &mut Layout) {
         child.parent = Some(self.id);
-        self.children.push(child.id);
+        child.arranged_by = if self.direct_container.is_some() { Some(self.vars) } else { None };
+        child.inherit_direction(self.direction);
+        let index = cmp::min(index, self.children.len());
+        self.children.insert(index, child.id);
         if let Some(container) = self.container.clone() {
             container.borrow_mut().add_child(self, child);
         }
@@ -286,6 +486,7 @@ impl Layout {
         if let Some(pos) = self.children.iter().position(|id| child.id == *id) {
             self.children.remove(pos);
         }
+        child.arranged_by = None;
         self.removed_children.push(child.id);
     }
     pub fn get_removed_children(&mut self) -> Vec<LayoutId> {
@@ -300,12 +501,43 @@ impl Layout {
             self.associated_vars.push((*var, format!("{}.{}", name, var_type)));
         }
     }
+    /// The baseline of the first line of text in this layout, created the first time it's used.
+    /// Nothing positions the baseline until a constraint is added for it, like the one added
+    /// for a text widget's measured text.
+    pub fn baseline(&mut self) -> Variable {
+        if let Some(baseline) = self.vars.baseline {
+            return baseline;
+        }
+        let baseline = Variable::new();
+        self.vars.baseline = Some(baseline);
+        self.add_associated_var(baseline, "baseline");
+        baseline
+    }
     pub fn add_associated_var(&mut self, var: Variable, name: &str) {
         self.associated_vars.push((var, name.to_owned()));
     }
     pub fn get_associated_vars(&mut self) -> Vec<(Variable, String)> {
         mem::replace(&mut self.associated_vars, Vec::new())
     }
+    /// Notifies this layout's container that the solved bounds of this layout
+    /// or it's children have changed.
+    pub fn container_layout_changed(&mut self, solver: &LimnSolver) {
+        if let Some(container) = self.container.clone() {
+            container.borrow_mut().layout_changed(self, solver);
+        }
+    }
+    /// Returns true if this layout has changes that haven't been passed to the solver yet
+    pub fn has_pending_changes(&self) -> bool {
+        !self.new_constraints.is_empty() || !self.removed_constraints.is_empty() ||
+            !self.edit_vars.is_empty() || !self.removed_children.is_empty() ||
+            !self.associated_vars.is_empty()
+    }
+    /// Passes every constraint added to this layout to the solver again, for layouts that
+    /// were arranged without the solver until now
+    fn requeue_constraints(&mut self) {
+        let constraints = self.constraints.drain().collect::<Vec<_>>();
+        self.new_constraints.extend(constraints);
+    }
     pub fn hide(&mut self) {
         self.hidden = true;
     }
@@ -386,8 +618,13 @@ macro_rules! constraints {
 
 /// Defines what constraints a parent applies to it's children as they are added
 pub trait LayoutContainer {
+    /// Called after the child has been added to the parent's children, at the position it was inserted
     fn add_child(&mut self, parent: &mut Layout, child: &mut Layout);
     fn remove_child(&mut self, _: &mut Layout, _: &mut Layout) {}
+    /// Called after the solved bounds of the parent or it's children have changed.
+    /// Containers that depend on the solved sizes of their children can update their
+    /// constraints here, the parent then needs to be passed to the solver again.
+    fn layout_changed(&mut self, _: &mut Layout, _: &LimnSolver) {}
 }
 
 #[derive(Debug, Default, Copy, Clone)]
@@ -419,8 +656,12 @@ pub mod solver;
 pub mod constraint;
 pub mod linear_layout;
 pub mod grid_layout;
+pub mod flow_layout;
+pub mod stack_layout;
+pub mod inspect;
+pub mod direct;
 
-pub use self::solver::LimnSolver;
+pub use self::solver::{LimnSolver, ConstraintConflict};
 
 lazy_static! {
     pub static ref LAYOUT: LayoutVars = LayoutVars::new();
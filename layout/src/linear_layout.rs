@@ -4,9 +4,11 @@ use cassowary::strength::*;
 use cassowary::WeightedRelation::*;
 use cassowary::{Variable, Constraint};
 
-use super::{LayoutId, LayoutVars, Layout, LayoutContainer};
+use super::{LayoutId, LayoutVars, Layout, LayoutContainer, DirectionalConstraints};
 use super::constraint::*;
 
+/// Distribution of the free space along the axis of a `LinearLayout`.
+/// In a right to left layout, the start of a horizontal layout is it's right edge.
 #[derive(Debug, Copy, Clone,PartialEq)]
 pub enum Spacing {
     /// Equal spacing before, after and between all elements
@@ -39,6 +41,9 @@ pub enum ItemAlignment {
     /// For a horizontal layout, align items to the parent's bottom bound.
     /// Do not use in a vertical layout
     Bottom,
+    /// For a horizontal layout, align the baselines of the items, as close to the parent's
+    /// top bound as possible. Do not use in a vertical layout
+    Baseline,
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -70,19 +75,108 @@ impl LinearLayoutSettings {
     }
 }
 
+/// Per child parameters for distributing space in a `LinearLayout`, set with
+/// `Layout::set_container_params` before the child is added.
+///
+/// Along the layout's axis, a child with `Flex` params has the size
+/// `basis + grow * g - shrink * s`, where `g` and `s` are shared by all children
+/// in the layout and are solved so that the children fill the layout.
+/// Children without `Flex` params keep the size given by their own constraints.
+#[derive(Debug, Copy, Clone)]
+pub struct Flex {
+    /// Share of the free space this child grows by, relative to the other children
+    pub grow: f32,
+    /// Share of the overflow this child shrinks by, relative to the other children
+    pub shrink: f32,
+    /// Size of the child before growing or shrinking
+    pub basis: f32,
+}
+
+impl Flex {
+    pub fn new(grow: f32, shrink: f32, basis: f32) -> Self {
+        Flex {
+            grow: grow,
+            shrink: shrink,
+            basis: basis,
+        }
+    }
+    /// Grows by `grow` from a basis of zero, so free space is shared between
+    /// children in proportion to their `grow` weights
+    pub fn grow(grow: f32) -> Self {
+        Flex {
+            grow: grow,
+            ..Flex::default()
+        }
+    }
+}
+
+impl Default for Flex {
+    fn default() -> Self {
+        Flex {
+            grow: 0.0,
+            shrink: 1.0,
+            basis: 0.0,
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone)]
 pub enum Orientation {
     Horizontal,
     Vertical,
 }
 
+/// The edges of a layout along the axis of a `LinearLayout`, in the order children are placed.
+/// Horizontal layouts place children from right to left in a right to left layout.
+#[derive(Debug, Copy, Clone)]
+struct Axis {
+    orientation: Orientation,
+    reversed: bool,
+}
+
+impl Axis {
+    fn start(&self, layout: &LayoutVars) -> Variable {
+        match self.orientation {
+            Orientation::Horizontal => if self.reversed { layout.right } else { layout.left },
+            Orientation::Vertical => layout.top,
+        }
+    }
+    fn end(&self, layout: &LayoutVars) -> Variable {
+        match self.orientation {
+            Orientation::Horizontal => if self.reversed { layout.left } else { layout.right },
+            Orientation::Vertical => layout.bottom,
+        }
+    }
+    /// Converts a distance along the axis to a distance in layout coordinates
+    fn sign(&self) -> f64 {
+        if self.reversed { -1.0 } else { 1.0 }
+    }
+    /// Constrain `first` to be at or before `second` along the axis
+    fn before(&self, first: Variable, second: Variable) -> Constraint {
+        (second - first) * self.sign() | GE(REQUIRED) | 0.0
+    }
+}
+
+/// Build a constraint along the axis of a layout, for both layout directions.
+/// Vertical layouts use the same constraint for both.
+fn directed<F: Fn(Axis) -> Constraint>(orientation: Orientation, build: F) -> DirectionalConstraints {
+    let left_to_right = build(Axis { orientation: orientation, reversed: false });
+    let right_to_left = match orientation {
+        Orientation::Horizontal => build(Axis { orientation: orientation, reversed: true }),
+        Orientation::Vertical => left_to_right.clone(),
+    };
+    DirectionalConstraints::new(vec![left_to_right], vec![right_to_left])
+}
+
 #[derive(Debug, Clone)]
 struct WidgetData {
-    start: Variable,
-    end: Variable,
+    vars: LayoutVars,
     prev: Option<LayoutId>,
     next: Option<LayoutId>,
-    end_constraint: Option<Constraint>,
+    /// Attaches the start of the child to the end of the previous child, or the start of the layout
+    link_constraint: Option<DirectionalConstraints>,
+    end_constraint: Option<DirectionalConstraints>,
+    flex: Option<(Flex, Constraint)>,
 }
 
 pub struct LinearLayout {
@@ -93,6 +187,14 @@ pub struct LinearLayout {
     size: Option<Variable>,
     widgets: HashMap<LayoutId, WidgetData>,
     last_widget: Option<LayoutId>,
+    /// The shared grow and shrink variables used by children with `Flex` params
+    flex_vars: Option<(Variable, Variable)>,
+    /// Number of children with a non zero grow weight
+    grow_children: usize,
+    /// Makes growing children take up the free space, if there are any
+    fill_constraint: Option<DirectionalConstraints>,
+    /// The baseline shared by all children, with `ItemAlignment::Baseline`
+    baseline: Option<Variable>,
 }
 
 impl LinearLayout {
@@ -107,20 +209,20 @@ impl LinearLayout {
             Spacing::Between | Spacing::Around => parent.add(space | GE(REQUIRED) | settings.padding),
             _ => parent.add(space | EQ(REQUIRED) | settings.padding)
         };
-        let parent_start = beginning(settings.orientation, &parent.vars);
-        let parent_end = ending(settings.orientation, &parent.vars);
+        let parent_vars = parent.vars;
+        let orientation = settings.orientation;
         match settings.spacing {
             Spacing::Around => {
-                parent.add(constraints![
-                    start | EQ(REQUIRED) | parent_start + space,
-                    end | EQ(REQUIRED) | parent_end - space,
-                ]);
+                parent.add_directional(directed(orientation, |axis| {
+                    start | EQ(REQUIRED) | axis.start(&parent_vars) + space * axis.sign()
+                }));
+                parent.add_directional(directed(orientation, |axis| {
+                    end | EQ(REQUIRED) | axis.end(&parent_vars) - space * axis.sign()
+                }));
             },
             _ => {
-                parent.add(constraints![
-                    start | EQ(REQUIRED) | parent_start,
-                    end | EQ(REQUIRED) | parent_end,
-                ]);
+                parent.add_directional(directed(orientation, |axis| start | EQ(REQUIRED) | axis.start(&parent_vars)));
+                parent.add_directional(directed(orientation, |axis| end | EQ(REQUIRED) | axis.end(&parent_vars)));
             }
         }
 
@@ -133,6 +235,14 @@ impl LinearLayout {
         } else {
             None
         };
+        let baseline = if let ItemAlignment::Baseline = settings.item_align {
+            let baseline = Variable::new();
+            parent.add_associated_var(baseline, "linear_layout_baseline");
+            parent.add(baseline | EQ(WEAK) | parent.vars.top);
+            Some(baseline)
+        } else {
+            None
+        };
         LinearLayout {
             settings: settings,
             start: start,
@@ -141,6 +251,94 @@ impl LinearLayout {
             size: size,
             widgets: HashMap::new(),
             last_widget: None,
+            flex_vars: None,
+            grow_children: 0,
+            fill_constraint: None,
+            baseline: baseline,
+        }
+    }
+
+    fn flex_vars(&mut self, parent: &mut Layout) -> (Variable, Variable) {
+        if let Some(flex_vars) = self.flex_vars {
+            return flex_vars;
+        }
+        let grow = Variable::new();
+        let shrink = Variable::new();
+        parent.add_associated_var(grow, "linear_layout_grow");
+        parent.add_associated_var(shrink, "linear_layout_shrink");
+        parent.add(grow | GE(REQUIRED) | 0.0);
+        parent.add(shrink | GE(REQUIRED) | 0.0);
+        parent.add(grow | EQ(WEAK) | 0.0);
+        parent.add(shrink | EQ(MEDIUM) | 0.0);
+        if let Spacing::Between | Spacing::Around = self.settings.spacing {
+            // growing children take up free space before the spacing does
+            parent.add(self.space | EQ(MEDIUM) | self.settings.padding);
+        }
+        self.flex_vars = Some((grow, shrink));
+        (grow, shrink)
+    }
+
+    /// The constraint attaching a child after `prev`, or at the start of the layout
+    fn link(&self, prev: Option<LayoutId>, vars: LayoutVars) -> Option<DirectionalConstraints> {
+        let (start, space) = (self.start, self.space);
+        let orientation = self.settings.orientation;
+        match prev {
+            Some(prev) => {
+                let prev_vars = self.widgets[&prev].vars;
+                Some(directed(orientation, |axis| {
+                    axis.start(&vars) | EQ(REQUIRED) | axis.end(&prev_vars) + space * axis.sign()
+                }))
+            }
+            None if self.settings.spacing != Spacing::Start => {
+                Some(directed(orientation, |axis| axis.start(&vars) | EQ(REQUIRED) | start))
+            }
+            None => None,
+        }
+    }
+
+    /// Replaces the link constraint of a child, after it's previous child has changed
+    fn relink(&mut self, parent: &mut Layout, id: LayoutId, prev: Option<LayoutId>) {
+        let link_constraint = self.link(prev, self.widgets[&id].vars);
+        let widget_data = self.widgets.get_mut(&id).unwrap();
+        if let Some(old_constraint) = widget_data.link_constraint.take() {
+            parent.remove_directional(old_constraint);
+        }
+        if let Some(ref link_constraint) = link_constraint {
+            parent.add_directional(link_constraint.clone());
+        }
+        widget_data.link_constraint = link_constraint;
+        widget_data.prev = prev;
+    }
+
+    fn first_widget(&self) -> Option<LayoutId> {
+        self.widgets.iter().find(|&(_, data)| data.prev.is_none()).map(|(id, _)| *id)
+    }
+
+    /// Keep the outermost child attached to the free end of the layout while any children can grow.
+    /// Not needed for `Between` or `Around` spacing, where both ends are already attached.
+    fn update_fill(&mut self, parent: &mut Layout) {
+        if let Some(fill_constraint) = self.fill_constraint.take() {
+            parent.remove_directional(fill_constraint);
+        }
+        if self.grow_children == 0 {
+            return;
+        }
+        let (start, end) = (self.start, self.end);
+        let orientation = self.settings.orientation;
+        let fill_constraint = match self.settings.spacing {
+            Spacing::End => self.last_widget.map(|id| {
+                let vars = self.widgets[&id].vars;
+                directed(orientation, |axis| axis.end(&vars) | EQ(STRONG) | end)
+            }),
+            Spacing::Start => self.first_widget().map(|id| {
+                let vars = self.widgets[&id].vars;
+                directed(orientation, |axis| axis.start(&vars) | EQ(STRONG) | start)
+            }),
+            _ => None,
+        };
+        if let Some(fill_constraint) = fill_constraint {
+            parent.add_directional(fill_constraint.clone());
+            self.fill_constraint = Some(fill_constraint);
         }
     }
 }
@@ -148,42 +346,67 @@ impl LinearLayout {
 impl LayoutContainer for LinearLayout {
     fn add_child(&mut self, parent: &mut Layout, child: &mut Layout) {
 
-        let child_start = beginning(self.settings.orientation, &child.vars);
-        let child_end = ending(self.settings.orientation, &child.vars);
+        let vars = child.vars;
+        let (start, end) = (self.start, self.end);
+        let orientation = self.settings.orientation;
 
-        parent.add(child_start | GE(REQUIRED) | self.start);
-        parent.add(child_end | LE(REQUIRED) | self.end);
+        parent.add_directional(directed(orientation, |axis| axis.before(start, axis.start(&vars))));
+        parent.add_directional(directed(orientation, |axis| axis.before(axis.end(&vars), end)));
 
-        if let Some(last_id) = self.last_widget {
-            let last_widget = self.widgets.get_mut(&last_id).unwrap();
-            parent.add(child_start | EQ(REQUIRED) | last_widget.end + self.space);
-            last_widget.next = Some(child.id);
-        } else {
-            if self.settings.spacing != Spacing::Start {
-                parent.add(child_start | EQ(REQUIRED) | self.start);
-            }
+        // the child follows the closest sibling before it, children can be inserted between others
+        let prev = {
+            let siblings = parent.get_children();
+            let index = siblings.iter().position(|id| *id == child.id).unwrap_or(siblings.len());
+            siblings[..index].iter().rev().find(|id| self.widgets.contains_key(id)).cloned()
+        };
+        let next = match prev {
+            Some(prev) => self.widgets[&prev].next,
+            None => self.first_widget(),
+        };
+        let link_constraint = self.link(prev, vars);
+        if let Some(ref link_constraint) = link_constraint {
+            parent.add_directional(link_constraint.clone());
+        }
+        if let Some(prev) = prev {
+            self.widgets.get_mut(&prev).unwrap().next = Some(child.id);
         }
         let end_constraint = {
-            if self.settings.spacing != Spacing::End {
+            if next.is_none() && self.settings.spacing != Spacing::End {
                 if let Some(last_id) = self.last_widget {
                     let last_widget = self.widgets.get_mut(&last_id).unwrap();
-                    parent.remove_constraint(last_widget.end_constraint.take().unwrap());
+                    parent.remove_directional(last_widget.end_constraint.take().unwrap());
                 }
-                let end_constraint = child_end | EQ(REQUIRED) | self.end;
-                parent.add(end_constraint.clone());
+                let end_constraint = directed(orientation, |axis| axis.end(&vars) | EQ(REQUIRED) | end);
+                parent.add_directional(end_constraint.clone());
                 Some(end_constraint)
             } else {
                 None
             }
         };
+        let flex = child.container_params::<Flex>().cloned().map(|flex| {
+            let (grow, shrink) = self.flex_vars(parent);
+            let child_size = axis_length(self.settings.orientation, &child.vars);
+            let flex_constraint = child_size | EQ(STRONG) |
+                grow * f64::from(flex.grow) - shrink * f64::from(flex.shrink) + f64::from(flex.basis);
+            parent.add(flex_constraint.clone());
+            if flex.grow > 0.0 {
+                self.grow_children += 1;
+            }
+            (flex, flex_constraint)
+        });
         self.widgets.insert(child.id, WidgetData {
-            start: child_start,
-            end: child_end,
-            prev: self.last_widget,
-            next: None,
+            vars: vars,
+            prev: prev,
+            next: next,
+            link_constraint: link_constraint,
             end_constraint: end_constraint,
+            flex: flex,
         });
-        self.last_widget = Some(child.id);
+        match next {
+            Some(next) => self.relink(parent, next, Some(child.id)),
+            None => self.last_widget = Some(child.id),
+        }
+        self.update_fill(parent);
 
         if self.settings.fill_equal {
             let child_size = axis_length(self.settings.orientation, &child.vars);
@@ -217,6 +440,15 @@ impl LayoutContainer for LinearLayout {
                             align_bottom(parent),
                         ]);
                     },
+                    ItemAlignment::Baseline => {
+                        let baseline = self.baseline.unwrap();
+                        let child_baseline = child.baseline();
+                        child.add(constraints![
+                            bound_top(parent),
+                            bound_bottom(parent),
+                            child_baseline | EQ(REQUIRED) | baseline,
+                        ]);
+                    },
                     ItemAlignment::None => {
                         child.add(constraints![
                             bound_top(parent),
@@ -267,27 +499,26 @@ impl LayoutContainer for LinearLayout {
 
     fn remove_child(&mut self, parent: &mut Layout, child: &mut Layout) {
         if let Some(widget_data) = self.widgets.remove(&child.id) {
+            let end = self.end;
+            let orientation = self.settings.orientation;
+            if let Some(link_constraint) = widget_data.link_constraint {
+                parent.remove_directional(link_constraint);
+            }
+            if let Some(end_constraint) = widget_data.end_constraint {
+                parent.remove_directional(end_constraint);
+            }
             if let Some(prev) = widget_data.prev {
-                let next_start = widget_data.next.map(|next_id| self.widgets[&next_id].start);
                 let prev = self.widgets.get_mut(&prev).unwrap();
-                if let Some(next_start) = next_start {
-                    parent.add(next_start | EQ(REQUIRED) | prev.end + self.space);
-                } else {
-                    if self.settings.spacing != Spacing::End {
-                        let end_constraint = prev.end | EQ(REQUIRED) | self.end;
-                        parent.add(end_constraint.clone());
-                        prev.end_constraint = Some(end_constraint);
-                    }
+                if widget_data.next.is_none() && self.settings.spacing != Spacing::End {
+                    let prev_vars = prev.vars;
+                    let end_constraint = directed(orientation, |axis| axis.end(&prev_vars) | EQ(REQUIRED) | end);
+                    parent.add_directional(end_constraint.clone());
+                    prev.end_constraint = Some(end_constraint);
                 }
                 prev.next = widget_data.next;
-            } else if let Some(next) = widget_data.next {
-                if self.settings.spacing != Spacing::Start {
-                    let next_start = self.widgets[&next].start;
-                    parent.add(next_start | EQ(REQUIRED) | self.start);
-                }
             }
             if let Some(next) = widget_data.next {
-                self.widgets.get_mut(&next).unwrap().prev = widget_data.prev;
+                self.relink(parent, next, widget_data.prev);
             }
 
             if let Some(last_id) = self.last_widget {
@@ -295,22 +526,17 @@ impl LayoutContainer for LinearLayout {
                     self.last_widget = widget_data.prev;
                 }
             }
+            if let Some((flex, flex_constraint)) = widget_data.flex {
+                parent.remove_constraint(flex_constraint);
+                if flex.grow > 0.0 {
+                    self.grow_children -= 1;
+                }
+            }
+            self.update_fill(parent);
         }
     }
 }
 
-fn beginning(orientation: Orientation, layout: &LayoutVars) -> Variable {
-    match orientation {
-        Orientation::Horizontal => layout.left,
-        Orientation::Vertical => layout.top,
-    }
-}
-fn ending(orientation: Orientation, layout: &LayoutVars) -> Variable {
-    match orientation {
-        Orientation::Horizontal => layout.right,
-        Orientation::Vertical => layout.bottom,
-    }
-}
 fn axis_length(orientation: Orientation, layout: &LayoutVars) -> Variable {
     match orientation {
         Orientation::Horizontal => layout.width,
@@ -1,17 +1,72 @@
 use std::collections::{HashMap, HashSet, VecDeque};
-use std::fmt::Write;
+use std::error::Error;
+use std::fmt::{self, Write};
+use std::mem;
 
 use cassowary;
+use cassowary::AddConstraintError;
 use cassowary::strength::*;
 use cassowary::{Variable, Constraint, Expression};
 use cassowary::WeightedRelation::*;
 
 use super::{LayoutId, Layout, VarType, LayoutVars, EditVariable, Rect, Point, Size};
+use super::inspect::{LayoutGraph, LayoutNode, ConstraintNode, EditVarNode, VariableNode};
+use super::direct::DirectLayouts;
+
+/// The most associated constraints searched for the ones that conflict with a new constraint,
+/// unless the solver is strict
+const MAX_CONFLICT_SEARCH: usize = 100;
+
+/// A constraint involved in a `ConstraintConflict`
+#[derive(Debug, Clone)]
+pub struct ConflictingConstraint {
+    pub constraint: Constraint,
+    pub strength: f64,
+    /// The constraint, formatted with the names of the layouts it refers to
+    pub description: String,
+}
+
+/// A required constraint that couldn't be added to the solver, along with a minimal set of
+/// other required constraints that, together with it, can't be satisfied.
+#[derive(Debug, Clone)]
+pub struct ConstraintConflict {
+    /// The constraint that couldn't be added
+    pub constraint: ConflictingConstraint,
+    /// Constraints already in the solver that conflict with `constraint`, empty if there
+    /// were too many constraints to search
+    pub conflicts_with: Vec<ConflictingConstraint>,
+    /// The constraint that is most likely to resolve the conflict if it's strength is
+    /// lowered from `REQUIRED`
+    pub suggestion: ConflictingConstraint,
+}
+
+impl fmt::Display for ConstraintConflict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Failed to add constraint {}", self.constraint.description)?;
+        if !self.conflicts_with.is_empty() {
+            writeln!(f, "conflicts with:")?;
+            for constraint in &self.conflicts_with {
+                writeln!(f, "    {}", constraint.description)?;
+            }
+        }
+        write!(f, "consider weakening {}, eg. with .strength(STRONG)", self.suggestion.description)
+    }
+}
+
+impl Error for ConstraintConflict {
+    fn description(&self) -> &str {
+        "unsatisfiable required constraint"
+    }
+}
 
 pub struct LimnSolver {
     pub solver: cassowary::Solver,
     pub strict: bool,
     layouts: LayoutManager,
+    conflicts: Vec<ConstraintConflict>,
+    direct: DirectLayouts,
+    /// Values suggested for layouts arranged by a `DirectContainer` that are also in the solver
+    direct_edit_vars: HashMap<Variable, f64>,
 }
 
 impl LimnSolver {
@@ -20,10 +75,33 @@ impl LimnSolver {
             solver: cassowary::Solver::new(),
             strict: false,
             layouts: LayoutManager::new(),
+            conflicts: Vec::new(),
+            direct: DirectLayouts::new(),
+            direct_edit_vars: HashMap::new(),
+        }
+    }
+
+    /// Passes any changes to a layout to the solver.
+    /// Returns any required constraints that couldn't be added, the conflicts are also logged.
+    pub fn update_layout(&mut self, layout: &mut Layout) -> Result<(), Vec<ConstraintConflict>> {
+
+        // other layouts' constraints in the solver keep a layout that was solved in the solver
+        let referenced = self.layouts.layouts.get(&layout.id).map_or(false, |internal| {
+            !internal.edit_vars.is_empty() ||
+                internal.constraints.iter().any(|constraint| !layout.constraints.contains(constraint))
+        });
+        if !self.direct.update_layout(layout, referenced) {
+            // arranged by it's parent's `DirectContainer` in `fetch_changes`
+            if self.layouts.layouts.contains_key(&layout.id) {
+                // constraints that needed the solver were removed
+                let associated_vars = self.unregister_layout(layout.id);
+                self.direct.hold_associated_vars(layout.id, associated_vars);
+            }
+            for child in layout.get_removed_children() {
+                self.remove_layout(child);
+            }
+            return Ok(());
         }
-    }
-
-    pub fn update_layout(&mut self, layout: &mut Layout) {
 
         let registered = self.layouts.layouts.contains_key(&layout.id);
         if !registered {
@@ -38,26 +116,35 @@ impl LimnSolver {
             self.layouts.remove_constraint(&constraint);
             self.remove_constraint(&constraint);
         }
-        if !registered {
-            for constraint in self.layouts.dequeue_constraints(layout) {
-                self.add_constraint(constraint.clone());
-            }
+        // constraints can also be waiting on variables created after the layout was registered
+        for constraint in self.layouts.dequeue_constraints(layout) {
+            self.add_constraint(constraint.clone());
         }
 
-        if layout.hidden && !self.layouts.layout_hidden(layout.id) {
-            self.hide_layout(layout.id);
-        } else if !layout.hidden && self.layouts.layout_hidden(layout.id) {
-            self.unhide_layout(layout.id);
-        }
         for constraint in layout.get_constraints() {
-            if self.layouts.add_constraint(&constraint) {
+            // constraints for hidden layouts are added when the layout is shown
+            if self.layouts.add_constraint(&constraint) && !self.layouts.layout_hidden(layout.id) {
                 self.add_constraint(constraint.clone());
             }
         }
+        if layout.hidden && !self.layouts.layout_hidden(layout.id) {
+            self.hide(layout.id);
+        } else if !layout.hidden && self.layouts.layout_hidden(layout.id) {
+            self.unhide(layout.id);
+        }
         for edit_var in layout.get_edit_vars() {
             self.update_edit_var(&edit_var);
             self.layouts.update_edit_var(layout.id, edit_var);
         }
+        self.take_conflicts()
+    }
+    /// Returns the conflicts found since the last call
+    fn take_conflicts(&mut self) -> Result<(), Vec<ConstraintConflict>> {
+        if self.conflicts.is_empty() {
+            Ok(())
+        } else {
+            Err(mem::replace(&mut self.conflicts, Vec::new()))
+        }
     }
     fn update_edit_var(&mut self, edit_var: &EditVariable) {
         let &EditVariable { var, val, strength } = edit_var;
@@ -74,15 +161,97 @@ impl LimnSolver {
     }
     fn add_constraint(&mut self, constraint: Constraint) {
         debug!("adding constraint {}", self.layouts.fmt_constraint(&constraint));
-        if self.solver.add_constraint(constraint.clone()).is_err() {
-            eprintln!("Failed to add constraint {}", self.layouts.fmt_constraint(&constraint));
-            self.debug_associated_constraints(&constraint);
-            if self.strict {
-                panic!("Solver unsatisfiable");
+        match self.solver.add_constraint(constraint.clone()) {
+            Err(AddConstraintError::UnsatisfiableConstraint) => {
+                let conflict = self.find_conflict(&constraint);
+                eprintln!("{}", conflict);
+                if self.strict {
+                    panic!("Solver unsatisfiable");
+                }
+                self.conflicts.push(conflict);
+            }
+            Err(err) => {
+                eprintln!("Failed to add constraint {}: {:?}", self.layouts.fmt_constraint(&constraint), err);
             }
+            Ok(()) => (),
+        }
+    }
+
+    /// Find a minimal set of required constraints in the solver that conflict with `constraint`,
+    /// by removing each associated constraint that isn't needed to make them unsatisfiable.
+    /// That takes a solve for each associated constraint, so outside of strict mode, the conflicting
+    /// constraints are only found if there are at most `MAX_CONFLICT_SEARCH` associated constraints.
+    fn find_conflict(&self, constraint: &Constraint) -> ConstraintConflict {
+        let mut conflicts: Vec<Constraint> = self.associated_constraints(constraint).into_iter()
+            .filter(|associated| associated != constraint).collect();
+        if conflicts.len() > MAX_CONFLICT_SEARCH && !self.strict {
+            debug!("not searching {} associated constraints for conflicts", conflicts.len());
+            conflicts.clear();
+        } else if satisfiable(&conflicts, constraint) {
+            // conflict involves constraints the layout manager doesn't track, eg. for hidden layouts
+            conflicts.clear();
+        } else {
+            let mut index = 0;
+            while index < conflicts.len() {
+                let removed = conflicts.remove(index);
+                if satisfiable(&conflicts, constraint) {
+                    conflicts.insert(index, removed);
+                    index += 1;
+                }
+            }
+        }
+        // the new constraint is most likely the one that needs to change, unless it's
+        // one of the constraints every layout has, that define it's width and height
+        let suggestion = if self.layouts.is_intrinsic(constraint) {
+            conflicts.iter().rev().find(|constraint| !self.layouts.is_intrinsic(constraint))
+                .unwrap_or(constraint)
+        } else {
+            constraint
+        };
+        ConstraintConflict {
+            constraint: self.conflicting_constraint(constraint),
+            conflicts_with: conflicts.iter().map(|constraint| self.conflicting_constraint(constraint)).collect(),
+            suggestion: self.conflicting_constraint(suggestion),
         }
     }
 
+    fn conflicting_constraint(&self, constraint: &Constraint) -> ConflictingConstraint {
+        ConflictingConstraint {
+            constraint: constraint.clone(),
+            strength: constraint.strength(),
+            description: self.layouts.fmt_constraint(constraint),
+        }
+    }
+
+    /// Find all required constraints in the solver that are connected to `constraint`
+    /// through shared variables
+    fn associated_constraints(&self, constraint: &Constraint) -> HashSet<Constraint> {
+        let mut visited_constraints = HashSet::new();
+        let mut new_constraints = HashSet::new();
+        new_constraints.insert(constraint.clone());
+
+        loop {
+            if new_constraints.len() == 0 {
+                break;
+            }
+            let mut newer_constraints = HashSet::new();
+            for constraint in new_constraints.drain() {
+                for var in constraint_vars(&constraint) {
+                    for constraint in self.layouts.constraints_for(var) {
+                        if constraint.strength() >= REQUIRED &&
+                            !visited_constraints.contains(constraint) &&
+                                self.solver.has_constraint(&constraint) {
+                            newer_constraints.insert(constraint.clone());
+                        }
+                    }
+                }
+                visited_constraints.insert(constraint);
+            }
+            new_constraints = newer_constraints;
+        }
+        visited_constraints
+    }
+
     fn remove_constraint(&mut self, constraint: &Constraint) {
         debug!("removing constraint {}", self.layouts.fmt_constraint(constraint));
         if self.solver.has_constraint(constraint) {
@@ -91,6 +260,9 @@ impl LimnSolver {
     }
 
     pub fn remove_layout(&mut self, id: LayoutId) {
+        if let Some(vars) = self.direct.remove_layout(id) {
+            self.remove_direct_edit_vars(&vars);
+        }
         if let Some(layout) = self.layouts.layouts.remove(&id) {
             for constraint in layout.constraints {
                 self.remove_constraint(&constraint);
@@ -101,7 +273,44 @@ impl LimnSolver {
         }
     }
 
-    pub fn hide_layout(&mut self, id: LayoutId) {
+    /// Removes a layout that's now arranged by it's parent's `DirectContainer` from the solver,
+    /// returns it's associated variables
+    fn unregister_layout(&mut self, id: LayoutId) -> Vec<(Variable, String)> {
+        let constraints: Vec<Constraint> = self.layouts.layouts[&id].constraints.iter().cloned().collect();
+        for constraint in constraints {
+            self.layouts.remove_constraint(&constraint);
+            self.remove_constraint(&constraint);
+        }
+        let vars = self.direct.vars(id);
+        self.remove_direct_edit_vars(&vars);
+        let layout = self.layouts.layouts.remove(&id).unwrap();
+        for var in layout.vars.array().iter().chain(layout.associated_vars.keys()) {
+            self.layouts.var_ids.remove(var);
+        }
+        layout.associated_vars.into_iter().collect()
+    }
+
+    fn remove_direct_edit_vars(&mut self, vars: &LayoutVars) {
+        for var in vars.array().iter() {
+            if self.direct_edit_vars.remove(var).is_some() && self.solver.has_edit_variable(var) {
+                self.solver.remove_edit_variable(*var).unwrap();
+            }
+        }
+    }
+
+    /// Hides a layout and it's children, giving them an empty size.
+    /// Returns any required constraints that couldn't be added.
+    pub fn hide_layout(&mut self, id: LayoutId) -> Result<(), Vec<ConstraintConflict>> {
+        self.hide(id);
+        self.take_conflicts()
+    }
+    /// Shows a hidden layout and it's children.
+    /// Returns any required constraints that couldn't be added.
+    pub fn unhide_layout(&mut self, id: LayoutId) -> Result<(), Vec<ConstraintConflict>> {
+        self.unhide(id);
+        self.take_conflicts()
+    }
+    fn hide(&mut self, id: LayoutId) {
         if !self.layouts.layout_hidden(id) {
             for constraint in self.layouts.layouts[&id].constraints.clone() {
                 self.remove_constraint(&constraint);
@@ -121,12 +330,12 @@ impl LimnSolver {
                 self.add_constraint(constraint.clone());
             }
         }
-        let children = self.layouts.layouts[&id].children.clone();
+        let children = self.layouts.children(id);
         for child in children {
-            self.hide_layout(child);
+            self.hide(child);
         }
     }
-    pub fn unhide_layout(&mut self, id: LayoutId) {
+    fn unhide(&mut self, id: LayoutId) {
         if self.layouts.layout_hidden(id) {
             for constraint in self.layouts.layouts[&id].hidden_constraints.clone() {
                 self.remove_constraint(&constraint);
@@ -148,9 +357,9 @@ impl LimnSolver {
             let layout = self.layouts.layouts.get_mut(&id).unwrap();
             layout.hidden = false;
         }
-        let children = self.layouts.layouts[&id].children.clone();
+        let children = self.layouts.children(id);
         for child in children {
-            self.unhide_layout(child);
+            self.unhide(child);
         }
     }
     pub fn update_solver<F>(&mut self, f: F)
@@ -166,8 +375,23 @@ impl LimnSolver {
         self.solver.has_constraint(constraint)
     }
 
+    /// Gets the current solved value of a variable
+    pub fn get_value(&self, var: Variable) -> f64 {
+        self.solver.get_value(var)
+    }
+    /// Gets the current solved bounds of a set of `LayoutVars`
+    pub fn get_rect(&self, vars: &LayoutVars) -> Rect {
+        solved_rect(&self.solver, vars)
+    }
+
     pub fn fetch_changes(&mut self) -> Vec<(LayoutId, VarType, f64)> {
         let mut changes = Vec::new();
+        for (id, rect) in self.arrange_direct() {
+            changes.push((id, VarType::Left, rect.origin.x as f64));
+            changes.push((id, VarType::Top, rect.origin.y as f64));
+            changes.push((id, VarType::Width, rect.size.width as f64));
+            changes.push((id, VarType::Height, rect.size.height as f64));
+        }
         for &(var, val) in self.solver.fetch_changes() {
             debug!("solver {} = {}", self.layouts.fmt_variable(var), val);
             if let Some(layout_id) = self.layouts.var_ids.get(&var) {
@@ -178,6 +402,133 @@ impl LimnSolver {
         changes
     }
 
+    /// Measures and arranges the layouts in `DirectContainer`s that changed, or were moved by
+    /// the solver. Arranged layouts that are in the solver have their bounds suggested to the
+    /// solver, returns the bounds of the others that changed.
+    fn arrange_direct(&mut self) -> Vec<(LayoutId, Rect)> {
+        let mut rects = Vec::new();
+        // suggesting bounds to the solver can move other containers, so repeat until nothing moves
+        for _ in 0..10 {
+            {
+                let solver = &self.solver;
+                self.direct.mark_moved(&|vars| solved_rect(solver, vars));
+            }
+            let roots = self.direct.dirty_roots();
+            if roots.is_empty() {
+                break;
+            }
+            let mut sizes = HashMap::new();
+            for root in &roots {
+                let size = self.direct.measure(*root, &|vars| self.get_rect(vars).size, &mut sizes);
+                let vars = self.direct.vars(*root);
+                self.suggest_direct(vars.width, size.width, WEAK);
+                self.suggest_direct(vars.height, size.height, WEAK);
+            }
+            let arranged = rects.len();
+            for root in &roots {
+                let bounds = self.get_rect(&self.direct.vars(*root));
+                self.direct.set_solved_rect(*root, bounds);
+                self.direct.arrange(*root, bounds, &sizes, &mut rects);
+            }
+            for &(id, rect) in &rects[arranged..] {
+                if self.direct.is_solved(id) {
+                    let vars = self.direct.vars(id);
+                    self.suggest_direct(vars.left, rect.origin.x, STRONG);
+                    self.suggest_direct(vars.top, rect.origin.y, STRONG);
+                    self.suggest_direct(vars.width, rect.size.width, STRONG);
+                    self.suggest_direct(vars.height, rect.size.height, STRONG);
+                }
+            }
+        }
+        let rects = rects.into_iter().filter(|&(id, _)| !self.direct.is_solved(id)).collect();
+        self.direct.update_rects(rects)
+    }
+
+    /// Suggests a value for a variable of an arranged layout, unless the layout has
+    /// it's own edit variable for it.
+    fn suggest_direct(&mut self, var: Variable, value: f32, strength: f64) {
+        let value = value as f64;
+        match self.direct_edit_vars.get(&var) {
+            Some(suggested) if *suggested == value => return,
+            Some(_) => (),
+            None => {
+                if self.solver.has_edit_variable(&var) {
+                    return;
+                }
+                self.solver.add_edit_variable(var, strength).unwrap();
+            }
+        }
+        self.solver.suggest_value(var, value).unwrap();
+        self.direct_edit_vars.insert(var, value);
+    }
+
+    /// Creates a snapshot of every layout in the solver, and the constraints between them
+    pub fn layout_graph(&self) -> LayoutGraph {
+        let mut ids: Vec<LayoutId> = self.layouts.layouts.keys().cloned().collect();
+        ids.sort();
+        let mut constraints = Vec::new();
+        let mut shown_constraints = HashSet::new();
+        for id in &ids {
+            for constraint in &self.layouts.layouts[id].constraints {
+                if shown_constraints.insert(constraint.clone()) {
+                    constraints.push((self.layouts.fmt_constraint(constraint), constraint.clone()));
+                }
+            }
+        }
+        constraints.sort_by(|&(ref a, _), &(ref b, _)| a.cmp(b));
+        let constraint_indices: HashMap<Constraint, usize> = constraints.iter().enumerate()
+            .map(|(index, &(_, ref constraint))| (constraint.clone(), index)).collect();
+
+        let layouts = ids.iter().map(|id| {
+            let layout = &self.layouts.layouts[id];
+            let mut edit_vars: Vec<EditVarNode> = layout.edit_vars.values().map(|edit_var| {
+                EditVarNode {
+                    name: self.layouts.fmt_variable(edit_var.var),
+                    value: edit_var.val,
+                    strength: edit_var.strength,
+                }
+            }).collect();
+            edit_vars.sort_by(|a, b| a.name.cmp(&b.name));
+            let mut associated_vars: Vec<VariableNode> = layout.associated_vars.iter().map(|(var, name)| {
+                VariableNode {
+                    name: name.clone(),
+                    value: self.solver.get_value(*var),
+                }
+            }).collect();
+            associated_vars.sort_by(|a, b| a.name.cmp(&b.name));
+            let mut layout_constraints: Vec<usize> = layout.constraints.iter()
+                .map(|constraint| constraint_indices[constraint]).collect();
+            layout_constraints.sort();
+            LayoutNode {
+                id: *id,
+                name: self.layouts.layout_name(*id),
+                rect: self.get_rect(&layout.vars),
+                hidden: layout.hidden,
+                children: self.layouts.children(*id),
+                edit_vars: edit_vars,
+                associated_vars: associated_vars,
+                constraints: layout_constraints,
+            }
+        }).collect();
+
+        let constraints = constraints.into_iter().map(|(description, constraint)| {
+            let mut layouts: Vec<LayoutId> = constraint_vars(&constraint).iter()
+                .filter_map(|var| self.layouts.var_ids.get(var).cloned()).collect();
+            layouts.sort();
+            layouts.dedup();
+            ConstraintNode {
+                description: description,
+                strength: constraint.strength(),
+                active: self.solver.has_constraint(&constraint),
+                layouts: layouts,
+            }
+        }).collect();
+        LayoutGraph {
+            layouts: layouts,
+            constraints: constraints,
+        }
+    }
+
     pub fn debug_variables(&self) {
         println!("VARIABLES");
         for var in self.layouts.var_ids.keys() {
@@ -211,30 +562,7 @@ impl LimnSolver {
     }
 
     pub fn debug_associated_constraints(&self, constraint: &Constraint) {
-        let mut visited_constraints = HashSet::new();
-        let mut new_constraints = HashSet::new();
-        new_constraints.insert(constraint.clone());
-
-        loop {
-            if new_constraints.len() == 0 {
-                break;
-            }
-            let mut newer_constraints = HashSet::new();
-            for constraint in new_constraints.drain() {
-                for var in constraint_vars(&constraint) {
-                    for constraint in self.layouts.constraints_for(var) {
-                        if constraint.strength() >= REQUIRED &&
-                            !visited_constraints.contains(constraint) &&
-                                self.solver.has_constraint(&constraint) {
-                            newer_constraints.insert(constraint.clone());
-                        }
-                    }
-                }
-                visited_constraints.insert(constraint);
-            }
-            new_constraints = newer_constraints;
-        }
-        for constraint in visited_constraints {
+        for constraint in self.associated_constraints(constraint) {
             self.debug_constraint(&constraint);
         }
     }
@@ -250,13 +578,7 @@ impl LimnSolver {
     }
 
     pub fn debug_layout(&self, id: LayoutId) {
-        let bounds = {
-            let get_val = |var| self.solver.get_value(var) as f32;
-            let vars = &self.layouts.layouts[&id].vars;
-            let origin = Point::new(get_val(vars.left), get_val(vars.top));
-            let size = Size::new(get_val(vars.width), get_val(vars.height));
-            Rect::new(origin, size)
-        };
+        let bounds = self.get_rect(&self.layouts.layouts[&id].vars);
         println!("{} {}", self.layouts.layout_name(id), bounds);
     }
 }
@@ -265,6 +587,14 @@ fn constraint_vars(constraint: &Constraint) -> Vec<Variable> {
     constraint.expr().terms.iter().map(|term| term.variable).collect()
 }
 
+/// Checks if `constraint` can be added to a solver containing only `constraints`
+fn satisfiable(constraints: &[Constraint], constraint: &Constraint) -> bool {
+    let mut solver = cassowary::Solver::new();
+    constraints.iter().chain(Some(constraint)).all(|constraint| {
+        solver.add_constraint(constraint.clone()).is_ok()
+    })
+}
+
 struct LayoutInternal {
     vars: LayoutVars,
     name: Option<String>,
@@ -323,7 +653,9 @@ impl LayoutManager {
             self.var_ids.insert(var, layout.id);
             internal_layout.associated_vars.insert(var, name);
         }
+        internal_layout.vars = layout.vars;
         internal_layout.name = layout.name.clone();
+        internal_layout.children = layout.children.clone();
     }
 
     pub fn add_constraint(&mut self, constraint: &Constraint) -> bool {
@@ -404,16 +736,31 @@ impl LayoutManager {
         constraint.expr().terms.iter().map(|term| self.var_ids[&term.variable]).collect()
     }
 
+    /// Children of a layout that have been registered
     pub fn children(&self, id: LayoutId) -> Vec<LayoutId> {
-        self.layouts[&id].children.clone()
+        self.layouts[&id].children.iter().cloned().filter(|child| self.layouts.contains_key(child)).collect()
     }
     pub fn layout_vars(&self, id: LayoutId) -> Vec<Variable> {
         let layout = &self.layouts[&id];
         layout.vars.array().iter().chain(layout.associated_vars.keys()).map(|var| *var).collect()
     }
 
+    /// Checks if a constraint is one of those added to every `Layout`, that relate
+    /// it's edges to it's size, or keep it's size positive
+    fn is_intrinsic(&self, constraint: &Constraint) -> bool {
+        let terms = &constraint.expr().terms;
+        match terms.first().and_then(|term| self.var_ids.get(&term.variable)) {
+            Some(layout_id) => self.layouts[layout_id].vars.is_intrinsic(constraint),
+            None => false,
+        }
+    }
+
     pub fn fmt_variable(&self, var: Variable) -> String {
-        let id = self.var_ids[&var];
+        let id = match self.var_ids.get(&var) {
+            Some(id) => *id,
+            // constraints can refer to layouts that haven't been registered yet, or were removed
+            None => return "unknown".to_owned(),
+        };
         let layout = &self.layouts[&id];
         let layout_name = layout.name.clone().unwrap_or("unknown".to_owned());
         let var_type = layout.vars.var_type(var);
@@ -473,6 +820,14 @@ impl LayoutManager {
     }
 }
 
+/// Gets the current solved bounds of a set of `LayoutVars`
+fn solved_rect(solver: &cassowary::Solver, vars: &LayoutVars) -> Rect {
+    let get_val = |var| solver.get_value(var) as f32;
+    let origin = Point::new(get_val(vars.left), get_val(vars.top));
+    let size = Size::new(get_val(vars.width), get_val(vars.height));
+    Rect::new(origin, size)
+}
+
 /// Creates a printable string value for a given strength
 fn strength_desc(strength: f64) -> &'static str {
     if strength < WEAK { "WEAK-" }
@@ -485,3 +840,4 @@ fn strength_desc(strength: f64) -> &'static str {
     else if strength == REQUIRED { "REQD " }
     else { "REQD+" }
 }
+
@@ -0,0 +1,119 @@
+use cassowary::strength::*;
+
+use super::{Layout, LayoutVars, LayoutContainer};
+use super::constraint::*;
+
+/// The point within the parent a child of a `StackLayout` is aligned to
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Anchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+    /// Child matches the bounds of the parent
+    Fill,
+}
+
+/// Alignment of a child in a `StackLayout`, set with `Layout::set_container_params`
+/// before the child is added. Children without a `StackItem` use the layout's default.
+#[derive(Debug, Copy, Clone)]
+pub struct StackItem {
+    pub anchor: Anchor,
+    /// Space between the child and the anchored edges of the parent
+    pub margin: f32,
+}
+
+impl StackItem {
+    pub fn new(anchor: Anchor) -> Self {
+        StackItem {
+            anchor: anchor,
+            margin: 0.0,
+        }
+    }
+    pub fn margin(mut self, margin: f32) -> Self {
+        self.margin = margin;
+        self
+    }
+}
+
+impl Default for StackItem {
+    fn default() -> Self {
+        StackItem::new(Anchor::Fill)
+    }
+}
+
+/// Places all children on top of each other, within the bounds of the parent.
+///
+/// Children are drawn in the order they are added, so the last child is on top.
+/// Use `show_selected` to show only one of the children.
+#[derive(Debug, Copy, Clone)]
+pub struct StackLayout {
+    parent: LayoutVars,
+    default_item: StackItem,
+}
+
+impl StackLayout {
+    pub fn new(parent: &mut Layout) -> Self {
+        StackLayout::with_default(parent, StackItem::default())
+    }
+    /// Creates a `StackLayout` that aligns children without a `StackItem` using `default_item`
+    pub fn with_default(parent: &mut Layout, default_item: StackItem) -> Self {
+        StackLayout {
+            parent: parent.vars,
+            default_item: default_item,
+        }
+    }
+}
+
+impl LayoutContainer for StackLayout {
+    fn add_child(&mut self, _: &mut Layout, child: &mut Layout) {
+        let item = child.container_params::<StackItem>().cloned().unwrap_or(self.default_item);
+        let parent = &self.parent;
+        let margin = item.margin;
+        child.add(constraints![
+            bound_by(parent).padding(margin),
+            match_layout(parent).padding(margin).strength(WEAK),
+        ]);
+        let (horizontal, vertical) = match item.anchor {
+            Anchor::TopLeft => (Some(align_left(parent)), Some(align_top(parent))),
+            Anchor::Top => (None, Some(align_top(parent))),
+            Anchor::TopRight => (Some(align_right(parent)), Some(align_top(parent))),
+            Anchor::Left => (Some(align_left(parent)), None),
+            Anchor::Center => (None, None),
+            Anchor::Right => (Some(align_right(parent)), None),
+            Anchor::BottomLeft => (Some(align_left(parent)), Some(align_bottom(parent))),
+            Anchor::Bottom => (None, Some(align_bottom(parent))),
+            Anchor::BottomRight => (Some(align_right(parent)), Some(align_bottom(parent))),
+            Anchor::Fill => {
+                child.add(match_layout(parent).padding(margin));
+                return;
+            }
+        };
+        match horizontal {
+            Some(horizontal) => child.add(horizontal.padding(margin)),
+            None => child.add(center_horizontal(parent)),
+        }
+        match vertical {
+            Some(vertical) => child.add(vertical.padding(margin)),
+            None => child.add(center_vertical(parent)),
+        }
+    }
+}
+
+/// Shows the child at index `selected`, or the last (top-most) child if `None`,
+/// and hides the rest of the children of a `StackLayout`
+pub fn show_selected(children: &mut [&mut Layout], selected: Option<usize>) {
+    let selected = selected.or_else(|| children.len().checked_sub(1));
+    for (index, child) in children.iter_mut().enumerate() {
+        if Some(index) == selected {
+            child.show();
+        } else {
+            child.hide();
+        }
+    }
+}
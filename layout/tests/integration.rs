@@ -10,8 +10,10 @@ use std::collections::HashMap;
 use std::ops::{Deref, DerefMut};
 
 use cassowary::strength::*;
+use cassowary::WeightedRelation::*;
+use cassowary::Variable;
 
-use layout::{LimnSolver, LayoutId, Layout, VarType, LayoutRef, LayoutVars};
+use layout::{LimnSolver, LayoutId, Layout, VarType, LayoutRef, LayoutVars, Direction};
 use layout::{Size, Point, Rect};
 use layout::constraint::*;
 
@@ -120,6 +122,258 @@ fn grid_layout() {
     });
 }
 
+#[test]
+fn grid_layout_placement() {
+    use layout::grid_layout::{GridLayout, GridLayoutSettings, GridPlacement, TrackSize};
+    let mut layout = TestLayout::new();
+
+    let mut grid = layout.new_widget("grid");
+    grid.add(constraints![
+        top_left(Point::new(0.0, 0.0)),
+        size(Size::new(310.0, 200.0)),
+    ]);
+    let mut settings = GridLayoutSettings::new(vec![
+        TrackSize::Fixed(50.0), TrackSize::Fraction(1.0), TrackSize::Fraction(2.0)]);
+    settings.rows = vec![TrackSize::Fixed(30.0), TrackSize::Auto];
+    settings.column_gap = 10.0;
+    settings.row_gap = 5.0;
+    let grid_layout = GridLayout::with_settings(&mut grid, settings);
+    grid.set_container(grid_layout);
+
+    let mut header = layout.new_widget("header");
+    let mut label = layout.new_widget("label");
+    let mut field = layout.new_widget("field");
+    let mut footer = layout.new_widget("footer");
+    header.set_container_params(GridPlacement::new(0, 0).span(1, 3));
+    label.set_container_params(GridPlacement::new(1, 0));
+    label.add(height(20.0));
+    field.set_container_params(GridPlacement::new(1, 1).span(1, 2));
+    footer.set_container_params(GridPlacement::new(2, 2));
+    footer.add(height(15.0));
+    grid.add_child(&mut *header);
+    grid.add_child(&mut *label);
+    grid.add_child(&mut *field);
+    grid.add_child(&mut *footer);
+
+    layout.add_root(grid.clone());
+    layout.update();
+
+    assert!(layout.match_layouts(hashmap!{
+        header.id => Rect::new(Point::new(0.0, 0.0), Size::new(310.0, 30.0)),
+        label.id => Rect::new(Point::new(0.0, 35.0), Size::new(50.0, 20.0)),
+        field.id => Rect::new(Point::new(60.0, 35.0), Size::new(250.0, 20.0)),
+        footer.id => Rect::new(Point::new(150.0, 60.0), Size::new(160.0, 15.0)),
+    }));
+}
+
+#[test]
+fn grid_layout_auto_columns() {
+    use layout::grid_layout::{GridLayout, GridLayoutSettings, TrackSize};
+    let mut layout = TestLayout::new();
+
+    let mut grid = layout.new_widget("grid");
+    grid.add(constraints![
+        top_left(Point::new(0.0, 0.0)),
+        size(Size::new(200.0, 200.0)),
+    ]);
+    let mut settings = GridLayoutSettings::new(vec![TrackSize::Auto, TrackSize::Fraction(1.0)]);
+    settings.column_gap = 10.0;
+    let grid_layout = GridLayout::with_settings(&mut grid, settings);
+    grid.set_container(grid_layout);
+
+    let mut label_1 = layout.new_widget("label_1");
+    let mut field_1 = layout.new_widget("field_1");
+    let mut label_2 = layout.new_widget("label_2");
+    let mut field_2 = layout.new_widget("field_2");
+    label_1.add(size(Size::new(40.0, 20.0)));
+    label_2.add(size(Size::new(60.0, 30.0)));
+    grid.add_child(&mut *label_1);
+    grid.add_child(&mut *field_1);
+    grid.add_child(&mut *label_2);
+    grid.add_child(&mut *field_2);
+
+    layout.add_root(grid.clone());
+    layout.update();
+
+    assert!(layout.match_layouts(hashmap!{
+        label_1.id => Rect::new(Point::new(0.0, 0.0), Size::new(40.0, 20.0)),
+        field_1.id => Rect::new(Point::new(70.0, 0.0), Size::new(130.0, 20.0)),
+        label_2.id => Rect::new(Point::new(0.0, 20.0), Size::new(60.0, 30.0)),
+        field_2.id => Rect::new(Point::new(70.0, 20.0), Size::new(130.0, 30.0)),
+    }));
+}
+
+#[test]
+fn constraint_conflict() {
+    let mut layout = TestLayout::new();
+    layout.solver.strict = false;
+
+    let mut root = layout.new_widget("root");
+    let mut child = layout.new_widget("child");
+    root.add(constraints![
+        top_left(Point::new(0.0, 0.0)),
+        size(Size::new(100.0, 100.0)),
+    ]);
+    child.add(constraints![
+        bound_by(&root),
+        width(50.0),
+    ]);
+    layout.solver.update_layout(&mut *root).unwrap();
+    layout.solver.update_layout(&mut *child).unwrap();
+
+    let conflicting_width = width(80.0).build(&child.vars).remove(0);
+    child.add(conflicting_width.clone());
+    let conflicts = layout.solver.update_layout(&mut *child).unwrap_err();
+    assert_eq!(conflicts.len(), 1);
+    let conflict = &conflicts[0];
+    assert_eq!(conflict.constraint.constraint, conflicting_width);
+    assert_eq!(conflict.constraint.description, "REQD  child.width == 80");
+    assert_eq!(conflict.conflicts_with.len(), 1);
+    assert_eq!(conflict.conflicts_with[0].description, "REQD  child.width == 50");
+    assert_eq!(conflict.suggestion.constraint, conflicting_width);
+}
+
+#[test]
+fn hide_layout_conflict() {
+    let mut layout = TestLayout::new();
+    layout.solver.strict = false;
+
+    let mut root = layout.new_widget("root");
+    let mut child = layout.new_widget("child");
+    root.add(constraints![
+        top_left(Point::new(0.0, 0.0)),
+        size(Size::new(100.0, 100.0)),
+    ]);
+    layout.solver.update_layout(&mut *root).unwrap();
+    layout.solver.update_layout(&mut *child).unwrap();
+    // added directly to the solver, so it isn't removed with the layout's constraints
+    let min_width = min_width(50.0).build(&child.vars).remove(0);
+    layout.solver.update_solver(|solver| solver.add_constraint(min_width.clone()).unwrap());
+
+    // hidden layouts have a required width of zero
+    let conflicts = layout.solver.hide_layout(child.id).unwrap_err();
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].constraint.description, "REQD  child.width == 0");
+    // the conflict isn't reported again by the next update
+    root.add(width(100.0));
+    layout.solver.update_layout(&mut *root).unwrap();
+}
+
+#[test]
+fn constraint_conflict_suggestion() {
+    let mut layout = TestLayout::new();
+    layout.solver.strict = false;
+
+    let mut root = layout.new_widget("root");
+    let mut child = layout.new_widget("child");
+    root.add(constraints![
+        top_left(Point::new(0.0, 0.0)),
+        size(Size::new(100.0, 100.0)),
+    ]);
+    child.add(constraints![
+        bound_by(&root),
+        width(50.0),
+    ]);
+    layout.solver.update_layout(&mut *root).unwrap();
+    layout.solver.update_layout(&mut *child).unwrap();
+
+    // has the same variables as the constraint relating the edges to the width,
+    // but isn't one of the constraints every layout has
+    let conflicting: cassowary::Constraint = {
+        let vars = &child.vars;
+        vars.left + vars.right + vars.width | EQ(REQUIRED) | 0.0
+    };
+    child.add(conflicting.clone());
+    let conflicts = layout.solver.update_layout(&mut *child).unwrap_err();
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].suggestion.constraint, conflicting);
+}
+
+#[test]
+fn layout_graph() {
+    let mut layout = TestLayout::new();
+
+    let mut root = layout.new_widget("root");
+    let mut child = layout.new_widget("child");
+    root.add(constraints![
+        top_left(Point::new(0.0, 0.0)),
+        size(Size::new(100.0, 100.0)),
+    ]);
+    root.add_child(&mut *child);
+    child.add(constraints![
+        width(50.0),
+        align_left(&root),
+    ]);
+
+    layout.add_root(root.clone());
+    layout.update();
+
+    let graph = layout.solver.layout_graph();
+    assert_eq!(graph.layouts.len(), 2);
+    let root_node = &graph.layouts[0];
+    assert_eq!(root_node.name, "root");
+    assert_eq!(root_node.rect, Rect::new(Point::new(0.0, 0.0), Size::new(100.0, 100.0)));
+    assert_eq!(root_node.children, vec![child.id]);
+    let child_node = &graph.layouts[1];
+    assert_eq!(child_node.rect, Rect::new(Point::new(0.0, 0.0), Size::new(50.0, 100.0)));
+    let width_constraint = graph.constraints.iter()
+        .position(|constraint| constraint.description == "REQD  child.width == 50").unwrap();
+    assert!(child_node.constraints.contains(&width_constraint));
+    assert!(!root_node.constraints.contains(&width_constraint));
+    // the frame constraints between the root and the child connect both layouts
+    assert!(graph.constraints.iter().any(|constraint| constraint.layouts == vec![root.id, child.id]));
+
+    let json = graph.to_json();
+    assert!(json.contains("\"name\": \"child\""));
+    assert!(json.contains("\"rect\": { \"x\": 0, \"y\": 0, \"width\": 50, \"height\": 100 }"));
+    let dot = graph.to_dot();
+    assert!(dot.starts_with("digraph layout {"));
+    assert!(dot.contains(&format!("layout_{} -> layout_{};", root.id, child.id)));
+}
+
+#[test]
+fn relative_size() {
+    let mut layout = TestLayout::new();
+
+    let mut root = layout.new_widget("root");
+    let mut child = layout.new_widget("child");
+    let mut bounded = layout.new_widget("bounded");
+    let mut padded = layout.new_widget("padded");
+    root.add(constraints![
+        top_left(Point::new(0.0, 0.0)),
+        size(Size::new(200.0, 100.0)),
+    ]);
+    child.add(constraints![
+        width_fraction_of(&root, 0.25),
+        height_fraction_of(&root, 0.5).padding(10.0),
+        offset_from_center(&root, 20.0, -10.0),
+    ]);
+    bounded.add(constraints![
+        top_left(Point::new(0.0, 0.0)),
+        between(Size::new(10.0, 10.0), Size::new(80.0, 30.0)),
+        width(500.0).strength(STRONG),
+        shrink_vertical(),
+    ]);
+    padded.add(constraints![
+        top_left(Point::new(0.0, 0.0)),
+        max_width(100.0).padding(10.0),
+        between(Size::new(10.0, 30.0), Size::new(80.0, 80.0)).padding(10.0),
+        width(500.0).strength(STRONG),
+        shrink_vertical(),
+    ]);
+
+    layout.add_root(root.clone());
+    layout.add_root(child.clone());
+    layout.add_root(bounded.clone());
+    layout.add_root(padded.clone());
+    layout.update();
+    assert!(layout.match_layouts(hashmap!{
+        child.id => Rect::new(Point::new(95.0, 20.0), Size::new(50.0, 40.0)),
+        bounded.id => Rect::new(Point::new(0.0, 0.0), Size::new(80.0, 10.0)),
+        padded.id => Rect::new(Point::new(0.0, 0.0), Size::new(70.0, 20.0)),
+    }));
+}
+
 #[test]
 fn edit_var() {
     let mut layout = TestLayout::new();
@@ -381,6 +635,742 @@ fn linear_layout_remove() {
     }));
 }
 
+#[test]
+fn linear_layout_insert() {
+    use layout::linear_layout::{LinearLayout, LinearLayoutSettings, Orientation, ItemAlignment};
+
+    let mut layout = TestLayout::new();
+
+    let mut root = layout.new_widget("root");
+    let mut item_1 = layout.new_widget("item_1");
+    let mut item_2 = layout.new_widget("item_2");
+    let mut item_3 = layout.new_widget("item_3");
+    let mut item_4 = layout.new_widget("item_4");
+
+    root.add(constraints![
+        top_left(Point::new(0.0, 0.0)),
+        size(Size::new(10.0, 100.0))
+    ]);
+    item_1.add(height(10.0));
+    item_2.add(height(20.0));
+    item_3.add(height(30.0));
+    item_4.add(height(5.0));
+    let mut settings = LinearLayoutSettings::new(Orientation::Vertical);
+    settings.item_align = ItemAlignment::Fill;
+    let linear_layout = LinearLayout::new(&mut *root, settings);
+    root.set_container(linear_layout);
+
+    root.add_child(&mut *item_1);
+    root.add_child(&mut *item_3);
+    layout.add_root(root.clone());
+    layout.update();
+
+    root.insert_child(1, &mut *item_2);
+    root.insert_child(0, &mut *item_4);
+    layout.update();
+    assert!(layout.match_layouts(hashmap!{
+        root.id => Rect::new(Point::new(0.0, 0.0), Size::new(10.0, 100.0)),
+        item_4.id => Rect::new(Point::new(0.0, 0.0), Size::new(10.0, 5.0)),
+        item_1.id => Rect::new(Point::new(0.0, 5.0), Size::new(10.0, 10.0)),
+        item_2.id => Rect::new(Point::new(0.0, 15.0), Size::new(10.0, 20.0)),
+        item_3.id => Rect::new(Point::new(0.0, 35.0), Size::new(10.0, 30.0)),
+    }));
+
+    root.remove_child(&mut *item_1);
+    layout.update();
+    assert!(layout.match_layouts(hashmap!{
+        root.id => Rect::new(Point::new(0.0, 0.0), Size::new(10.0, 100.0)),
+        item_4.id => Rect::new(Point::new(0.0, 0.0), Size::new(10.0, 5.0)),
+        item_2.id => Rect::new(Point::new(0.0, 5.0), Size::new(10.0, 20.0)),
+        item_3.id => Rect::new(Point::new(0.0, 25.0), Size::new(10.0, 30.0)),
+    }));
+}
+
+#[test]
+fn linear_layout_flex_grow() {
+    use layout::linear_layout::{LinearLayout, LinearLayoutSettings, Orientation, ItemAlignment, Flex};
+
+    let mut layout = TestLayout::new();
+
+    let mut root = layout.new_widget("root");
+    let mut sidebar = layout.new_widget("sidebar");
+    let mut content = layout.new_widget("content");
+
+    root.add(constraints![
+        top_left(Point::new(0.0, 0.0)),
+        size(Size::new(400.0, 10.0))
+    ]);
+    sidebar.set_container_params(Flex::grow(1.0));
+    content.set_container_params(Flex::grow(3.0));
+    let mut settings = LinearLayoutSettings::new(Orientation::Horizontal);
+    settings.item_align = ItemAlignment::Fill;
+    let linear_layout = LinearLayout::new(&mut *root, settings);
+    root.set_container(linear_layout);
+
+    root.add_child(&mut *sidebar);
+    root.add_child(&mut *content);
+
+    layout.add_root(root.clone());
+    layout.update();
+    assert!(layout.match_layouts(hashmap!{
+        sidebar.id => Rect::new(Point::new(0.0, 0.0), Size::new(100.0, 10.0)),
+        content.id => Rect::new(Point::new(100.0, 0.0), Size::new(300.0, 10.0)),
+    }));
+}
+
+#[test]
+fn linear_layout_flex_basis() {
+    use layout::linear_layout::{LinearLayout, LinearLayoutSettings, Orientation, ItemAlignment, Flex};
+
+    let mut layout = TestLayout::new();
+
+    let mut root = layout.new_widget("root");
+    let mut fixed = layout.new_widget("fixed");
+    let mut item_1 = layout.new_widget("item_1");
+    let mut item_2 = layout.new_widget("item_2");
+
+    root.add(constraints![
+        top_left(Point::new(0.0, 0.0)),
+        size(Size::new(300.0, 10.0))
+    ]);
+    fixed.add(width(50.0));
+    item_1.set_container_params(Flex::new(1.0, 1.0, 100.0));
+    item_2.set_container_params(Flex::new(1.0, 1.0, 50.0));
+    let mut settings = LinearLayoutSettings::new(Orientation::Horizontal);
+    settings.item_align = ItemAlignment::Fill;
+    let linear_layout = LinearLayout::new(&mut *root, settings);
+    root.set_container(linear_layout);
+
+    root.add_child(&mut *fixed);
+    root.add_child(&mut *item_1);
+    root.add_child(&mut *item_2);
+
+    layout.add_root(root.clone());
+    layout.update();
+    assert!(layout.match_layouts(hashmap!{
+        fixed.id => Rect::new(Point::new(0.0, 0.0), Size::new(50.0, 10.0)),
+        item_1.id => Rect::new(Point::new(50.0, 0.0), Size::new(150.0, 10.0)),
+        item_2.id => Rect::new(Point::new(200.0, 0.0), Size::new(100.0, 10.0)),
+    }));
+}
+
+#[test]
+fn linear_layout_flex_shrink() {
+    use layout::linear_layout::{LinearLayout, LinearLayoutSettings, Orientation, ItemAlignment, Flex};
+
+    let mut layout = TestLayout::new();
+
+    let mut root = layout.new_widget("root");
+    let mut item_1 = layout.new_widget("item_1");
+    let mut item_2 = layout.new_widget("item_2");
+
+    root.add(constraints![
+        top_left(Point::new(0.0, 0.0)),
+        size(Size::new(100.0, 10.0))
+    ]);
+    item_1.set_container_params(Flex::new(0.0, 0.0, 60.0));
+    item_2.set_container_params(Flex::new(0.0, 1.0, 60.0));
+    let mut settings = LinearLayoutSettings::new(Orientation::Horizontal);
+    settings.item_align = ItemAlignment::Fill;
+    let linear_layout = LinearLayout::new(&mut *root, settings);
+    root.set_container(linear_layout);
+
+    root.add_child(&mut *item_1);
+    root.add_child(&mut *item_2);
+
+    layout.add_root(root.clone());
+    layout.update();
+    assert!(layout.match_layouts(hashmap!{
+        item_1.id => Rect::new(Point::new(0.0, 0.0), Size::new(60.0, 10.0)),
+        item_2.id => Rect::new(Point::new(60.0, 0.0), Size::new(40.0, 10.0)),
+    }));
+}
+
+#[test]
+fn flow_layout_wrap() {
+    use layout::flow_layout::{FlowLayout, FlowLayoutSettings};
+
+    let mut layout = TestLayout::new();
+
+    let mut root = layout.new_widget("root");
+    root.add(constraints![
+        top_left(Point::new(0.0, 0.0)),
+        size(Size::new(100.0, 100.0))
+    ]);
+    let mut settings = FlowLayoutSettings::new();
+    settings.item_spacing = 10.0;
+    settings.row_spacing = 5.0;
+    let flow_layout = FlowLayout::new(&mut *root, settings);
+    root.set_container(flow_layout);
+
+    let mut items = Vec::new();
+    for (index, item_width) in [40.0, 40.0, 30.0, 60.0].iter().enumerate() {
+        let mut item = layout.new_widget(&format!("item_{}", index));
+        item.add(size(Size::new(*item_width, 20.0)));
+        root.add_child(&mut *item);
+        items.push(item);
+    }
+
+    layout.add_root(root.clone());
+    layout.update();
+    assert!(layout.match_layouts(hashmap!{
+        items[0].id => Rect::new(Point::new(0.0, 0.0), Size::new(40.0, 20.0)),
+        items[1].id => Rect::new(Point::new(50.0, 0.0), Size::new(40.0, 20.0)),
+        items[2].id => Rect::new(Point::new(0.0, 25.0), Size::new(30.0, 20.0)),
+        items[3].id => Rect::new(Point::new(40.0, 25.0), Size::new(60.0, 20.0)),
+    }));
+}
+
+#[test]
+fn flow_layout_shrink_parent() {
+    use layout::flow_layout::{FlowLayout, FlowLayoutSettings};
+
+    let mut layout = TestLayout::new();
+
+    let mut root = layout.new_widget("root");
+    root.add(constraints![
+        top_left(Point::new(0.0, 0.0)),
+        size(Size::new(100.0, 100.0))
+    ]);
+    // the width of the flow layout depends on it's rows, which depend on it's width
+    let mut flow = layout.new_widget("flow");
+    flow.add(constraints![
+        align_top(&root),
+        align_left(&root),
+        bound_right(&root),
+        shrink(),
+    ]);
+    let flow_layout = FlowLayout::new(&mut *flow, FlowLayoutSettings::new());
+    flow.set_container(flow_layout);
+    root.add_child(&mut *flow);
+
+    let mut items = Vec::new();
+    for (index, item_width) in [50.0, 30.0, 30.0].iter().enumerate() {
+        let mut item = layout.new_widget(&format!("item_{}", index));
+        item.add(constraints![
+            size(Size::new(*item_width, 10.0)),
+            bound_right(&flow).strength(STRONG),
+        ]);
+        flow.add_child(&mut *item);
+        items.push(item);
+    }
+
+    layout.add_root(root.clone());
+    layout.update();
+    assert!(layout.match_layouts(hashmap!{
+        flow.id => Rect::new(Point::new(0.0, 0.0), Size::new(80.0, 20.0)),
+        items[0].id => Rect::new(Point::new(0.0, 0.0), Size::new(50.0, 10.0)),
+        items[1].id => Rect::new(Point::new(50.0, 0.0), Size::new(30.0, 10.0)),
+        items[2].id => Rect::new(Point::new(0.0, 10.0), Size::new(30.0, 10.0)),
+    }));
+}
+
+#[test]
+fn flow_layout_row_align() {
+    use layout::flow_layout::{FlowLayout, FlowLayoutSettings, RowAlignment};
+
+    let mut layout = TestLayout::new();
+
+    let mut root = layout.new_widget("root");
+    root.add(constraints![
+        top_left(Point::new(0.0, 0.0)),
+        size(Size::new(100.0, 100.0))
+    ]);
+    let mut settings = FlowLayoutSettings::new();
+    settings.row_align = RowAlignment::Center;
+    let flow_layout = FlowLayout::new(&mut *root, settings);
+    root.set_container(flow_layout);
+
+    let mut item_1 = layout.new_widget("item_1");
+    let mut item_2 = layout.new_widget("item_2");
+    let mut item_3 = layout.new_widget("item_3");
+    item_1.add(size(Size::new(50.0, 10.0)));
+    item_2.add(size(Size::new(30.0, 20.0)));
+    item_3.add(size(Size::new(40.0, 10.0)));
+    root.add_child(&mut *item_1);
+    root.add_child(&mut *item_2);
+    root.add_child(&mut *item_3);
+
+    layout.add_root(root.clone());
+    layout.update();
+    assert!(layout.match_layouts(hashmap!{
+        item_1.id => Rect::new(Point::new(10.0, 0.0), Size::new(50.0, 10.0)),
+        item_2.id => Rect::new(Point::new(60.0, 0.0), Size::new(30.0, 20.0)),
+        item_3.id => Rect::new(Point::new(30.0, 20.0), Size::new(40.0, 10.0)),
+    }));
+}
+
+#[test]
+fn flow_layout_resize() {
+    use layout::flow_layout::{FlowLayout, FlowLayoutSettings};
+
+    let mut layout = TestLayout::new();
+
+    let mut root = layout.new_widget("root");
+    root.add(top_left(Point::new(0.0, 0.0)));
+    root.edit_width().set(100.0);
+    root.edit_height().set(100.0);
+    let flow_layout = FlowLayout::new(&mut *root, FlowLayoutSettings::new());
+    root.set_container(flow_layout);
+
+    let mut items = Vec::new();
+    for index in 0..3 {
+        let mut item = layout.new_widget(&format!("item_{}", index));
+        item.add(size(Size::new(40.0, 10.0)));
+        root.add_child(&mut *item);
+        items.push(item);
+    }
+
+    layout.add_root(root.clone());
+    layout.update();
+    assert!(layout.match_layouts(hashmap!{
+        items[0].id => Rect::new(Point::new(0.0, 0.0), Size::new(40.0, 10.0)),
+        items[1].id => Rect::new(Point::new(40.0, 0.0), Size::new(40.0, 10.0)),
+        items[2].id => Rect::new(Point::new(0.0, 10.0), Size::new(40.0, 10.0)),
+    }));
+
+    root.edit_width().set(150.0);
+    layout.update();
+    assert!(layout.match_layouts(hashmap!{
+        items[0].id => Rect::new(Point::new(0.0, 0.0), Size::new(40.0, 10.0)),
+        items[1].id => Rect::new(Point::new(40.0, 0.0), Size::new(40.0, 10.0)),
+        items[2].id => Rect::new(Point::new(80.0, 0.0), Size::new(40.0, 10.0)),
+    }));
+
+    let item = items.remove(0);
+    root.remove_child(&mut *item.clone());
+    root.edit_width().set(50.0);
+    layout.update();
+    assert!(layout.match_layouts(hashmap!{
+        items[0].id => Rect::new(Point::new(0.0, 0.0), Size::new(40.0, 10.0)),
+        items[1].id => Rect::new(Point::new(0.0, 10.0), Size::new(40.0, 10.0)),
+    }));
+}
+
+#[test]
+fn stack_layout_anchors() {
+    use layout::stack_layout::{StackLayout, StackItem, Anchor};
+
+    let mut layout = TestLayout::new();
+
+    let mut root = layout.new_widget("root");
+    root.add(constraints![
+        top_left(Point::new(0.0, 0.0)),
+        size(Size::new(100.0, 100.0))
+    ]);
+    let stack_layout = StackLayout::new(&mut *root);
+    root.set_container(stack_layout);
+
+    let mut background = layout.new_widget("background");
+    let mut badge = layout.new_widget("badge");
+    let mut label = layout.new_widget("label");
+    let mut footer = layout.new_widget("footer");
+    background.set_container_params(StackItem::new(Anchor::Fill).margin(5.0));
+    badge.set_container_params(StackItem::new(Anchor::TopRight));
+    badge.add(size(Size::new(10.0, 10.0)));
+    label.set_container_params(StackItem::new(Anchor::Center));
+    label.add(size(Size::new(40.0, 20.0)));
+    footer.set_container_params(StackItem::new(Anchor::Bottom).margin(10.0));
+    footer.add(size(Size::new(60.0, 10.0)));
+    root.add_child(&mut *background);
+    root.add_child(&mut *badge);
+    root.add_child(&mut *label);
+    root.add_child(&mut *footer);
+
+    layout.add_root(root.clone());
+    layout.update();
+    assert!(layout.match_layouts(hashmap!{
+        background.id => Rect::new(Point::new(5.0, 5.0), Size::new(90.0, 90.0)),
+        badge.id => Rect::new(Point::new(90.0, 0.0), Size::new(10.0, 10.0)),
+        label.id => Rect::new(Point::new(30.0, 40.0), Size::new(40.0, 20.0)),
+        footer.id => Rect::new(Point::new(20.0, 80.0), Size::new(60.0, 10.0)),
+    }));
+}
+
+#[test]
+fn stack_layout_show_selected() {
+    use layout::stack_layout::{self, StackLayout};
+
+    let mut layout = TestLayout::new();
+
+    let mut root = layout.new_widget("root");
+    root.add(constraints![
+        top_left(Point::new(0.0, 0.0)),
+        size(Size::new(100.0, 100.0))
+    ]);
+    let stack_layout = StackLayout::new(&mut *root);
+    root.set_container(stack_layout);
+
+    let mut page_1 = layout.new_widget("page_1");
+    let mut page_2 = layout.new_widget("page_2");
+    root.add_child(&mut *page_1);
+    root.add_child(&mut *page_2);
+    stack_layout::show_selected(&mut [&mut *page_1, &mut *page_2], None);
+
+    layout.add_root(root.clone());
+    layout.update();
+    assert!(layout.match_layouts(hashmap!{
+        page_1.id => Rect::zero(),
+        page_2.id => Rect::new(Point::new(0.0, 0.0), Size::new(100.0, 100.0)),
+    }));
+
+    stack_layout::show_selected(&mut [&mut *page_1, &mut *page_2], Some(0));
+    layout.update();
+    assert!(layout.match_layouts(hashmap!{
+        page_1.id => Rect::new(Point::new(0.0, 0.0), Size::new(100.0, 100.0)),
+        page_2.id => Rect::zero(),
+    }));
+}
+
+#[test]
+fn linear_layout_baseline() {
+    use layout::linear_layout::{LinearLayout, LinearLayoutSettings, Orientation, ItemAlignment};
+
+    let mut layout = TestLayout::new();
+
+    let mut root = layout.new_widget("root");
+    let mut small = layout.new_widget("small");
+    let mut large = layout.new_widget("large");
+
+    root.add(constraints![
+        top_left(Point::new(0.0, 0.0)),
+        size(Size::new(200.0, 50.0))
+    ]);
+    let (small_baseline, small_top) = (small.baseline(), small.vars.top);
+    small.add(constraints![
+        size(Size::new(50.0, 20.0)),
+        small_baseline | EQ(REQUIRED) | small_top + 15.0,
+    ]);
+    let (large_baseline, large_top) = (large.baseline(), large.vars.top);
+    large.add(constraints![
+        size(Size::new(50.0, 40.0)),
+        large_baseline | EQ(REQUIRED) | large_top + 30.0,
+    ]);
+    let mut settings = LinearLayoutSettings::new(Orientation::Horizontal);
+    settings.item_align = ItemAlignment::Baseline;
+    let linear_layout = LinearLayout::new(&mut *root, settings);
+    root.set_container(linear_layout);
+
+    root.add_child(&mut *small);
+    root.add_child(&mut *large);
+
+    layout.add_root(root.clone());
+    layout.update();
+    assert!(layout.match_layouts(hashmap!{
+        small.id => Rect::new(Point::new(0.0, 15.0), Size::new(50.0, 20.0)),
+        large.id => Rect::new(Point::new(50.0, 0.0), Size::new(50.0, 40.0)),
+    }));
+}
+
+#[test]
+fn align_baseline_creates_baselines() {
+    let mut layout = TestLayout::new();
+
+    let mut root = layout.new_widget("root");
+    let mut label = layout.new_widget("label");
+    let mut detail = layout.new_widget("detail");
+
+    root.add(constraints![
+        top_left(Point::new(0.0, 0.0)),
+        size(Size::new(200.0, 50.0))
+    ]);
+    label.add(constraints![
+        align_top(&root),
+        align_left(&root),
+        size(Size::new(100.0, 40.0)),
+    ]);
+    assert!(label.vars.baseline.is_none() && detail.vars.baseline.is_none());
+    detail.add(constraints![
+        align_to_right_of(&label),
+        size(Size::new(50.0, 20.0)),
+        align_baseline(&label),
+    ]);
+    let (label_baseline, label_top) = (label.vars.baseline.unwrap(), label.vars.top);
+    label.add(label_baseline | EQ(REQUIRED) | label_top + 30.0);
+    let (detail_baseline, detail_top) = (detail.vars.baseline.unwrap(), detail.vars.top);
+    detail.add(detail_baseline | EQ(REQUIRED) | detail_top + 15.0);
+    assert_eq!(root.vars.array().len(), 6);
+
+    root.add_child(&mut *label);
+    root.add_child(&mut *detail);
+    layout.add_root(root.clone());
+    layout.update();
+    assert!(layout.match_layouts(hashmap!{
+        label.id => Rect::new(Point::new(0.0, 0.0), Size::new(100.0, 40.0)),
+        detail.id => Rect::new(Point::new(100.0, 15.0), Size::new(50.0, 20.0)),
+    }));
+}
+
+#[test]
+fn linear_layout_right_to_left() {
+    use layout::linear_layout::{LinearLayout, LinearLayoutSettings, Orientation, ItemAlignment};
+
+    let mut layout = TestLayout::new();
+
+    let mut root = layout.new_widget("root");
+    let mut widget_o = layout.new_widget("widget_o");
+    let mut widget_t = layout.new_widget("widget_t");
+
+    root.add(constraints![
+        top_left(Point::new(0.0, 0.0)),
+        size(Size::new(200.0, 100.0))
+    ]);
+    widget_o.add(size(Size::new(50.0, 50.0)));
+    widget_t.add(size(Size::new(50.0, 50.0)));
+    root.set_direction(Direction::RightToLeft);
+    let mut settings = LinearLayoutSettings::new(Orientation::Horizontal);
+    settings.padding = 10.0;
+    settings.item_align = ItemAlignment::Top;
+    let linear_layout = LinearLayout::new(&mut *root, settings);
+    root.set_container(linear_layout);
+
+    root.add_child(&mut *widget_o);
+    root.add_child(&mut *widget_t);
+    assert_eq!(widget_o.direction(), Direction::RightToLeft);
+
+    layout.add_root(root.clone());
+    layout.update();
+    assert!(layout.match_layouts(hashmap!{
+        widget_o.id => Rect::new(Point::new(150.0, 0.0), Size::new(50.0, 50.0)),
+        widget_t.id => Rect::new(Point::new(90.0, 0.0), Size::new(50.0, 50.0)),
+    }));
+
+    root.set_direction(Direction::LeftToRight);
+    layout.update();
+    assert!(layout.match_layouts(hashmap!{
+        widget_o.id => Rect::new(Point::new(0.0, 0.0), Size::new(50.0, 50.0)),
+        widget_t.id => Rect::new(Point::new(60.0, 0.0), Size::new(50.0, 50.0)),
+    }));
+}
+
+#[test]
+fn leading_trailing() {
+    let mut layout = TestLayout::new();
+
+    let mut root = layout.new_widget("root");
+    let mut leading = layout.new_widget("leading");
+    let mut trailing = layout.new_widget("trailing");
+
+    root.add(constraints![
+        top_left(Point::new(0.0, 0.0)),
+        size(Size::new(200.0, 100.0))
+    ]);
+    leading.add(constraints![
+        size(Size::new(50.0, 50.0)),
+        align_top(&root),
+        align_leading(&root).padding(10.0),
+    ]);
+    trailing.add(constraints![
+        size(Size::new(50.0, 50.0)),
+        align_top(&root),
+        align_trailing(&root).padding(20.0),
+        bound_leading(&root),
+    ]);
+    root.add_child(&mut *leading);
+    root.add_child(&mut *trailing);
+
+    layout.add_root(root.clone());
+    layout.update();
+    assert!(layout.match_layouts(hashmap!{
+        leading.id => Rect::new(Point::new(10.0, 0.0), Size::new(50.0, 50.0)),
+        trailing.id => Rect::new(Point::new(130.0, 0.0), Size::new(50.0, 50.0)),
+    }));
+
+    root.set_direction(Direction::RightToLeft);
+    for child in &mut [&mut leading, &mut trailing] {
+        child.inherit_direction(Direction::RightToLeft);
+    }
+    layout.update();
+    assert!(layout.match_layouts(hashmap!{
+        leading.id => Rect::new(Point::new(140.0, 0.0), Size::new(50.0, 50.0)),
+        trailing.id => Rect::new(Point::new(20.0, 0.0), Size::new(50.0, 50.0)),
+    }));
+}
+
+#[test]
+fn direct_linear_layout() {
+    use layout::linear_layout::{LinearLayoutSettings, Orientation, ItemAlignment};
+    use layout::direct::DirectLinearLayout;
+
+    let mut layout = TestLayout::new();
+
+    let mut root = layout.new_widget("root");
+    let mut widget_o = layout.new_widget("widget_o");
+    let mut widget_t = layout.new_widget("widget_t");
+    let mut widget_h = layout.new_widget("widget_h");
+
+    root.add(constraints![
+        top_left(Point::new(0.0, 0.0)),
+        size(Size::new(200.0, 100.0))
+    ]);
+    let mut settings = LinearLayoutSettings::new(Orientation::Vertical);
+    settings.padding = 10.0;
+    settings.item_align = ItemAlignment::Fill;
+    root.set_direct_container(DirectLinearLayout::new(settings));
+
+    widget_o.set_measured_size(Size::new(50.0, 20.0));
+    widget_t.set_measured_size(Size::new(80.0, 30.0));
+    widget_h.set_measured_size(Size::new(60.0, 20.0));
+    widget_h.hide();
+    root.add_child(&mut *widget_o);
+    root.add_child(&mut *widget_t);
+    root.add_child(&mut *widget_h);
+
+    layout.add_root(root.clone());
+    layout.update();
+    assert!(layout.match_layouts(hashmap!{
+        widget_o.id => Rect::new(Point::new(0.0, 0.0), Size::new(200.0, 20.0)),
+        widget_t.id => Rect::new(Point::new(0.0, 30.0), Size::new(200.0, 30.0)),
+        widget_h.id => Rect::zero(),
+    }));
+    // the children are arranged without the solver
+    assert_eq!(layout.solver.layout_graph().layouts.len(), 1);
+
+    widget_h.show();
+    layout.update();
+    assert!(layout.match_layouts(hashmap!{
+        widget_h.id => Rect::new(Point::new(0.0, 70.0), Size::new(200.0, 20.0)),
+    }));
+}
+
+#[test]
+fn direct_layout_solved_children() {
+    use layout::linear_layout::{LinearLayoutSettings, Orientation, ItemAlignment};
+    use layout::direct::DirectLinearLayout;
+
+    let mut layout = TestLayout::new();
+
+    let mut root = layout.new_widget("root");
+    let mut panel = layout.new_widget("panel");
+    let mut label = layout.new_widget("label");
+    let mut fixed = layout.new_widget("fixed");
+
+    root.add(constraints![
+        top_left(Point::new(0.0, 0.0)),
+        size(Size::new(200.0, 100.0))
+    ]);
+    let mut settings = LinearLayoutSettings::new(Orientation::Horizontal);
+    settings.item_align = ItemAlignment::Top;
+    root.set_direct_container(DirectLinearLayout::new(settings));
+
+    // a child with children outside of a direct container is solved, with it's arranged bounds
+    panel.set_measured_size(Size::new(100.0, 50.0));
+    root.add_child(&mut *panel);
+    panel.add_child(&mut *label);
+    label.add(constraints![
+        size(Size::new(20.0, 10.0)),
+        align_top(&panel),
+        align_left(&panel).padding(5.0),
+    ]);
+    // a child with a fixed size is sized by the container, without the solver
+    fixed.add(size(Size::new(60.0, 60.0)));
+    root.add_child(&mut *fixed);
+
+    layout.add_root(root.clone());
+    layout.update();
+    assert!(layout.match_layouts(hashmap!{
+        panel.id => Rect::new(Point::new(0.0, 0.0), Size::new(100.0, 50.0)),
+        label.id => Rect::new(Point::new(5.0, 0.0), Size::new(20.0, 10.0)),
+        fixed.id => Rect::new(Point::new(100.0, 0.0), Size::new(60.0, 60.0)),
+    }));
+    assert_eq!(layout.solver.layout_graph().layouts.len(), 3);
+}
+
+#[test]
+fn direct_layout_size_constraints() {
+    use layout::linear_layout::{LinearLayoutSettings, Orientation, ItemAlignment};
+    use layout::direct::DirectLinearLayout;
+
+    let mut layout = TestLayout::new();
+
+    let mut root = layout.new_widget("root");
+    let mut item_m = layout.new_widget("item_m");
+    let mut item_h = layout.new_widget("item_h");
+    let mut item_c = layout.new_widget("item_c");
+
+    root.add(constraints![
+        top_left(Point::new(0.0, 0.0)),
+        size(Size::new(200.0, 100.0))
+    ]);
+    let mut settings = LinearLayoutSettings::new(Orientation::Vertical);
+    settings.item_align = ItemAlignment::Left;
+    root.set_direct_container(DirectLinearLayout::new(settings));
+
+    item_m.set_measured_size(Size::new(50.0, 20.0));
+    item_m.add(match_width(&root).padding(10.0));
+    item_h.set_measured_size(Size::new(50.0, 20.0));
+    item_h.add(height(30.0));
+    // other constraints need the solver, until they're removed
+    item_c.set_measured_size(Size::new(50.0, 20.0));
+    let min_width = item_c.create_constraint(min_width(70.0));
+    item_c.add(min_width.clone());
+    root.add_child(&mut *item_m);
+    root.add_child(&mut *item_h);
+    root.add_child(&mut *item_c);
+
+    layout.add_root(root.clone());
+    layout.update();
+    assert!(layout.match_layouts(hashmap!{
+        item_m.id => Rect::new(Point::new(0.0, 0.0), Size::new(190.0, 20.0)),
+        item_h.id => Rect::new(Point::new(0.0, 20.0), Size::new(50.0, 30.0)),
+        item_c.id => Rect::new(Point::new(0.0, 50.0), Size::new(70.0, 20.0)),
+    }));
+    assert_eq!(layout.solver.layout_graph().layouts.len(), 2);
+
+    item_c.remove_constraints(min_width);
+    layout.update();
+    assert!(layout.match_layouts(hashmap!{
+        item_c.id => Rect::new(Point::new(0.0, 50.0), Size::new(50.0, 20.0)),
+    }));
+    assert_eq!(layout.solver.layout_graph().layouts.len(), 1);
+}
+
+#[test]
+#[should_panic]
+fn direct_linear_layout_baseline() {
+    use layout::linear_layout::{LinearLayoutSettings, Orientation, ItemAlignment};
+    use layout::direct::DirectLinearLayout;
+
+    let mut settings = LinearLayoutSettings::new(Orientation::Horizontal);
+    settings.item_align = ItemAlignment::Baseline;
+    DirectLinearLayout::new(settings);
+}
+
+#[test]
+fn direct_grid_layout() {
+    use layout::grid_layout::{GridLayoutSettings, TrackSize};
+    use layout::direct::DirectGridLayout;
+
+    let mut layout = TestLayout::new();
+
+    let mut root = layout.new_widget("root");
+    root.add(constraints![
+        top_left(Point::new(0.0, 0.0)),
+        size(Size::new(300.0, 100.0))
+    ]);
+    let mut settings = GridLayoutSettings::new(vec![TrackSize::Fixed(50.0), TrackSize::Auto, TrackSize::Fraction(1.0)]);
+    settings.column_gap = 10.0;
+    root.set_direct_container(DirectGridLayout::new(settings));
+
+    let sizes = vec![Size::new(30.0, 20.0), Size::new(40.0, 25.0), Size::new(10.0, 10.0), Size::new(20.0, 15.0)];
+    let mut children = Vec::new();
+    for (index, size) in sizes.into_iter().enumerate() {
+        let mut child = layout.new_widget(&format!("child_{}", index));
+        child.set_measured_size(size);
+        root.add_child(&mut *child);
+        children.push(child);
+    }
+
+    layout.add_root(root.clone());
+    layout.update();
+    assert!(layout.match_layouts(hashmap!{
+        children[0].id => Rect::new(Point::new(0.0, 0.0), Size::new(50.0, 25.0)),
+        children[1].id => Rect::new(Point::new(60.0, 0.0), Size::new(40.0, 25.0)),
+        children[2].id => Rect::new(Point::new(110.0, 0.0), Size::new(190.0, 25.0)),
+        children[3].id => Rect::new(Point::new(0.0, 25.0), Size::new(50.0, 15.0)),
+    }));
+}
+
 #[derive(Clone)]
 struct SharedLayout(Rc<RefCell<Layout>>);
 impl SharedLayout {
@@ -392,6 +1382,9 @@ impl LayoutRef for SharedLayout {
     fn layout_ref(&self) -> LayoutVars {
         self.0.borrow().vars.clone()
     }
+    fn baseline_ref(&self) -> Option<Variable> {
+        Some(self.0.borrow_mut().baseline())
+    }
 }
 impl <'a> Deref for SharedLayout {
     type Target = Layout;
@@ -437,27 +1430,45 @@ impl TestLayout {
     fn add_root(&mut self, layout: SharedLayout) {
         self.roots.push(layout);
     }
-    fn update_layout(&mut self, mut layout: SharedLayout) {
-        self.solver.update_layout(layout.deref_mut());
+    /// Pass `layout` and it's descendants to the solver, adding them to `updated`
+    fn update_layout(&mut self, mut layout: SharedLayout, updated: &mut Vec<SharedLayout>) {
+        self.solver.update_layout(layout.deref_mut()).unwrap();
+        updated.push(layout.clone());
         for child in layout.get_children() {
             let layout = self.layouts[child].clone();
-            self.update_layout(layout);
+            self.update_layout(layout, updated);
         }
     }
     fn update(&mut self) {
-        for layout in self.roots.clone() {
-            self.update_layout(layout);
-        }
-        for (id, var, value) in self.solver.fetch_changes() {
-            let rect = self.layout_rects.entry(id).or_insert(Rect::zero());
-            match var {
-                VarType::Left => rect.origin.x = value as f32,
-                VarType::Top => rect.origin.y = value as f32,
-                VarType::Width => rect.size.width = value as f32,
-                VarType::Height => rect.size.height = value as f32,
-                _ => (),
+        // containers can change their constraints after a solve, eg. a `FlowLayout`
+        // wrapping it's children, so keep solving until the layouts are stable
+        let mut stable = false;
+        for _ in 0..10 {
+            let mut updated = Vec::new();
+            for layout in self.roots.clone() {
+                self.update_layout(layout, &mut updated);
+            }
+            for (id, var, value) in self.solver.fetch_changes() {
+                let rect = self.layout_rects.entry(id).or_insert(Rect::zero());
+                match var {
+                    VarType::Left => rect.origin.x = value as f32,
+                    VarType::Top => rect.origin.y = value as f32,
+                    VarType::Width => rect.size.width = value as f32,
+                    VarType::Height => rect.size.height = value as f32,
+                    _ => (),
+                }
+            }
+            let mut pending_changes = false;
+            for layout in &mut updated {
+                layout.container_layout_changed(&self.solver);
+                pending_changes |= layout.has_pending_changes();
+            }
+            if !pending_changes {
+                stable = true;
+                break;
             }
         }
+        assert!(stable, "layout still changing after 10 solves");
     }
     fn match_layouts(&self, layouts: HashMap<LayoutId, Rect>) -> bool {
         for (match_layout_id, match_layout_rect) in layouts {
@@ -11,5 +11,5 @@ pub mod prelude {
     pub use super::glcanvas::GLCanvasState;
     pub use super::image::ImageState;
     pub use super::rect::{RectState, RectStyle};
-    pub use super::text::{TextState, TextStyle};
+    pub use super::text::{TextState, TextStyle, TextLink, TextShadow, TextOrientation};
 }
@@ -1,11 +1,15 @@
-use webrender::api::{LayoutPoint, GlyphInstance, PrimitiveInfo, FontInstanceKey};
+use std::f32;
+use std::ops::Range;
+
+use webrender::api::{LayoutPoint, GlyphInstance, PrimitiveInfo, FontInstanceKey, ColorF, Shadow};
+use webrender::api::{LayoutTransform, PropertyBinding, ScrollPolicy, TransformStyle, MixBlendMode};
 use rusttype::{Scale, GlyphId, VMetrics};
 
 use render::RenderBuilder;
 use text_layout::{self, Wrap, Align};
 use resources::resources;
-use resources::font::FontDescriptor;
-use geometry::{Size, Rect, RectExt, Vector};
+use resources::font::{FontDescriptor, DecorationMetrics};
+use geometry::{Size, Rect, RectExt, Point, Vector};
 use render;
 use widget::draw::Draw;
 use color::*;
@@ -20,8 +24,53 @@ component_style!{pub struct TextState<name="text", style=TextStyle> {
     background_color: Color = TRANSPARENT,
     wrap: Wrap = Wrap::Whitespace,
     align: Align = Align::Start,
+    links: Vec<TextLink> = Vec::new(),
+    link_color: Color = BLUE,
+    link_hover_color: Color = BLUE_HIGHLIGHT,
+    hovered_link: Option<usize> = None,
+    underline: bool = false,
+    overline: bool = false,
+    strikethrough: bool = false,
+    /// Color of underlines, overlines and strikethroughs, if `None` the text color is used
+    decoration_color: Option<Color> = None,
+    shadow: Option<TextShadow> = None,
+    orientation: TextOrientation = TextOrientation::Horizontal,
 }}
 
+/// Direction text is drawn in. Rotated text is laid out as if it were horizontal,
+/// with the width and height of the bounds swapped, then rotated into place.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TextOrientation {
+    Horizontal,
+    /// Rotated 90 degrees clockwise, read from top to bottom
+    Rotate90,
+    /// Rotated 270 degrees clockwise, read from bottom to top
+    Rotate270,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextShadow {
+    pub offset: Vector,
+    pub blur_radius: f32,
+    pub color: Color,
+}
+
+/// A clickable span of text, `range` is a byte range into the text.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextLink {
+    pub range: Range<usize>,
+    pub target: String,
+}
+
+impl TextLink {
+    pub fn new(range: Range<usize>, target: &str) -> Self {
+        TextLink {
+            range: range,
+            target: String::from(target),
+        }
+    }
+}
+
 impl TextStyle {
     pub fn from_text(text: &str) -> Self {
         Self {
@@ -32,16 +81,31 @@ impl TextStyle {
 }
 
 impl TextState {
+    /// Measure the size of the text, the width and height are swapped for rotated text
     pub fn measure(&self) -> Size {
         let line_height = self.line_height();
         let mut resources = resources();
         let font = resources.font_loader.get_font(&self.font).unwrap();
-        Size::from_untyped(&text_layout::get_text_size(
+        let size = Size::from_untyped(&text_layout::get_text_size(
             &self.text,
             &font.info,
             self.font_size,
             line_height,
-            self.wrap))
+            self.wrap));
+        if self.is_rotated() {
+            Size::new(size.height, size.width)
+        } else {
+            size
+        }
+    }
+    /// Distance from the top of the text to the baseline of the first line,
+    /// `None` for rotated text
+    pub fn baseline(&self) -> Option<f32> {
+        if self.is_rotated() {
+            None
+        } else {
+            Some(self.font_size + self.v_metrics().descent)
+        }
     }
     pub fn min_height(&self) -> f32 {
         self.line_height()
@@ -50,6 +114,7 @@ impl TextState {
         self.font_size + self.v_metrics().line_gap
     }
     pub fn text_fits(&self, text: &str, bounds: Rect) -> bool {
+        let bounds = self.layout_bounds(bounds);
         let line_height = self.line_height();
         let mut resources = resources();
         let font = resources.font_loader.get_font(&self.font).unwrap();
@@ -75,26 +140,128 @@ impl TextState {
             self.wrap,
             self.align).iter().map(|rect| Rect::from_untyped(rect)).collect()
     }
-    fn position_glyphs(&self, bounds: Rect) -> Vec<GlyphInstance> {
+    /// Get the index of the link under `point`, for text drawn within `bounds`
+    pub fn link_at(&self, bounds: Rect, point: Point) -> Option<usize> {
+        if self.links.is_empty() {
+            return None;
+        }
+        let point = self.unrotate_point(bounds, point);
+        let bounds = self.layout_bounds(bounds);
+        let line_height = self.line_height();
+        let byte = {
+            let mut resources = resources();
+            let font = resources.font_loader.get_font(&self.font).unwrap();
+            text_layout::get_byte_index_at(
+                &self.text,
+                bounds.to_untyped(),
+                &font.info,
+                self.font_size,
+                line_height,
+                self.wrap,
+                self.align,
+                point.to_untyped())
+        };
+        byte.and_then(|byte| self.link_containing(byte))
+    }
+    fn is_rotated(&self) -> bool {
+        self.orientation != TextOrientation::Horizontal
+    }
+    /// The bounds text is laid out in before it is rotated, `bounds` rotated around it's center
+    fn layout_bounds(&self, bounds: Rect) -> Rect {
+        if self.is_rotated() {
+            let center = bounds.center();
+            let size = Size::new(bounds.height(), bounds.width());
+            Rect::new(Point::new(center.x - size.width / 2.0, center.y - size.height / 2.0), size)
+        } else {
+            bounds
+        }
+    }
+    /// Clockwise rotation of the text in radians
+    fn rotation(&self) -> f32 {
+        match self.orientation {
+            TextOrientation::Horizontal => 0.0,
+            TextOrientation::Rotate90 => f32::consts::FRAC_PI_2,
+            TextOrientation::Rotate270 => 3.0 * f32::consts::FRAC_PI_2,
+        }
+    }
+    /// Transform that rotates the text around the center of `bounds`
+    fn rotation_transform(&self, bounds: Rect) -> LayoutTransform {
+        let center = bounds.center();
+        let (sin, cos) = self.rotation().sin_cos();
+        LayoutTransform::row_major(
+            cos, sin, 0.0, 0.0,
+            -sin, cos, 0.0, 0.0,
+            0.0, 0.0, 1.0, 0.0,
+            center.x - center.x * cos + center.y * sin, center.y - center.x * sin - center.y * cos, 0.0, 1.0)
+    }
+    /// Map a point within `bounds` to where it would be in the text before rotation
+    fn unrotate_point(&self, bounds: Rect, point: Point) -> Point {
+        let center = bounds.center();
+        let (sin, cos) = (-self.rotation()).sin_cos();
+        let offset = point - center;
+        Point::new(center.x + offset.x * cos - offset.y * sin, center.y + offset.x * sin + offset.y * cos)
+    }
+    fn link_containing(&self, byte: usize) -> Option<usize> {
+        self.links.iter().position(|link| link.range.start <= byte && byte < link.range.end)
+    }
+    fn glyph_color(&self, byte: usize) -> Color {
+        match self.link_containing(byte) {
+            Some(link) if Some(link) == self.hovered_link => self.link_hover_color,
+            Some(_) => self.link_color,
+            None => self.text_color,
+        }
+    }
+    /// Position glyphs within `bounds`, each paired with the byte index of the char it represents
+    fn position_glyphs(&self, bounds: Rect) -> Vec<(usize, GlyphInstance)> {
         let line_height = self.line_height();
         let descent = self.v_metrics().descent;
         let mut resources = resources();
         let font = resources.font_loader.get_font(&self.font).unwrap();
-        text_layout::get_positioned_glyphs(
+        text_layout::get_indexed_glyphs(
             &self.text,
             bounds.to_untyped(),
             &font.info,
             self.font_size,
             line_height,
             self.wrap,
-            self.align).iter().map(|glyph| {
+            self.align).iter().map(|&(byte, ref glyph)| {
                 let position = glyph.position();
-                GlyphInstance {
+                (byte, GlyphInstance {
                     index: glyph.id().0,
                     point: LayoutPoint::new(position.x, position.y + descent),
-                }
+                })
             }).collect()
     }
+    /// Draw underline, overline and strikethrough for each line of text
+    fn draw_decorations(&self, bounds: Rect, renderer: &mut RenderBuilder) {
+        if !(self.underline || self.overline || self.strikethrough) {
+            return;
+        }
+        let v_metrics = self.v_metrics();
+        let metrics = self.decoration_metrics();
+        let color = self.decoration_color.unwrap_or(self.text_color).into();
+        let font_size = self.font_size;
+        for line in self.get_line_rects(bounds) {
+            let baseline = line.top() + font_size + v_metrics.descent;
+            if self.underline {
+                let top = baseline - metrics.underline_position * font_size;
+                push_line(line, top, metrics.underline_thickness * font_size, color, renderer);
+            }
+            if self.overline {
+                let top = baseline - v_metrics.ascent;
+                push_line(line, top, metrics.underline_thickness * font_size, color, renderer);
+            }
+            if self.strikethrough {
+                let top = baseline - metrics.strikeout_position * font_size;
+                push_line(line, top, metrics.strikeout_thickness * font_size, color, renderer);
+            }
+        }
+    }
+    fn decoration_metrics(&self) -> DecorationMetrics {
+        let mut resources = resources();
+        let font = resources.font_loader.get_font(&self.font).unwrap();
+        font.decorations
+    }
     fn font_instance_key(&self) -> FontInstanceKey {
         *resources().font_loader.get_font_instance(&self.font, self.font_size).unwrap()
     }
@@ -103,10 +270,7 @@ impl TextState {
         let font = resources.font_loader.get_font(&self.font).unwrap();
         font.info.v_metrics(Scale::uniform(self.font_size))
     }
-}
-
-impl Draw for TextState {
-    fn draw(&mut self, bounds: Rect, _: Rect, renderer: &mut RenderBuilder) {
+    fn draw_text(&self, bounds: Rect, renderer: &mut RenderBuilder) {
         let glyphs = self.position_glyphs(bounds);
         if DEBUG_LINE_BOUNDS {
             let line_rects = self.get_line_rects(bounds);
@@ -120,7 +284,7 @@ impl Draw for TextState {
                 render::draw_rect_outline(rect, RED, renderer);
             }
             let scale = Scale::uniform(self.font_size);
-            for glyph in &glyphs {
+            for &(_, ref glyph) in &glyphs {
                 let scaled_glyph = font.info.glyph(GlyphId(glyph.index)).unwrap().scaled(scale);
                 if let Some(rect) = scaled_glyph.exact_bounding_box() {
                     let origin = glyph.point.to_vector() + Vector::new(0.0, -1.0);
@@ -134,12 +298,57 @@ impl Draw for TextState {
         }
         let key = self.font_instance_key();
         let info = PrimitiveInfo::new(bounds);
-        renderer.builder.push_text(
-            &info,
-            &glyphs,
-            key,
-            self.text_color.into(),
-            None,
-        );
+        if let Some(ref shadow) = self.shadow {
+            renderer.builder.push_shadow(&info, Shadow {
+                offset: shadow.offset,
+                color: shadow.color.into(),
+                blur_radius: shadow.blur_radius,
+            });
+        }
+        // glyphs are pushed in runs of the same color, so links can be drawn differently
+        let mut run = Vec::new();
+        let mut run_color = self.text_color;
+        for &(byte, glyph) in &glyphs {
+            let color = self.glyph_color(byte);
+            if color != run_color && !run.is_empty() {
+                renderer.builder.push_text(&info, &run, key, run_color.into(), None);
+                run.clear();
+            }
+            run_color = color;
+            run.push(glyph);
+        }
+        if !run.is_empty() {
+            renderer.builder.push_text(&info, &run, key, run_color.into(), None);
+        }
+        self.draw_decorations(bounds, renderer);
+        if self.shadow.is_some() {
+            renderer.builder.pop_all_shadows();
+        }
+    }
+}
+
+impl Draw for TextState {
+    fn draw(&mut self, bounds: Rect, _: Rect, renderer: &mut RenderBuilder) {
+        if self.is_rotated() {
+            renderer.builder.push_stacking_context(
+                &PrimitiveInfo::new(Rect::zero()),
+                ScrollPolicy::Scrollable,
+                Some(PropertyBinding::Value(self.rotation_transform(bounds))),
+                TransformStyle::Flat,
+                None,
+                MixBlendMode::Normal,
+                Vec::new(),
+            );
+            self.draw_text(self.layout_bounds(bounds), renderer);
+            renderer.builder.pop_stacking_context();
+        } else {
+            self.draw_text(bounds, renderer);
+        }
     }
 }
+
+/// Push a decoration line spanning `line`, at least one pixel thick so it stays visible at small sizes
+fn push_line(line: Rect, top: f32, thickness: f32, color: ColorF, renderer: &mut RenderBuilder) {
+    let rect = Rect::new(Point::new(line.left(), top), Size::new(line.width(), thickness.max(1.0)));
+    renderer.builder.push_rect(&PrimitiveInfo::new(rect), color);
+}
@@ -38,6 +38,7 @@ impl WidgetModifier for Button {
                 bound_top(widget).padding(10.0),
                 bound_bottom(widget).padding(10.0),
                 center(widget),
+                align_baseline(widget),
             ]);
 
             widget.add_child(button_text_widget);
@@ -90,6 +91,7 @@ impl WidgetModifier for ToggleButton {
             bound_top(widget).padding(10.0),
             bound_bottom(widget).padding(10.0),
             center(widget),
+            align_baseline(widget),
         ]);
         StaticTextStyle::from_style(self.off_text.clone()).component().apply(&mut button_text_widget);
 
@@ -0,0 +1,303 @@
+use prelude::*;
+use draw::prelude::*;
+use widget::property::PropSet;
+use widgets::text::StaticTextStyle;
+
+/// State of a `Checkbox`, `Checked` sets `Property::Activated` on the checkbox
+/// and `Indeterminate` sets `Property::Indeterminate`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum CheckState {
+    Unchecked,
+    Checked,
+    /// Partially checked, eg. a checkbox for a group of items where only some are checked.
+    /// Only reached by clicking a checkbox if it's `tri_state`
+    Indeterminate,
+}
+
+impl CheckState {
+    fn from_props(props: &PropSet) -> Self {
+        if props.contains(&Property::Activated) {
+            CheckState::Checked
+        } else if props.contains(&Property::Indeterminate) {
+            CheckState::Indeterminate
+        } else {
+            CheckState::Unchecked
+        }
+    }
+    /// The state after the checkbox is clicked
+    fn next(&self, tri_state: bool) -> Self {
+        match *self {
+            CheckState::Unchecked => CheckState::Checked,
+            CheckState::Checked if tri_state => CheckState::Indeterminate,
+            CheckState::Checked | CheckState::Indeterminate => CheckState::Unchecked,
+        }
+    }
+}
+
+/// Sets the state of a `Checkbox`
+#[derive(Debug, Copy, Clone)]
+pub struct SetCheckState(pub CheckState);
+
+/// Emitted by a `Checkbox` when it's state changes
+#[derive(Debug, Copy, Clone)]
+pub struct CheckStateChanged(pub CheckState);
+
+component_style!{pub struct Checkbox<name="checkbox", style=CheckboxStyle> {
+    rect: RectStyle = RectStyle::default(),
+    check_color: Color = BLACK,
+    size: f32 = 20.0,
+    text: Option<TextStyle> = None,
+    tri_state: bool = false,
+}}
+
+impl CheckboxStyle {
+    pub fn from_text(text: &str) -> Self {
+        Self {
+            text: Some(Some(TextStyle::from_text(text))),
+            ..Self::default()
+        }
+    }
+    /// Cycle through `Unchecked`, `Checked` and `Indeterminate` when clicked
+    pub fn tri_state(mut self) -> Self {
+        self.tri_state = Some(true);
+        self
+    }
+}
+
+impl WidgetModifier for Checkbox {
+    fn apply(&self, widget: &mut Widget) {
+        widget.enable_hover();
+        widget.layout().add(shrink());
+
+        let mut box_widget = Widget::new("checkbox_rect");
+        let mut draw_style = DrawStyle::from(self.rect.clone());
+        draw_style.set_class("checkbox_rect");
+        box_widget.set_draw_style(draw_style);
+        box_widget.layout().add(constraints![
+            size(Size::new(self.size, self.size)),
+            align_left(widget),
+            bound_top(widget),
+            bound_bottom(widget),
+            center_vertical(widget),
+        ]);
+
+        // the check mark and the indeterminate dash are only visible in their respective states
+        let mut check = Widget::new("checkbox_check");
+        check.set_draw_style(check_draw_style("checkbox_check", ACTIVATED.clone(), self.check_color));
+        check.layout().add(match_layout(&box_widget).padding(self.size / 4.0));
+
+        let mut dash = Widget::new("checkbox_dash");
+        dash.set_draw_style(check_draw_style("checkbox_dash", INDETERMINATE.clone(), self.check_color));
+        dash.layout().add(constraints![
+            match_width(&box_widget).padding(self.size / 4.0),
+            height(self.size / 5.0),
+            center(&box_widget),
+        ]);
+
+        box_widget.add_child(check);
+        box_widget.add_child(dash);
+
+        if let Some(text_style) = self.text.clone() {
+            let mut text_widget = Widget::new("checkbox_text");
+            text_widget.set_draw_style(DrawStyle::from_class::<TextStyle>("checkbox_text"));
+            StaticTextStyle::from_style(text_style).component().apply(&mut text_widget);
+            text_widget.layout().add(constraints![
+                to_right_of(&box_widget).padding(self.size / 2.0),
+                bound_right(widget),
+                bound_top(widget),
+                bound_bottom(widget),
+                center_vertical(widget),
+            ]);
+            widget.add_child(box_widget);
+            widget.add_child(text_widget);
+        } else {
+            box_widget.layout().add(align_right(widget));
+            widget.add_child(box_widget);
+        }
+
+        let tri_state = self.tri_state;
+        widget.add_handler(move |_: &ClickEvent, mut args: EventArgs| {
+            if !args.widget.props().contains(&Property::Inactive) {
+                let state = CheckState::from_props(&args.widget.props());
+                args.widget.event(SetCheckState(state.next(tri_state)));
+                *args.handled = true;
+            }
+        });
+        widget.add_handler(|event: &SetCheckState, mut args: EventArgs| {
+            let SetCheckState(state) = *event;
+            if state != CheckState::from_props(&args.widget.props()) {
+                match state {
+                    CheckState::Unchecked => {
+                        args.widget.remove_prop(Property::Activated);
+                        args.widget.remove_prop(Property::Indeterminate);
+                    }
+                    CheckState::Checked => {
+                        args.widget.remove_prop(Property::Indeterminate);
+                        args.widget.add_prop(Property::Activated);
+                    }
+                    CheckState::Indeterminate => {
+                        args.widget.remove_prop(Property::Activated);
+                        args.widget.add_prop(Property::Indeterminate);
+                    }
+                }
+                args.widget.event(CheckStateChanged(state));
+            }
+        });
+    }
+}
+
+/// Draw style for the check mark of a `Checkbox` or `RadioButton`, transparent unless
+/// the widget has all of `props`
+pub(crate) fn check_draw_style(class: &str, props: PropSet, color: Color) -> DrawStyle {
+    let mut draw_style = DrawStyle::from(style!(RectStyle {
+        background_color: TRANSPARENT,
+    }));
+    draw_style.set_class(class);
+    draw_style.prop_style(props, style!(RectStyle {
+        background_color: color,
+    }));
+    draw_style
+}
+
+/// Emitted by a `RadioButton` when clicked, handled by the enclosing `RadioGroup`
+#[derive(Clone)]
+struct RadioButtonClicked(Widget);
+
+component_style!{pub struct RadioButton<name="radio_button", style=RadioButtonStyle> {
+    circle: EllipseStyle = EllipseStyle::default(),
+    dot_color: Color = BLACK,
+    size: f32 = 20.0,
+    text: Option<TextStyle> = None,
+}}
+
+impl RadioButtonStyle {
+    pub fn from_text(text: &str) -> Self {
+        Self {
+            text: Some(Some(TextStyle::from_text(text))),
+            ..Self::default()
+        }
+    }
+}
+
+impl WidgetModifier for RadioButton {
+    fn apply(&self, widget: &mut Widget) {
+        widget.enable_hover();
+        widget.layout().add(shrink());
+
+        let mut circle = Widget::new("radio_button_circle");
+        let mut draw_style = DrawStyle::from(self.circle.clone());
+        draw_style.set_class("radio_button_circle");
+        circle.set_draw_style(draw_style);
+        circle.layout().add(constraints![
+            size(Size::new(self.size, self.size)),
+            align_left(widget),
+            bound_top(widget),
+            bound_bottom(widget),
+            center_vertical(widget),
+        ]);
+
+        let mut dot = Widget::new("radio_button_dot");
+        let mut draw_style = DrawStyle::from(style!(EllipseStyle {
+            background_color: TRANSPARENT,
+        }));
+        draw_style.set_class("radio_button_dot");
+        draw_style.prop_style(ACTIVATED.clone(), style!(EllipseStyle {
+            background_color: self.dot_color,
+        }));
+        dot.set_draw_style(draw_style);
+        dot.layout().add(match_layout(&circle).padding(self.size / 4.0));
+        circle.add_child(dot);
+
+        if let Some(text_style) = self.text.clone() {
+            let mut text_widget = Widget::new("radio_button_text");
+            text_widget.set_draw_style(DrawStyle::from_class::<TextStyle>("radio_button_text"));
+            StaticTextStyle::from_style(text_style).component().apply(&mut text_widget);
+            text_widget.layout().add(constraints![
+                to_right_of(&circle).padding(self.size / 2.0),
+                bound_right(widget),
+                bound_top(widget),
+                bound_bottom(widget),
+                center_vertical(widget),
+            ]);
+            widget.add_child(circle);
+            widget.add_child(text_widget);
+        } else {
+            circle.layout().add(align_right(widget));
+            widget.add_child(circle);
+        }
+
+        widget.add_handler(|_: &ClickEvent, mut args: EventArgs| {
+            if !args.widget.props().contains(&Property::Inactive) {
+                args.widget.event_bubble_up(RadioButtonClicked(args.widget.clone()));
+                *args.handled = true;
+            }
+        });
+    }
+}
+
+/// Selects the child of a `RadioGroup` at the given index, or clears the selection if `None`
+#[derive(Debug, Copy, Clone)]
+pub struct SelectRadio(pub Option<usize>);
+
+/// Emitted by a `RadioGroup` when a different child is selected, contains the child's index
+#[derive(Debug, Copy, Clone)]
+pub struct RadioSelected(pub usize);
+
+/// Allows only one of it's children to be selected, the selected child has `Property::Activated`.
+/// Children are typically `RadioButton`s, or widgets that contain one.
+struct RadioGroupHandler;
+
+impl EventHandler<SelectRadio> for RadioGroupHandler {
+    fn handle(&mut self, event: &SelectRadio, args: EventArgs) {
+        let SelectRadio(selected) = *event;
+        let children = args.widget.children();
+        // found from the children rather than stored, so it's still correct after children are added or removed
+        let current = children.iter().position(|child| child.props().contains(&Property::Activated));
+        let new_selected = selected.and_then(|index| children.get(index).cloned());
+        if selected == current || (selected.is_some() && new_selected.is_none()) {
+            return;
+        }
+        if let Some(mut old_selected) = current.map(|index| children[index].clone()) {
+            old_selected.remove_prop(Property::Activated);
+        }
+        if let Some(mut new_selected) = new_selected {
+            new_selected.add_prop(Property::Activated);
+        }
+        if let Some(selected) = selected {
+            args.widget.event(RadioSelected(selected));
+        }
+    }
+}
+
+component_style!{pub struct RadioGroup<name="radio_group", style=RadioGroupStyle> {
+    layout_settings: LinearLayoutSettings = {
+        let mut layout_settings = LinearLayoutSettings::new(Orientation::Vertical);
+        layout_settings.item_align = ItemAlignment::Left;
+        layout_settings.padding = 5.0;
+        layout_settings
+    },
+}}
+
+impl WidgetModifier for RadioGroup {
+    fn apply(&self, widget: &mut Widget) {
+        widget
+            .add_handler(RadioGroupHandler)
+            .add_handler(|event: &RadioButtonClicked, mut args: EventArgs| {
+                // the clicked button might be nested inside one of the group's children
+                let mut clicked = Some(event.0.clone());
+                while let Some(widget) = clicked.clone() {
+                    let parent = widget.parent();
+                    if parent.as_ref() == Some(&args.widget) {
+                        break;
+                    }
+                    clicked = parent;
+                }
+                if let Some(clicked) = clicked {
+                    let index = args.widget.children().iter().position(|child| *child == clicked);
+                    args.widget.event(SelectRadio(index));
+                    *args.handled = true;
+                }
+            })
+            .linear_layout(self.layout_settings);
+    }
+}
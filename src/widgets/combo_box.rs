@@ -0,0 +1,282 @@
+use prelude::*;
+use draw::prelude::*;
+use input::keyboard::WidgetKeyboardInput;
+use widgets::text::StaticTextStyle;
+use widgets::edit_text::TextUpdated;
+use widgets::list::{self, ListItemSelected};
+
+/// Selects the item of a `ComboBox` at the given index
+#[derive(Debug, Copy, Clone)]
+pub struct SelectComboBoxItem(pub usize);
+
+/// Emitted by a `ComboBox` when a different item is selected, contains the item's index
+#[derive(Debug, Copy, Clone)]
+pub struct ComboBoxSelected(pub usize);
+
+/// Emitted by an item in the popup list when clicked
+#[derive(Debug, Copy, Clone)]
+struct ComboBoxItemClicked(usize);
+
+/// Closes the popup list without changing the selection
+#[derive(Debug, Copy, Clone)]
+struct CloseComboBox;
+
+multi_event!{impl EventHandler<ComboBoxEvent> for ComboBoxHandler {
+    ClickEvent => clicked,
+    WidgetKeyboardInput => key_input,
+    WidgetReceivedCharacter => received_char,
+    WidgetDetachedEvent => detached,
+    ComboBoxItemClicked => item_clicked,
+    CloseComboBox => close_popup,
+    SelectComboBoxItem => select_item,
+}}
+
+/// The popup list, and the transparent widget behind it that covers the window
+/// and closes the popup when clicked
+struct Popup {
+    backdrop: Widget,
+    list: Widget,
+}
+
+struct ComboBoxHandler {
+    items: Vec<String>,
+    selected: Option<usize>,
+    /// Item highlighted in the popup list with the arrow keys
+    highlighted: Option<usize>,
+    text_widget: Widget,
+    popup: Option<Popup>,
+    /// Characters typed so far, matched against the start of the items
+    typed: String,
+}
+
+impl ComboBoxHandler {
+    fn open(&mut self, args: &mut EventArgs) {
+        if self.popup.is_some() {
+            return;
+        }
+        let combo_box = args.widget.clone();
+        let mut backdrop = Widget::new("combo_box_backdrop");
+        {
+            let combo_box = combo_box.clone();
+            backdrop.add_handler(move |_: &ClickEvent, mut args: EventArgs| {
+                combo_box.event(CloseComboBox);
+                *args.handled = true;
+            });
+        }
+        let mut list_widget = Widget::from_modifier(List::default());
+        list::add_contents_to_list(&mut list_widget, self.items.clone().into_iter().enumerate(), |(index, text), list| {
+            let mut item = list::default_text_adapter(text, list);
+            let combo_box = combo_box.clone();
+            item.add_handler(move |_: &ClickEvent, mut args: EventArgs| {
+                combo_box.event(ComboBoxItemClicked(index));
+                *args.handled = true;
+            });
+            item
+        });
+        list_widget.layout().add(constraints![
+            shrink(),
+            match_width(&combo_box),
+        ]);
+        args.ui.add_popup(backdrop.clone(), OverlayAnchor::Fill);
+        args.ui.add_overlay(list_widget.clone(), OverlayAnchor::Widget(combo_box, Placement::Below));
+        self.popup = Some(Popup {
+            backdrop: backdrop,
+            list: list_widget,
+        });
+        self.typed.clear();
+        let selected = self.selected;
+        self.highlight(selected);
+    }
+
+    fn close(&mut self) {
+        if let Some(mut popup) = self.popup.take() {
+            popup.backdrop.remove_widget();
+            popup.list.remove_widget();
+        }
+        self.highlighted = None;
+        self.typed.clear();
+    }
+
+    fn highlight(&mut self, index: Option<usize>) {
+        self.highlighted = index;
+        if let Some(ref popup) = self.popup {
+            let item = index.and_then(|index| popup.list.children().get(index).cloned());
+            if let Some(mut item) = item.clone() {
+                item.add_prop(Property::Selected);
+            }
+            popup.list.event(ListItemSelected { widget: item });
+        }
+    }
+
+    fn select(&mut self, index: usize, args: &EventArgs) {
+        if index >= self.items.len() || Some(index) == self.selected {
+            return;
+        }
+        self.selected = Some(index);
+        self.text_widget.event(TextUpdated(self.items[index].clone()));
+        args.widget.event(ComboBoxSelected(index));
+    }
+
+    /// The item that is moved by the arrow keys, the highlighted item if the popup is open,
+    /// otherwise the selected item
+    fn current(&self) -> Option<usize> {
+        if self.popup.is_some() { self.highlighted } else { self.selected }
+    }
+
+    fn move_to(&mut self, index: usize, args: &EventArgs) {
+        if self.popup.is_some() {
+            self.highlight(Some(index));
+        } else {
+            self.select(index, args);
+        }
+    }
+
+    fn clicked(&mut self, _: &ClickEvent, mut args: EventArgs) {
+        if self.popup.is_some() {
+            self.close();
+        } else if !args.widget.props().contains(&Property::Inactive) {
+            self.open(&mut args);
+        }
+    }
+
+    fn key_input(&mut self, event: &WidgetKeyboardInput, mut args: EventArgs) {
+        let &WidgetKeyboardInput(input) = event;
+        if input.state != glutin::ElementState::Pressed || self.items.is_empty() {
+            return;
+        }
+        match input.virtual_keycode {
+            Some(glutin::VirtualKeyCode::Down) => {
+                let index = self.current().map_or(0, |index| index + 1);
+                if index < self.items.len() {
+                    self.move_to(index, &args);
+                }
+            }
+            Some(glutin::VirtualKeyCode::Up) => {
+                let index = self.current().map_or(0, |index| index.saturating_sub(1));
+                self.move_to(index, &args);
+            }
+            Some(glutin::VirtualKeyCode::Return) => {
+                if self.popup.is_some() {
+                    if let Some(index) = self.highlighted {
+                        self.select(index, &args);
+                    }
+                    self.close();
+                } else {
+                    self.open(&mut args);
+                }
+            }
+            Some(glutin::VirtualKeyCode::Escape) => self.close(),
+            _ => (),
+        }
+    }
+
+    fn received_char(&mut self, event: &WidgetReceivedCharacter, mut args: EventArgs) {
+        let &WidgetReceivedCharacter(char) = event;
+        if char.is_control() {
+            return;
+        }
+        if char == ' ' && self.typed.is_empty() {
+            if self.popup.is_none() {
+                self.open(&mut args);
+            }
+            return;
+        }
+        self.typed.extend(char.to_lowercase());
+        let mut found = self.find_typed();
+        if found.is_none() {
+            // start a new search from the last character
+            self.typed = char.to_lowercase().collect();
+            found = self.find_typed();
+        }
+        if let Some(index) = found {
+            self.move_to(index, &args);
+        }
+    }
+
+    fn find_typed(&self) -> Option<usize> {
+        let typed = &self.typed;
+        self.items.iter().position(|item| item.to_lowercase().starts_with(typed.as_str()))
+    }
+
+    fn detached(&mut self, _: &WidgetDetachedEvent, _: EventArgs) {
+        self.close();
+    }
+
+    fn item_clicked(&mut self, event: &ComboBoxItemClicked, args: EventArgs) {
+        self.select(event.0, &args);
+        self.close();
+    }
+
+    fn close_popup(&mut self, _: &CloseComboBox, _: EventArgs) {
+        self.close();
+    }
+
+    fn select_item(&mut self, event: &SelectComboBoxItem, args: EventArgs) {
+        self.select(event.0, &args);
+        if self.popup.is_some() {
+            self.highlight(Some(event.0));
+        }
+    }
+}
+
+component_style!{pub struct ComboBox<name="combo_box", style=ComboBoxStyle> {
+    rect: RectStyle = RectStyle::default(),
+    items: Vec<String> = Vec::new(),
+    selected: Option<usize> = None,
+}}
+
+impl ComboBoxStyle {
+    pub fn from_items<S: Into<String>, I: IntoIterator<Item = S>>(items: I) -> Self {
+        Self {
+            items: Some(items.into_iter().map(|item| item.into()).collect()),
+            ..Self::default()
+        }
+    }
+}
+
+impl WidgetModifier for ComboBox {
+    fn apply(&self, widget: &mut Widget) {
+        let selected = self.selected.and_then(|index| {
+            if index < self.items.len() { Some(index) } else { None }
+        });
+        let text = selected.map_or("", |index| self.items[index].as_str());
+        let mut text_widget = Widget::new("combo_box_text");
+        text_widget.set_draw_style(DrawStyle::from_class::<TextStyle>("combo_box_text"));
+        StaticTextStyle::from_text(text).component().apply(&mut text_widget);
+        text_widget.layout().add(constraints![
+            bound_left(widget).padding(10.0),
+            bound_right(widget).padding(10.0),
+            bound_top(widget).padding(5.0),
+            bound_bottom(widget).padding(5.0),
+            center_vertical(widget),
+            align_baseline(widget),
+        ]);
+
+        let mut draw_style = DrawStyle::from(self.rect.clone());
+        draw_style.set_class("combo_box_rect");
+        widget
+            .set_draw_style(draw_style)
+            .enable_hover()
+            .add_handler(|_: &WidgetAttachedEvent, args: EventArgs| {
+                args.ui.event(KeyboardInputEvent::AddFocusable(args.widget));
+            })
+            .add_handler(|_: &WidgetDetachedEvent, args: EventArgs| {
+                args.ui.event(KeyboardInputEvent::RemoveFocusable(args.widget));
+            })
+            .add_handler(ComboBoxHandler {
+                items: self.items.clone(),
+                selected: selected,
+                highlighted: None,
+                text_widget: text_widget.clone(),
+                popup: None,
+                typed: String::new(),
+            })
+            .make_focusable();
+        ComboBoxHandler::add_adapters(widget);
+        widget.layout().add(constraints![
+            min_size(Size::new(100.0, 30.0)),
+            shrink(),
+        ]);
+        widget.add_child(text_widget);
+    }
+}
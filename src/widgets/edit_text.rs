@@ -97,34 +97,42 @@ impl WidgetModifier for EditText {
             align_left(widget).padding(5.0),
             align_top(widget).padding(5.0),
             bound_by(widget).padding(5.0),
+            align_baseline(widget),
         ]);
         widget.add_child(text_widget);
     }
 }
 
-// Ensures the edit text is at least tall enough to fit the text. Width is unconstrained.
+// Ensures the edit text is at least tall enough to fit the text, and positions it's baseline.
+// Width is unconstrained.
 #[derive(Default)]
 struct TextHeightHandler {
     measured_height: f32,
+    measured_baseline: Option<f32>,
     size_constraints: Vec<Constraint>,
 }
 
 impl EventHandler<StyleUpdated> for TextHeightHandler {
     fn handle(&mut self, _: &StyleUpdated, mut args: EventArgs) {
-        let line_height = {
+        let (line_height, baseline) = {
             let draw_state = args.widget.draw_state();
             let text_draw_state = draw_state.downcast_ref::<TextState>().unwrap();
-            text_draw_state.line_height()
+            (text_draw_state.line_height(), text_draw_state.baseline())
         };
-        if self.measured_height != line_height {
-            let size_constraints = min_height(line_height).build(&args.widget.layout_vars());
+        if self.measured_height != line_height || self.measured_baseline != baseline {
             let mut layout = args.widget.layout();
+            let mut size_constraints = min_height(line_height).build(&layout.vars);
+            if let Some(baseline) = baseline {
+                let top = layout.vars.top;
+                size_constraints.push(layout.baseline() | EQ(REQUIRED) | top + baseline);
+            }
             for constraint in self.size_constraints.drain(..) {
                 layout.remove_constraint(constraint);
             }
             layout.add(size_constraints.clone());
             self.size_constraints = size_constraints;
             self.measured_height = line_height;
+            self.measured_baseline = baseline;
         }
     }
 }
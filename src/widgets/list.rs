@@ -1,6 +1,10 @@
+use std::cmp;
+use std::rc::Rc;
+
 use prelude::*;
 use draw::prelude::*;
 use widgets::text::StaticTextStyle;
+use widgets::edit_text::TextUpdated;
 
 pub struct ListItemSelected {
     pub widget: Option<Widget>,
@@ -71,12 +75,21 @@ pub fn add_contents_to_list<C, I, F>(list: &mut Widget, contents: C, build: F)
     where C: Iterator<Item=I>,
           F: Fn(I, &mut Widget) -> Widget,
 {
-    for item in contents {
+    let index = list.children().len();
+    insert_contents_into_list(list, index, contents, build);
+}
+
+/// Like `add_contents_to_list`, but inserts the items before the item currently at `index`
+pub fn insert_contents_into_list<C, I, F>(list: &mut Widget, index: usize, contents: C, build: F)
+    where C: Iterator<Item=I>,
+          F: Fn(I, &mut Widget) -> Widget,
+{
+    for (offset, item) in contents.enumerate() {
         let mut widget = build(item, list);
         widget
             .set_name("list_item")
             .add_handler(ListItemHandler::new(list.clone()));
-        list.add_child(widget);
+        list.insert_child(index + offset, widget);
     }
 }
 
@@ -94,3 +107,221 @@ pub fn default_text_adapter(text: String, list: &mut Widget) -> Widget {
     item_widget.add_child(text_widget);
     item_widget
 }
+
+/// Provides the items of a `VirtualList`, which only builds widgets for the visible items
+pub trait ListDataSource {
+    fn item_count(&self) -> usize;
+    /// Builds the widget for the item at `index`. While scrolling, the widget is reused
+    /// for other items with `update_item`
+    fn build_item(&self, index: usize, list: &mut Widget) -> Widget;
+    /// Updates a widget built by `build_item` to show the item at `index`
+    fn update_item(&self, index: usize, item: &mut Widget);
+}
+
+impl ListDataSource for Vec<String> {
+    fn item_count(&self) -> usize {
+        self.len()
+    }
+    fn build_item(&self, index: usize, list: &mut Widget) -> Widget {
+        default_text_adapter(self[index].clone(), list)
+    }
+    fn update_item(&self, index: usize, item: &mut Widget) {
+        if let Some(text) = item.child("list_item_text") {
+            text.event(TextUpdated(self[index].clone()));
+        }
+    }
+}
+
+/// Updates a `VirtualList` after it's data source has changed, resizing the list
+/// and updating the visible items
+#[derive(Debug, Copy, Clone)]
+pub struct RefreshList;
+
+/// Emitted by a `VirtualList` when an item is selected, contains the item's index in the data source
+#[derive(Debug, Copy, Clone)]
+pub struct VirtualListItemSelected(pub usize);
+
+/// Emitted by an item of a `VirtualList` when selected, handled by the enclosing `VirtualList`
+#[derive(Debug, Clone)]
+struct VirtualItemSelected(Widget);
+
+/// Emitted by the parent of a `VirtualList` when it's bounds change
+#[derive(Debug, Copy, Clone)]
+struct ViewportUpdated;
+
+/// A list for large data sets, meant to be the content of a `ScrollContainer`.
+/// The list has the height of all the items, but only the items visible inside the
+/// `ScrollContainer` have widgets, which are reused for other items while scrolling.
+/// All items have the same height.
+#[derive(Clone)]
+pub struct VirtualList {
+    source: Rc<ListDataSource>,
+    item_height: f32,
+}
+
+impl VirtualList {
+    pub fn new(source: Rc<ListDataSource>, item_height: f32) -> Self {
+        VirtualList {
+            source: source,
+            item_height: item_height,
+        }
+    }
+}
+
+impl Component for VirtualList {
+    fn name() -> String {
+        "virtual_list".to_owned()
+    }
+}
+
+multi_event!{impl EventHandler<VirtualListEvent> for VirtualListHandler {
+    LayoutUpdated => layout_updated,
+    ViewportUpdated => viewport_updated,
+    RefreshList => refresh,
+    VirtualItemSelected => item_selected,
+}}
+
+struct VirtualListHandler {
+    source: Rc<ListDataSource>,
+    item_height: f32,
+    item_count: usize,
+    /// Takes up the space of the items before the first visible item
+    spacer: Widget,
+    /// Widgets for the visible items, starting with the item at `first`
+    items: Vec<Widget>,
+    first: usize,
+    /// Whether the items need to be updated, even if the visible range hasn't changed
+    dirty: bool,
+    selected: Option<usize>,
+    /// The item widget currently showing the selected item
+    selected_widget: Option<Widget>,
+    /// Whether the parent forwards it's bounds changes
+    viewport_connected: bool,
+}
+
+impl VirtualListHandler {
+    fn update(&mut self, list: &Widget) {
+        let mut parent = match list.parent() {
+            Some(parent) => parent,
+            None => return,
+        };
+        if !self.viewport_connected {
+            // the list isn't resized when it's container is, so it needs to know
+            // when more items become visible
+            let list = list.clone();
+            parent.add_handler(move |_: &LayoutUpdated, _: EventArgs| {
+                list.event(ViewportUpdated);
+            });
+            self.viewport_connected = true;
+        }
+        let viewport = parent.bounds();
+        let bounds = list.bounds();
+        let visible_top = f32::max(0.0, viewport.top() - bounds.top());
+        let visible_len = (viewport.height() / self.item_height).ceil() as usize + 1;
+        let len = cmp::min(visible_len, self.item_count);
+        let first = cmp::min((visible_top / self.item_height) as usize, self.item_count - len);
+
+        if len < self.items.len() {
+            for mut item in self.items.split_off(len) {
+                item.remove_widget();
+            }
+        }
+        let mut list = list.clone();
+        while self.items.len() < len {
+            let mut item = self.source.build_item(first + self.items.len(), &mut list);
+            item.set_name("list_item")
+                .add_handler(ListItemHandler::new(list.clone()))
+                .add_handler(|_: &ItemSelected, args: EventArgs| {
+                    args.widget.event_bubble_up(VirtualItemSelected(args.widget.clone()));
+                });
+            item.layout().add(height(self.item_height));
+            list.add_child(item.clone());
+            self.items.push(item);
+        }
+        if first != self.first || self.dirty {
+            for (index, item) in self.items.iter_mut().enumerate() {
+                self.source.update_item(first + index, item);
+            }
+            self.spacer.layout().edit_height().set(first as f32 * self.item_height);
+            self.first = first;
+            self.dirty = false;
+        }
+        self.update_selection(&list);
+    }
+
+    /// Moves the selected property to the item widget showing the selected item, if it's visible
+    fn update_selection(&mut self, list: &Widget) {
+        let first = self.first;
+        let selected = self.selected.and_then(|selected| {
+            if selected >= first { self.items.get(selected - first).cloned() } else { None }
+        });
+        for item in &mut self.items {
+            let is_selected = Some(item.clone()) == selected;
+            if is_selected != item.props().contains(&Property::Selected) {
+                if is_selected {
+                    item.add_prop(Property::Selected);
+                } else {
+                    item.remove_prop(Property::Selected);
+                }
+            }
+        }
+        if selected != self.selected_widget {
+            self.selected_widget = selected.clone();
+            list.event(ListItemSelected { widget: selected });
+        }
+    }
+
+    fn layout_updated(&mut self, _: &LayoutUpdated, args: EventArgs) {
+        self.update(&args.widget);
+    }
+
+    fn viewport_updated(&mut self, _: &ViewportUpdated, args: EventArgs) {
+        self.update(&args.widget);
+    }
+
+    fn refresh(&mut self, _: &RefreshList, mut args: EventArgs) {
+        self.item_count = self.source.item_count();
+        args.widget.layout().edit_height().set(self.item_count as f32 * self.item_height);
+        if self.selected.map_or(false, |selected| selected >= self.item_count) {
+            self.selected = None;
+        }
+        self.dirty = true;
+        self.update(&args.widget);
+    }
+
+    fn item_selected(&mut self, event: &VirtualItemSelected, mut args: EventArgs) {
+        if let Some(position) = self.items.iter().position(|item| *item == event.0) {
+            let index = self.first + position;
+            self.selected = Some(index);
+            self.selected_widget = Some(event.0.clone());
+            args.widget.event(VirtualListItemSelected(index));
+            *args.handled = true;
+        }
+    }
+}
+
+impl WidgetModifier for VirtualList {
+    fn apply(&self, widget: &mut Widget) {
+        List::default().apply(widget);
+        let item_count = self.source.item_count();
+        widget.layout().edit_height().set(item_count as f32 * self.item_height);
+
+        let mut spacer = Widget::new("virtual_list_spacer");
+        spacer.layout().edit_height().set(0.0);
+        widget.add_child(spacer.clone());
+
+        widget.add_handler(VirtualListHandler {
+            source: self.source.clone(),
+            item_height: self.item_height,
+            item_count: item_count,
+            spacer: spacer,
+            items: Vec::new(),
+            first: 0,
+            dirty: true,
+            selected: None,
+            selected_widget: None,
+            viewport_connected: false,
+        });
+        VirtualListHandler::add_adapters(widget);
+    }
+}
@@ -0,0 +1,755 @@
+//! Context menus and menu bars.
+//!
+//! Both show a `Menu` in the overlay, with nested submenus opened by hovering over an item,
+//! or with the arrow keys. When an item is clicked, or selected with Return, the widget the menu
+//! belongs to emits a `MenuItemActivated` event.
+
+use prelude::*;
+use draw::prelude::*;
+use input::keyboard::WidgetKeyboardInput;
+use input::mouse::MouseOverEvent;
+use widgets::checkbox::check_draw_style;
+use widgets::text::StaticTextStyle;
+
+/// An item in a `Menu`
+#[derive(Debug, Clone)]
+pub struct MenuItem {
+    id: String,
+    label: String,
+    accelerator: Option<String>,
+    enabled: bool,
+    checked: Option<bool>,
+    submenu: Option<Menu>,
+}
+
+impl MenuItem {
+    /// An item with the given label, which is also it's id unless set with `id`
+    pub fn new(label: &str) -> Self {
+        MenuItem {
+            id: label.to_owned(),
+            label: label.to_owned(),
+            accelerator: None,
+            enabled: true,
+            checked: None,
+            submenu: None,
+        }
+    }
+    /// Identifies the item in `MenuItemActivated`, `SetMenuItemEnabled` and `SetMenuItemChecked`
+    pub fn id(mut self, id: &str) -> Self {
+        self.id = id.to_owned();
+        self
+    }
+    /// Text shown to the right of the label, eg. "Ctrl+S", the key combination isn't handled by the menu
+    pub fn accelerator(mut self, accelerator: &str) -> Self {
+        self.accelerator = Some(accelerator.to_owned());
+        self
+    }
+    /// The item has `Property::Inactive` and can't be activated
+    pub fn disabled(mut self) -> Self {
+        self.enabled = false;
+        self
+    }
+    /// The item is toggled between checked and unchecked when activated,
+    /// checked items have `Property::Activated`
+    pub fn checkable(mut self, checked: bool) -> Self {
+        self.checked = Some(checked);
+        self
+    }
+    /// Opens `submenu` instead of being activated
+    pub fn submenu(mut self, submenu: Menu) -> Self {
+        self.submenu = Some(submenu);
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum MenuEntry {
+    Item(MenuItem),
+    Separator,
+}
+
+/// The entries of a context menu, menu bar, or submenu
+#[derive(Debug, Clone, Default)]
+pub struct Menu {
+    entries: Vec<MenuEntry>,
+}
+
+impl Menu {
+    pub fn new() -> Self {
+        Menu::default()
+    }
+    pub fn item(mut self, item: MenuItem) -> Self {
+        self.entries.push(MenuEntry::Item(item));
+        self
+    }
+    pub fn separator(mut self) -> Self {
+        self.entries.push(MenuEntry::Separator);
+        self
+    }
+    /// The item at `path`, a list of indices into this menu and it's submenus
+    fn get_item(&self, path: &[usize]) -> Option<&MenuItem> {
+        match path.split_first() {
+            Some((&index, rest)) => match self.entries.get(index) {
+                Some(&MenuEntry::Item(ref item)) => {
+                    if rest.is_empty() {
+                        Some(item)
+                    } else {
+                        item.submenu.as_ref().and_then(|submenu| submenu.get_item(rest))
+                    }
+                }
+                _ => None,
+            },
+            None => None,
+        }
+    }
+    fn get_item_mut(&mut self, path: &[usize]) -> Option<&mut MenuItem> {
+        match path.split_first() {
+            Some((&index, rest)) => match self.entries.get_mut(index) {
+                Some(&mut MenuEntry::Item(ref mut item)) => {
+                    if rest.is_empty() {
+                        Some(item)
+                    } else {
+                        item.submenu.as_mut().and_then(|submenu| submenu.get_item_mut(rest))
+                    }
+                }
+                _ => None,
+            },
+            None => None,
+        }
+    }
+    /// The submenu of the item at `path`, or this menu if `path` is empty
+    fn submenu(&self, path: &[usize]) -> Option<&Menu> {
+        if path.is_empty() {
+            Some(self)
+        } else {
+            self.get_item(path).and_then(|item| item.submenu.as_ref())
+        }
+    }
+    /// The path of the item with the given id
+    fn find(&self, id: &str) -> Option<Vec<usize>> {
+        for (index, entry) in self.entries.iter().enumerate() {
+            if let MenuEntry::Item(ref item) = *entry {
+                if item.id == id {
+                    return Some(vec![index]);
+                }
+                if let Some(mut path) = item.submenu.as_ref().and_then(|submenu| submenu.find(id)) {
+                    path.insert(0, index);
+                    return Some(path);
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Emitted by the widget a menu belongs to when one of it's items is activated
+#[derive(Debug, Clone)]
+pub struct MenuItemActivated {
+    pub id: String,
+    /// The new state of a checkable item
+    pub checked: Option<bool>,
+}
+
+/// Enables or disables the menu item with the given id, the next time the menu is opened
+#[derive(Debug, Clone)]
+pub struct SetMenuItemEnabled(pub String, pub bool);
+
+/// Checks or unchecks the menu item with the given id, the next time the menu is opened
+#[derive(Debug, Clone)]
+pub struct SetMenuItemChecked(pub String, pub bool);
+
+#[derive(Debug, Clone)]
+struct MenuItemClicked(Vec<usize>);
+
+#[derive(Debug, Clone)]
+struct MenuItemHovered(Vec<usize>);
+
+#[derive(Debug, Copy, Clone)]
+struct OpenMenuBarMenu(usize);
+
+#[derive(Debug, Copy, Clone)]
+struct CloseMenu;
+
+/// Keyboard input received by the backdrop while the menu is open
+#[derive(Debug, Copy, Clone)]
+struct MenuKeyInput(glutin::KeyboardInput);
+
+/// Cursor moved over the backdrop while the menu is open
+#[derive(Debug, Copy, Clone)]
+struct MenuCursorMoved(Point);
+
+enum MenuKind {
+    /// Opened at the cursor by a right click
+    Context,
+    /// Opened by clicking one of the titles, the top level items of the menu
+    Bar {
+        titles: Vec<Widget>,
+        open: Option<usize>,
+    },
+}
+
+/// An open menu or submenu, `path` is the path of the item it's the submenu of
+struct MenuLevel {
+    path: Vec<usize>,
+    popup: Widget,
+}
+
+multi_event!{impl EventHandler<MenuEvent> for MenuHandler {
+    WidgetMouseButton => mouse_button,
+    WidgetMouseMoved => mouse_moved,
+    WidgetDetachedEvent => detached,
+    MenuItemClicked => item_clicked,
+    MenuItemHovered => item_hovered,
+    OpenMenuBarMenu => open_bar_menu_event,
+    CloseMenu => close_menu,
+    MenuKeyInput => key_input,
+    MenuCursorMoved => cursor_moved,
+    SetMenuItemEnabled => set_enabled,
+    SetMenuItemChecked => set_checked,
+}}
+
+struct MenuHandler {
+    menu: Menu,
+    kind: MenuKind,
+    /// Last position of the cursor over the widget, where a context menu is opened
+    cursor: Option<Point>,
+    /// Transparent widget behind the open menus, that closes them when clicked and receives keyboard input
+    backdrop: Option<Widget>,
+    levels: Vec<MenuLevel>,
+    highlighted: Option<Vec<usize>>,
+}
+
+impl MenuHandler {
+    fn new(menu: Menu, kind: MenuKind) -> Self {
+        MenuHandler {
+            menu: menu,
+            kind: kind,
+            cursor: None,
+            backdrop: None,
+            levels: Vec::new(),
+            highlighted: None,
+        }
+    }
+
+    fn open_backdrop(&mut self, owner: &Widget, ui: &mut Ui) {
+        if self.backdrop.is_some() {
+            return;
+        }
+        let mut backdrop = Widget::new("menu_backdrop");
+        {
+            let owner = owner.clone();
+            backdrop.add_handler(move |_: &ClickEvent, mut args: EventArgs| {
+                owner.event(CloseMenu);
+                *args.handled = true;
+            });
+        }
+        {
+            let owner = owner.clone();
+            backdrop.add_handler(move |event: &WidgetMouseButton, _: EventArgs| {
+                let &WidgetMouseButton(state, button) = event;
+                if state == glutin::ElementState::Pressed && button != glutin::MouseButton::Left {
+                    owner.event(CloseMenu);
+                }
+            });
+        }
+        {
+            let owner = owner.clone();
+            backdrop.add_handler(move |event: &WidgetMouseMoved, _: EventArgs| {
+                owner.event(MenuCursorMoved(event.0));
+            });
+        }
+        {
+            let owner = owner.clone();
+            backdrop.add_handler(move |event: &WidgetKeyboardInput, _: EventArgs| {
+                owner.event(MenuKeyInput(event.0));
+            });
+        }
+        ui.add_popup(backdrop.clone(), OverlayAnchor::Fill);
+        // keyboard input goes to the backdrop while the menu is open
+        ui.event(KeyboardInputEvent::PushFocusTrap(backdrop.clone()));
+        self.backdrop = Some(backdrop);
+    }
+
+    fn open_level(&mut self, path: Vec<usize>, anchor: OverlayAnchor, owner: &Widget, ui: &mut Ui) {
+        let popup = match self.menu.submenu(&path) {
+            Some(menu) => build_popup(menu, &path, owner),
+            None => return,
+        };
+        self.open_backdrop(owner, ui);
+        ui.add_overlay(popup.clone(), anchor);
+        self.levels.push(MenuLevel {
+            path: path,
+            popup: popup,
+        });
+    }
+
+    fn open_submenu(&mut self, path: &[usize], owner: &Widget, ui: &mut Ui) {
+        if let Some(item_widget) = self.item_widget(path) {
+            self.open_level(path.to_vec(), OverlayAnchor::Widget(item_widget, Placement::RightOf), owner, ui);
+        }
+    }
+
+    fn is_open(&self, path: &[usize]) -> bool {
+        self.levels.iter().any(|level| level.path[..] == *path)
+    }
+
+    /// Closes the submenus of the items in the menu at `path`
+    fn close_below(&mut self, path: &[usize]) {
+        while self.levels.last().map_or(false, |level| level.path.len() > path.len()) {
+            let mut level = self.levels.pop().unwrap();
+            level.popup.remove_widget();
+        }
+        let highlight_closed = self.highlighted.as_ref().map_or(false, |highlighted| {
+            !self.is_open(&highlighted[..highlighted.len() - 1])
+        });
+        if highlight_closed {
+            self.highlighted = None;
+        }
+    }
+
+    fn close(&mut self, ui: &mut Ui) {
+        for mut level in self.levels.drain(..) {
+            level.popup.remove_widget();
+        }
+        if let Some(mut backdrop) = self.backdrop.take() {
+            ui.event(KeyboardInputEvent::PopFocusTrap(backdrop.clone()));
+            backdrop.remove_widget();
+        }
+        self.highlighted = None;
+        if let MenuKind::Bar { ref mut titles, ref mut open } = self.kind {
+            if let Some(index) = open.take() {
+                titles[index].remove_prop(Property::Activated);
+            }
+        }
+    }
+
+    fn item_widget(&self, path: &[usize]) -> Option<Widget> {
+        match path.split_last() {
+            Some((&index, parent)) => {
+                self.levels.iter()
+                    .find(|level| level.path[..] == *parent)
+                    .and_then(|level| level.popup.children().get(index).cloned())
+            }
+            None => None,
+        }
+    }
+
+    fn highlight(&mut self, path: Option<Vec<usize>>) {
+        if path == self.highlighted {
+            return;
+        }
+        if let Some(mut old) = self.highlighted.take().and_then(|old| self.item_widget(&old)) {
+            old.remove_prop(Property::Selected);
+        }
+        if let Some(mut new) = path.as_ref().and_then(|new| self.item_widget(new)) {
+            new.add_prop(Property::Selected);
+        }
+        self.highlighted = path;
+    }
+
+    /// The next enabled item in the menu at `path` after `from`, or before it if not `forward`,
+    /// wrapping around. Starts at the first or last item if `from` is `None`
+    fn step(&self, path: &[usize], from: Option<usize>, forward: bool) -> Option<usize> {
+        let menu = match self.menu.submenu(path) {
+            Some(menu) => menu,
+            None => return None,
+        };
+        let len = menu.entries.len();
+        if len == 0 {
+            return None;
+        }
+        let start = from.unwrap_or(if forward { len - 1 } else { 0 });
+        (1..len + 1)
+            .map(|offset| if forward { (start + offset) % len } else { (start + len - offset % len) % len })
+            .find(|&index| match menu.entries[index] {
+                MenuEntry::Item(ref item) => item.enabled,
+                MenuEntry::Separator => false,
+            })
+    }
+
+    fn highlight_first(&mut self, path: &[usize]) {
+        if let Some(index) = self.step(path, None, true) {
+            let mut first = path.to_vec();
+            first.push(index);
+            self.highlight(Some(first));
+        }
+    }
+
+    fn activate(&mut self, path: &[usize], owner: &Widget, ui: &mut Ui) {
+        let (enabled, has_submenu) = match self.menu.get_item(path) {
+            Some(item) => (item.enabled, item.submenu.is_some()),
+            None => return,
+        };
+        if !enabled {
+            return;
+        }
+        if has_submenu {
+            if !self.is_open(path) {
+                self.close_below(&path[..path.len() - 1]);
+                self.open_submenu(path, owner, ui);
+            }
+            self.highlight_first(path);
+            return;
+        }
+        let event = {
+            let item = self.menu.get_item_mut(path).unwrap();
+            if let Some(ref mut checked) = item.checked {
+                *checked = !*checked;
+            }
+            MenuItemActivated {
+                id: item.id.clone(),
+                checked: item.checked,
+            }
+        };
+        self.close(ui);
+        owner.event(event);
+    }
+
+    fn open_bar_menu(&mut self, index: usize, owner: &Widget, ui: &mut Ui) {
+        let enabled = self.menu.get_item(&[index]).map_or(false, |item| item.enabled && item.submenu.is_some());
+        let title = match self.kind {
+            MenuKind::Bar { ref titles, open } if enabled && open != Some(index) => titles[index].clone(),
+            _ => return,
+        };
+        self.close_below(&[]);
+        if let MenuKind::Bar { ref mut titles, ref mut open } = self.kind {
+            if let Some(old) = open.take() {
+                titles[old].remove_prop(Property::Activated);
+            }
+            *open = Some(index);
+        }
+        let mut title = title;
+        title.add_prop(Property::Activated);
+        self.open_level(vec![index], OverlayAnchor::Widget(title, Placement::Below), owner, ui);
+    }
+
+    /// Opens the menu of the next or previous title of a menu bar
+    fn step_bar(&mut self, forward: bool, owner: &Widget, ui: &mut Ui) {
+        let open = match self.kind {
+            MenuKind::Bar { open, .. } => open,
+            MenuKind::Context => None,
+        };
+        if let Some(next) = self.step(&[], open, forward) {
+            self.open_bar_menu(next, owner, ui);
+            self.highlight_first(&[next]);
+        }
+    }
+
+    fn mouse_button(&mut self, event: &WidgetMouseButton, mut args: EventArgs) {
+        let &WidgetMouseButton(state, button) = event;
+        if let MenuKind::Context = self.kind {
+            if state == glutin::ElementState::Pressed && button == glutin::MouseButton::Right {
+                if let Some(cursor) = self.cursor {
+                    self.close(args.ui);
+                    self.open_level(Vec::new(), OverlayAnchor::Point(cursor), &args.widget, args.ui);
+                    // the innermost widget with a context menu opens it
+                    *args.handled = true;
+                }
+            }
+        }
+    }
+
+    fn mouse_moved(&mut self, event: &WidgetMouseMoved, _: EventArgs) {
+        self.cursor = Some(event.0);
+    }
+
+    fn detached(&mut self, _: &WidgetDetachedEvent, args: EventArgs) {
+        self.close(args.ui);
+    }
+
+    fn item_clicked(&mut self, event: &MenuItemClicked, args: EventArgs) {
+        self.activate(&event.0, &args.widget, args.ui);
+    }
+
+    fn item_hovered(&mut self, event: &MenuItemHovered, args: EventArgs) {
+        let path = event.0.clone();
+        let (enabled, has_submenu) = match self.menu.get_item(&path) {
+            Some(item) => (item.enabled, item.submenu.is_some()),
+            None => return,
+        };
+        if !self.is_open(&path[..path.len() - 1]) {
+            return;
+        }
+        if !self.is_open(&path) {
+            self.close_below(&path[..path.len() - 1]);
+            if enabled && has_submenu {
+                self.open_submenu(&path, &args.widget, args.ui);
+            }
+        }
+        self.highlight(if enabled { Some(path) } else { None });
+    }
+
+    fn open_bar_menu_event(&mut self, event: &OpenMenuBarMenu, args: EventArgs) {
+        let was_open = match self.kind {
+            MenuKind::Bar { open, .. } => open == Some(event.0),
+            MenuKind::Context => false,
+        };
+        if was_open {
+            self.close(args.ui);
+        } else {
+            self.open_bar_menu(event.0, &args.widget, args.ui);
+        }
+    }
+
+    fn close_menu(&mut self, _: &CloseMenu, args: EventArgs) {
+        self.close(args.ui);
+    }
+
+    fn key_input(&mut self, event: &MenuKeyInput, args: EventArgs) {
+        let MenuKeyInput(input) = *event;
+        if input.state != glutin::ElementState::Pressed || self.levels.is_empty() {
+            return;
+        }
+        let current = self.levels.last().unwrap().path.clone();
+        // the highlighted item, if it's in the innermost open menu
+        let highlighted = self.highlighted.as_ref().and_then(|path| {
+            if path[..path.len() - 1] == current[..] { path.last().cloned() } else { None }
+        });
+        let highlighted_path = highlighted.map(|index| {
+            let mut path = current.clone();
+            path.push(index);
+            path
+        });
+        match input.virtual_keycode {
+            Some(glutin::VirtualKeyCode::Down) | Some(glutin::VirtualKeyCode::Up) => {
+                let forward = input.virtual_keycode == Some(glutin::VirtualKeyCode::Down);
+                if let Some(index) = self.step(&current, highlighted, forward) {
+                    let mut path = current.clone();
+                    path.push(index);
+                    self.highlight(Some(path));
+                }
+            }
+            Some(glutin::VirtualKeyCode::Right) => {
+                let submenu = highlighted_path.and_then(|path| {
+                    if self.menu.get_item(&path).map_or(false, |item| item.submenu.is_some()) { Some(path) } else { None }
+                });
+                match submenu {
+                    Some(path) => self.activate(&path, &args.widget, args.ui),
+                    None => self.step_bar(true, &args.widget, args.ui),
+                }
+            }
+            Some(glutin::VirtualKeyCode::Left) => {
+                if self.levels.len() > 1 {
+                    let parent = self.levels[self.levels.len() - 2].path.clone();
+                    self.close_below(&parent);
+                    self.highlight(Some(current));
+                } else {
+                    self.step_bar(false, &args.widget, args.ui);
+                }
+            }
+            Some(glutin::VirtualKeyCode::Return) => {
+                if let Some(path) = highlighted_path {
+                    self.activate(&path, &args.widget, args.ui);
+                }
+            }
+            Some(glutin::VirtualKeyCode::Escape) => self.close(args.ui),
+            _ => (),
+        }
+    }
+
+    fn cursor_moved(&mut self, event: &MenuCursorMoved, args: EventArgs) {
+        // moving over another title of a menu bar while a menu is open switches to it's menu
+        let hovered = match self.kind {
+            MenuKind::Bar { ref titles, open: Some(_) } => {
+                titles.iter().position(|title| title.bounds().contains(&event.0))
+            }
+            _ => None,
+        };
+        if let Some(index) = hovered {
+            self.open_bar_menu(index, &args.widget, args.ui);
+        }
+    }
+
+    fn set_enabled(&mut self, event: &SetMenuItemEnabled, _: EventArgs) {
+        if let Some(path) = self.menu.find(&event.0) {
+            self.menu.get_item_mut(&path).unwrap().enabled = event.1;
+        }
+    }
+
+    fn set_checked(&mut self, event: &SetMenuItemChecked, _: EventArgs) {
+        if let Some(path) = self.menu.find(&event.0) {
+            self.menu.get_item_mut(&path).unwrap().checked = Some(event.1);
+        }
+    }
+}
+
+/// Builds the popup for `menu`, which is the submenu at `path`, with a child for each entry
+fn build_popup(menu: &Menu, path: &[usize], owner: &Widget) -> Widget {
+    let mut popup = Widget::new("menu");
+    popup.set_draw_style(DrawStyle::from_class::<RectStyle>("menu_rect"));
+    let mut layout_settings = LinearLayoutSettings::new(Orientation::Vertical);
+    layout_settings.item_align = ItemAlignment::Fill;
+    popup.linear_layout(layout_settings);
+    popup.layout().add(shrink());
+    for (index, entry) in menu.entries.iter().enumerate() {
+        let mut item_path = path.to_vec();
+        item_path.push(index);
+        let child = match *entry {
+            MenuEntry::Item(ref item) => build_item(item, item_path, owner),
+            MenuEntry::Separator => build_separator(),
+        };
+        popup.add_child(child);
+    }
+    popup
+}
+
+fn build_item(item: &MenuItem, path: Vec<usize>, owner: &Widget) -> Widget {
+    let mut item_widget = Widget::new("menu_item");
+    item_widget.set_draw_style(DrawStyle::from_class::<RectStyle>("menu_item_rect"));
+
+    // the space for the check mark is reserved even if the item isn't checkable, so the labels line up
+    let mut check = Widget::new("menu_item_check");
+    check.set_draw_style(check_draw_style("menu_item_check", ACTIVATED.clone(), BLACK));
+    check.layout().add(constraints![
+        size(Size::new(8.0, 8.0)),
+        align_left(&item_widget).padding(8.0),
+        center_vertical(&item_widget),
+    ]);
+
+    let mut label = Widget::new("menu_item_text");
+    label.set_draw_style(DrawStyle::from_class::<TextStyle>("menu_item_text"));
+    StaticTextStyle::from_text(&item.label).component().apply(&mut label);
+    label.layout().add(constraints![
+        to_right_of(&check).padding(8.0),
+        bound_top(&item_widget).padding(4.0),
+        bound_bottom(&item_widget).padding(4.0),
+    ]);
+
+    // accelerator, or an arrow for items with a submenu, aligned to the right edge
+    let detail = if item.submenu.is_some() {
+        Some((">", "menu_item_arrow"))
+    } else {
+        item.accelerator.as_ref().map(|accelerator| (accelerator.as_str(), "menu_item_accelerator"))
+    };
+    let detail = detail.map(|(text, class)| {
+        let mut detail = Widget::new(class);
+        detail.set_draw_style(DrawStyle::from_class::<TextStyle>(class));
+        StaticTextStyle::from_text(text).component().apply(&mut detail);
+        detail.layout().add(constraints![
+            to_right_of(&label).padding(30.0),
+            align_right(&item_widget).padding(10.0),
+            align_baseline(&label),
+        ]);
+        detail
+    });
+    if detail.is_none() {
+        label.layout().add(bound_right(&item_widget).padding(10.0));
+    }
+
+    {
+        let owner = owner.clone();
+        let path = path.clone();
+        item_widget.add_handler(move |_: &ClickEvent, mut args: EventArgs| {
+            owner.event(MenuItemClicked(path.clone()));
+            *args.handled = true;
+        });
+    }
+    {
+        let owner = owner.clone();
+        item_widget.add_handler(move |event: &MouseOverEvent, _: EventArgs| {
+            if let MouseOverEvent::Over = *event {
+                owner.event(MenuItemHovered(path.clone()));
+            }
+        });
+    }
+    item_widget.add_child(check);
+    item_widget.add_child(label);
+    if let Some(detail) = detail {
+        item_widget.add_child(detail);
+    }
+    if !item.enabled {
+        item_widget.add_prop(Property::Inactive);
+    }
+    if item.checked == Some(true) {
+        item_widget.add_prop(Property::Activated);
+    }
+    item_widget
+}
+
+fn build_separator() -> Widget {
+    let mut separator = Widget::new("menu_separator");
+    separator.layout().add(height(9.0));
+    let mut line = Widget::new("menu_separator_line");
+    line.set_draw_style(DrawStyle::from_class::<RectStyle>("menu_separator"));
+    line.layout().add(constraints![
+        height(1.0),
+        match_width(&separator).padding(5.0),
+        center(&separator),
+    ]);
+    separator.add_child(line);
+    separator
+}
+
+/// Adds context menus to a `Widget`
+pub trait ContextMenuExt {
+    /// Opens `menu` at the cursor when the widget is right clicked.
+    /// The widget emits `MenuItemActivated` when an item is activated.
+    fn set_context_menu(&mut self, menu: Menu) -> &mut Self;
+}
+
+impl ContextMenuExt for Widget {
+    fn set_context_menu(&mut self, menu: Menu) -> &mut Self {
+        self.add_handler(MenuHandler::new(menu, MenuKind::Context));
+        MenuHandler::add_adapters(self);
+        self
+    }
+}
+
+component_style!{pub struct MenuBar<name="menu_bar", style=MenuBarStyle> {
+    menu: Menu = Menu::default(),
+}}
+
+impl MenuBarStyle {
+    /// The items of `menu` are the titles of the menu bar, each opens it's submenu
+    pub fn from_menu(menu: Menu) -> Self {
+        MenuBarStyle {
+            menu: Some(menu),
+        }
+    }
+}
+
+impl WidgetModifier for MenuBar {
+    fn apply(&self, widget: &mut Widget) {
+        widget.set_draw_style(DrawStyle::from_class::<RectStyle>("menu_bar_rect"));
+        widget.linear_layout(LinearLayoutSettings::new(Orientation::Horizontal));
+        widget.layout().add(shrink_vertical());
+        let mut titles = Vec::new();
+        for (index, entry) in self.menu.entries.iter().enumerate() {
+            let mut title = Widget::new("menu_bar_item");
+            match *entry {
+                MenuEntry::Item(ref item) => {
+                    title.set_draw_style(DrawStyle::from_class::<RectStyle>("menu_bar_item_rect"));
+                    title.enable_hover();
+                    let mut text = Widget::new("menu_bar_item_text");
+                    text.set_draw_style(DrawStyle::from_class::<TextStyle>("menu_bar_item_text"));
+                    StaticTextStyle::from_text(&item.label).component().apply(&mut text);
+                    text.layout().add(constraints![
+                        bound_left(&title).padding(10.0),
+                        bound_right(&title).padding(10.0),
+                        bound_top(&title).padding(4.0),
+                        bound_bottom(&title).padding(4.0),
+                    ]);
+                    title.add_child(text);
+                    let bar = widget.clone();
+                    title.add_handler(move |_: &ClickEvent, mut args: EventArgs| {
+                        bar.event(OpenMenuBarMenu(index));
+                        *args.handled = true;
+                    });
+                    if !item.enabled {
+                        title.add_prop(Property::Inactive);
+                    }
+                }
+                MenuEntry::Separator => {
+                    title.layout().add(width(10.0));
+                }
+            }
+            titles.push(title.clone());
+            widget.add_child(title);
+        }
+        widget.add_handler(MenuHandler::new(self.menu.clone(), MenuKind::Bar {
+            titles: titles,
+            open: None,
+        }));
+        MenuHandler::add_adapters(widget);
+    }
+}
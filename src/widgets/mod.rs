@@ -1,21 +1,39 @@
 //! Includes standard bundled widgets.
 
 pub mod button;
+pub mod checkbox;
+pub mod combo_box;
+pub mod menu;
 pub mod scroll;
 pub mod list;
 pub mod slider;
+pub mod tab_view;
+pub mod table;
 pub mod edit_text;
 pub mod image;
 pub mod glcanvas;
 pub mod text;
+pub mod tree_view;
+pub mod tooltip;
 
 pub mod prelude {
-    pub use super::text::StaticTextStyle;
+    pub use super::text::{StaticTextStyle, LinkClicked};
     pub use super::button::{ButtonStyle, ToggleButtonStyle, ToggleEvent};
+    pub use super::checkbox::{CheckboxStyle, CheckState, SetCheckState, CheckStateChanged};
+    pub use super::checkbox::{RadioButtonStyle, RadioGroupStyle, SelectRadio, RadioSelected};
+    pub use super::combo_box::{ComboBoxStyle, SelectComboBoxItem, ComboBoxSelected};
+    pub use super::menu::{Menu, MenuItem, MenuBarStyle, ContextMenuExt};
+    pub use super::menu::{MenuItemActivated, SetMenuItemEnabled, SetMenuItemChecked};
     pub use super::edit_text::{EditText, TextUpdated};
+    pub use super::tab_view::{TabViewStyle, AddTab, RemoveTab, SelectTab, TabSelected, TabClosed, TabMoved};
     pub use super::slider::{Slider, SetSliderValue, SliderEvent};
     pub use super::list::{List, ListItemSelected, ItemSelected, ListItemHandler};
+    pub use super::list::{VirtualList, ListDataSource, RefreshList, VirtualListItemSelected};
+    pub use super::table::{Table, TableColumn, SortTable, TableSorted, TableRowSelected, ColumnResized, ColumnMoved};
+    pub use super::tree_view::{TreeViewStyle, TreeNode, ExpandTreeNode, CollapseTreeNode, SetTreeNodeChildren};
+    pub use super::tree_view::{TreeNodeExpanded, TreeNodeCollapsed, LoadTreeNodeChildren, TreeNodeSelected, TreeNodeDeselected, TreeNodeActivated};
     pub use super::scroll::ScrollContainer;
     pub use super::image::Image;
     pub use super::glcanvas::{GLCanvasBuilder, GLCanvasState};
+    pub use super::tooltip::{Tooltip, TooltipExt};
 }
@@ -0,0 +1,355 @@
+use prelude::*;
+use draw::prelude::*;
+use input::keyboard::WidgetKeyboardInput;
+use input::drag::drop_target;
+use widgets::text::StaticTextStyle;
+use widgets::edit_text::TextUpdated;
+
+/// Adds a page to a `TabView`, after the existing pages, with a tab showing the given title
+#[derive(Debug, Clone)]
+pub struct AddTab(pub String, pub Widget);
+
+/// Removes the page of a `TabView` at the given index
+#[derive(Debug, Copy, Clone)]
+pub struct RemoveTab(pub usize);
+
+/// Shows the page of a `TabView` at the given index
+#[derive(Debug, Copy, Clone)]
+pub struct SelectTab(pub usize);
+
+/// Emitted by a `TabView` when a different page is shown, contains the page's index
+#[derive(Debug, Copy, Clone)]
+pub struct TabSelected(pub usize);
+
+/// Emitted by a `TabView` when a page is removed, by `RemoveTab` or by it's close button
+#[derive(Debug, Copy, Clone)]
+pub struct TabClosed(pub usize);
+
+/// Emitted by a `TabView` when a tab is dragged to a different position
+#[derive(Debug, Copy, Clone)]
+pub struct TabMoved {
+    pub from: usize,
+    pub to: usize,
+}
+
+/// Emitted by a tab when clicked, handled by the enclosing `TabView`
+#[derive(Debug, Clone)]
+struct TabClicked(Widget);
+
+/// Emitted by the close button of a tab when clicked
+#[derive(Debug, Clone)]
+struct TabCloseClicked(Widget);
+
+#[derive(Debug, Clone)]
+struct TabDragged(Widget, DragEvent);
+
+multi_event!{impl EventHandler<TabViewEvent> for TabViewHandler {
+    AddTab => add_tab,
+    RemoveTab => remove_tab,
+    SelectTab => select_tab,
+    TabClicked => tab_clicked,
+    TabCloseClicked => close_clicked,
+    TabDragged => tab_dragged,
+    WidgetKeyboardInput => key_input,
+}}
+
+struct Page {
+    title: String,
+    widget: Widget,
+}
+
+/// A tab in the strip, which shows the title of the page at the same index.
+/// Tabs stay in place when pages are reordered, only their titles are updated.
+struct Tab {
+    widget: Widget,
+    text: Widget,
+}
+
+struct TabViewHandler {
+    pages: Vec<Page>,
+    tabs: Vec<Tab>,
+    selected: Option<usize>,
+    strip: Widget,
+    content: Widget,
+    closable: bool,
+    reorderable: bool,
+    /// Index of the page whose tab is being dragged
+    dragging: Option<usize>,
+}
+
+impl TabViewHandler {
+    fn build_tab(&self, title: &str) -> Tab {
+        let mut tab = Widget::new("tab_view_tab");
+        tab.set_draw_style(DrawStyle::from_class::<RectStyle>("tab_view_tab_rect"));
+        tab.enable_hover();
+
+        let mut text = Widget::new("tab_view_tab_text");
+        text.set_draw_style(DrawStyle::from_class::<TextStyle>("tab_view_tab_text"));
+        StaticTextStyle::from_text(title).component().apply(&mut text);
+        text.layout().add(constraints![
+            bound_left(&tab).padding(10.0),
+            bound_top(&tab).padding(5.0),
+            bound_bottom(&tab).padding(5.0),
+        ]);
+
+        if self.closable {
+            let mut close = Widget::new("tab_view_tab_close");
+            close.set_draw_style(DrawStyle::from_class::<TextStyle>("tab_view_tab_close"));
+            StaticTextStyle::from_text("x").component().apply(&mut close);
+            close.enable_hover();
+            close.layout().add(constraints![
+                to_right_of(&text).padding(8.0),
+                bound_right(&tab).padding(8.0),
+                align_baseline(&text),
+            ]);
+            close.add_handler(|_: &ClickEvent, mut args: EventArgs| {
+                if let Some(tab) = args.widget.parent() {
+                    args.widget.event_bubble_up(TabCloseClicked(tab));
+                }
+                // don't select the tab that's being closed
+                *args.handled = true;
+            });
+            tab.add_child(text.clone());
+            tab.add_child(close);
+        } else {
+            text.layout().add(bound_right(&tab).padding(10.0));
+            tab.add_child(text.clone());
+        }
+
+        tab.add_handler(|_: &ClickEvent, args: EventArgs| {
+            args.widget.event_bubble_up(TabClicked(args.widget.clone()));
+        });
+        if self.reorderable {
+            tab.make_draggable();
+            tab.add_handler(|event: &DragEvent, args: EventArgs| {
+                args.widget.event_bubble_up(TabDragged(args.widget.clone(), *event));
+            });
+        }
+        Tab {
+            widget: tab,
+            text: text,
+        }
+    }
+
+    fn tab_index(&self, tab: &Widget) -> Option<usize> {
+        self.tabs.iter().position(|other| other.widget == *tab)
+    }
+
+    /// Updates the tabs to match the pages and the selection, and shows the selected page
+    fn update(&mut self) {
+        for (index, (tab, page)) in self.tabs.iter_mut().zip(self.pages.iter()).enumerate() {
+            tab.text.event(TextUpdated(page.title.clone()));
+            let selected = Some(index) == self.selected;
+            if selected != tab.widget.props().contains(&Property::Activated) {
+                if selected {
+                    tab.widget.add_prop(Property::Activated);
+                } else {
+                    tab.widget.remove_prop(Property::Activated);
+                }
+            }
+        }
+        let content = self.content.clone();
+        let page = self.selected.and_then(|index| {
+            content.children().iter().position(|child| *child == self.pages[index].widget)
+        });
+        self.content.event(StackSelect(page));
+    }
+
+    fn select(&mut self, index: Option<usize>, args: &EventArgs) {
+        let index = index.and_then(|index| if index < self.pages.len() { Some(index) } else { None });
+        if index == self.selected {
+            return;
+        }
+        self.selected = index;
+        self.update();
+        if let Some(index) = index {
+            args.widget.event(TabSelected(index));
+        }
+    }
+
+    fn remove(&mut self, index: usize, args: &EventArgs) {
+        if index >= self.pages.len() {
+            return;
+        }
+        let mut page = self.pages.remove(index);
+        page.widget.remove_widget();
+        let mut tab = self.tabs.pop().unwrap();
+        tab.widget.remove_widget();
+        args.widget.event(TabClosed(index));
+        match self.selected {
+            Some(selected) if selected > index => {
+                // same page, at a new index
+                self.selected = Some(selected - 1);
+                self.update();
+            }
+            Some(selected) if selected == index => {
+                // select the page that took it's place, or the new last page
+                self.selected = None;
+                let next = if index < self.pages.len() { index } else { self.pages.len().wrapping_sub(1) };
+                self.select(Some(next), args);
+                if self.selected.is_none() {
+                    self.update();
+                }
+            }
+            _ => self.update(),
+        }
+    }
+
+    fn move_page(&mut self, from: usize, to: usize, args: &EventArgs) {
+        let page = self.pages.remove(from);
+        self.pages.insert(to, page);
+        self.selected = self.selected.map(|selected| {
+            if selected == from {
+                to
+            } else if from < selected && selected <= to {
+                selected - 1
+            } else if to <= selected && selected < from {
+                selected + 1
+            } else {
+                selected
+            }
+        });
+        self.update();
+        args.widget.event(TabMoved { from: from, to: to });
+    }
+
+    fn add_tab(&mut self, event: &AddTab, args: EventArgs) {
+        let AddTab(ref title, ref page) = *event;
+        let tab = self.build_tab(title);
+        self.strip.add_child(tab.widget.clone());
+        self.content.add_child(page.clone());
+        self.tabs.push(tab);
+        self.pages.push(Page {
+            title: title.clone(),
+            widget: page.clone(),
+        });
+        if self.selected.is_none() {
+            self.select(Some(0), &args);
+        }
+    }
+
+    fn remove_tab(&mut self, event: &RemoveTab, args: EventArgs) {
+        self.remove(event.0, &args);
+    }
+
+    fn select_tab(&mut self, event: &SelectTab, args: EventArgs) {
+        self.select(Some(event.0), &args);
+    }
+
+    fn tab_clicked(&mut self, event: &TabClicked, mut args: EventArgs) {
+        if let Some(index) = self.tab_index(&event.0) {
+            self.select(Some(index), &args);
+            *args.handled = true;
+        }
+    }
+
+    fn close_clicked(&mut self, event: &TabCloseClicked, mut args: EventArgs) {
+        if let Some(index) = self.tab_index(&event.0) {
+            self.remove(index, &args);
+            *args.handled = true;
+        }
+    }
+
+    fn tab_dragged(&mut self, event: &TabDragged, mut args: EventArgs) {
+        let TabDragged(ref tab, drag) = *event;
+        match drag.state {
+            DragState::Start => self.dragging = self.tab_index(tab),
+            DragState::Moved => {
+                if let Some(dragging) = self.dragging {
+                    let target = {
+                        let bounds = self.tabs.iter().map(|tab| tab.widget.bounds());
+                        drop_target(bounds, dragging, drag.position.x)
+                    };
+                    if let Some(target) = target {
+                        self.move_page(dragging, target, &args);
+                        self.dragging = Some(target);
+                    }
+                }
+            }
+            DragState::End => self.dragging = None,
+        }
+        *args.handled = true;
+    }
+
+    fn key_input(&mut self, event: &WidgetKeyboardInput, mut args: EventArgs) {
+        let &WidgetKeyboardInput(input) = event;
+        if input.state != glutin::ElementState::Pressed || !input.modifiers.ctrl || self.pages.is_empty() {
+            return;
+        }
+        let forward = match input.virtual_keycode {
+            Some(glutin::VirtualKeyCode::Tab) => !input.modifiers.shift,
+            Some(glutin::VirtualKeyCode::PageDown) => true,
+            Some(glutin::VirtualKeyCode::PageUp) => false,
+            _ => return,
+        };
+        let len = self.pages.len();
+        let next = match self.selected {
+            Some(selected) => if forward { (selected + 1) % len } else { (selected + len - 1) % len },
+            None => 0,
+        };
+        self.select(Some(next), &args);
+        // nested tab views don't also switch
+        *args.handled = true;
+    }
+}
+
+component_style!{pub struct TabView<name="tab_view", style=TabViewStyle> {
+    closable: bool = false,
+    reorderable: bool = true,
+}}
+
+impl TabViewStyle {
+    /// Tabs have a close button, that removes the page and emits `TabClosed`
+    pub fn closable(mut self) -> Self {
+        self.closable = Some(true);
+        self
+    }
+}
+
+impl WidgetModifier for TabView {
+    fn apply(&self, widget: &mut Widget) {
+        let mut strip = Widget::new("tab_view_strip");
+        strip.set_draw_style(DrawStyle::from_class::<RectStyle>("tab_view_strip_rect"));
+        strip.linear_layout(LinearLayoutSettings::new(Orientation::Horizontal));
+        strip
+            .add_handler(|_: &WidgetAttachedEvent, args: EventArgs| {
+                args.ui.event(KeyboardInputEvent::AddFocusable(args.widget));
+            })
+            .add_handler(|_: &WidgetDetachedEvent, args: EventArgs| {
+                args.ui.event(KeyboardInputEvent::RemoveFocusable(args.widget));
+            })
+            .make_focusable();
+        strip.layout().add(constraints![
+            align_top(widget),
+            align_left(widget),
+            align_right(widget),
+            min_height(30.0),
+            shrink_vertical(),
+        ]);
+
+        // only the selected page is shown, the rest are hidden and don't take part in layout
+        let mut content = Widget::new("tab_view_content");
+        content.set_draw_style(DrawStyle::from_class::<RectStyle>("tab_view_content_rect"));
+        content.stack_layout();
+        content.layout().add(constraints![
+            below(&strip),
+            align_left(widget),
+            align_right(widget),
+            align_bottom(widget),
+        ]);
+
+        widget.add_handler(TabViewHandler {
+            pages: Vec::new(),
+            tabs: Vec::new(),
+            selected: None,
+            strip: strip.clone(),
+            content: content.clone(),
+            closable: self.closable,
+            reorderable: self.reorderable,
+            dragging: None,
+        });
+        TabViewHandler::add_adapters(widget);
+        widget.add_child(strip);
+        widget.add_child(content);
+    }
+}
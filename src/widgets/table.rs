@@ -0,0 +1,487 @@
+use std::cmp::{self, Ordering};
+use std::rc::Rc;
+
+use prelude::*;
+use draw::prelude::*;
+use widgets::text::StaticTextStyle;
+use widgets::edit_text::TextUpdated;
+use widgets::list::{self, ListItemSelected, ItemSelected};
+use input::drag::drop_target;
+
+/// Sorts the rows of a `Table` by a column, if the column is sortable
+#[derive(Debug, Copy, Clone)]
+pub struct SortTable {
+    pub column: usize,
+    pub ascending: bool,
+}
+
+/// Emitted by a `Table` when it's rows are sorted
+#[derive(Debug, Copy, Clone)]
+pub struct TableSorted {
+    pub column: usize,
+    pub ascending: bool,
+}
+
+/// Emitted by a `Table` when a row is selected, contains the index of the row in the order
+/// the rows were added, regardless of sorting
+#[derive(Debug, Copy, Clone)]
+pub struct TableRowSelected(pub usize);
+
+/// Emitted by a `Table` when a column is resized by dragging the edge of it's header
+#[derive(Debug, Copy, Clone)]
+pub struct ColumnResized {
+    pub column: usize,
+    pub width: f32,
+}
+
+/// Emitted by a `Table` when a column is dragged to a different position
+#[derive(Debug, Copy, Clone)]
+pub struct ColumnMoved {
+    pub from: usize,
+    pub to: usize,
+}
+
+/// A column of a `Table`, with a function that builds the cell for each row
+pub struct TableColumn<T> {
+    title: String,
+    width: f32,
+    build_cell: Rc<Fn(&T) -> Widget>,
+    compare: Option<Rc<Fn(&T, &T) -> Ordering>>,
+}
+
+impl<T> Clone for TableColumn<T> {
+    fn clone(&self) -> Self {
+        TableColumn {
+            title: self.title.clone(),
+            width: self.width,
+            build_cell: self.build_cell.clone(),
+            compare: self.compare.clone(),
+        }
+    }
+}
+
+impl<T: 'static> TableColumn<T> {
+    /// A column whose cells are built by `build_cell`, like the `build` closure of `add_contents_to_list`
+    pub fn new<F: Fn(&T) -> Widget + 'static>(title: &str, build_cell: F) -> Self {
+        TableColumn {
+            title: title.to_owned(),
+            width: 100.0,
+            build_cell: Rc::new(build_cell),
+            compare: None,
+        }
+    }
+    /// A column of text cells, sortable by the text
+    pub fn text<F: Fn(&T) -> String + 'static>(title: &str, text: F) -> Self {
+        let text = Rc::new(text);
+        let cell_text = text.clone();
+        TableColumn::new(title, move |row| text_cell(&cell_text(row)))
+            .sort_by(move |a, b| text(a).cmp(&text(b)))
+    }
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+    /// Clicking the header sorts the rows with `compare`, clicking it again reverses the order
+    pub fn sort_by<F: Fn(&T, &T) -> Ordering + 'static>(mut self, compare: F) -> Self {
+        self.compare = Some(Rc::new(compare));
+        self
+    }
+}
+
+/// A cell showing `text`, styled with the "table_cell_text" class
+pub fn text_cell(text: &str) -> Widget {
+    let mut text_widget = Widget::new("table_cell_text");
+    text_widget.set_draw_style(DrawStyle::from_class::<TextStyle>("table_cell_text"));
+    StaticTextStyle::from_text(text).component().apply(&mut text_widget);
+    text_widget
+}
+
+/// Rows of cells under a header row. Columns are resized by dragging the right edge
+/// of their header, reordered by dragging the header, and sorted by clicking it.
+pub struct Table<T> {
+    columns: Vec<TableColumn<T>>,
+    rows: Vec<Rc<T>>,
+}
+
+impl<T: 'static> Table<T> {
+    pub fn new() -> Self {
+        Table {
+            columns: Vec::new(),
+            rows: Vec::new(),
+        }
+    }
+    pub fn add_column(&mut self, column: TableColumn<T>) -> &mut Self {
+        self.columns.push(column);
+        self
+    }
+    pub fn add_rows<I: IntoIterator<Item = T>>(&mut self, rows: I) -> &mut Self {
+        self.rows.extend(rows.into_iter().map(Rc::new));
+        self
+    }
+}
+
+impl<T> Clone for Table<T> {
+    fn clone(&self) -> Self {
+        Table {
+            columns: self.columns.clone(),
+            rows: self.rows.clone(),
+        }
+    }
+}
+
+impl<T> Component for Table<T> {
+    fn name() -> String {
+        "table".to_owned()
+    }
+}
+
+enum TableEvent {
+    Sort(usize, bool),
+    HeaderClicked(usize),
+    HeaderDragged(usize, DragEvent),
+    ResizeDragged(usize, DragEvent),
+    RowSelected(usize),
+}
+
+/// A position in the header row, which shows the column at the same position in `TableHandler::order`
+struct Header {
+    widget: Widget,
+    text: Widget,
+    sort_indicator: Widget,
+}
+
+/// A cell of a shown row, aligned with the header at the same position
+struct Cell {
+    widget: Widget,
+    /// Aligns the cell with it's header, replaced when the cell's column moves
+    header_constraints: Vec<Constraint>,
+}
+
+impl Cell {
+    fn align_to(&mut self, header: &Header) {
+        let mut layout = self.widget.layout();
+        for constraint in self.header_constraints.drain(..) {
+            layout.remove_constraint(constraint);
+        }
+        let mut header_constraints = align_left(&header.widget).build(&layout.vars);
+        header_constraints.extend(align_right(&header.widget).build(&layout.vars));
+        layout.add(header_constraints.clone());
+        self.header_constraints = header_constraints;
+    }
+}
+
+struct TableHandler<T> {
+    columns: Vec<TableColumn<T>>,
+    rows: Vec<Rc<T>>,
+    /// Columns in the order they are shown
+    order: Vec<usize>,
+    /// Rows in the order they are shown
+    row_order: Vec<usize>,
+    sort: Option<(usize, bool)>,
+    selected: Option<usize>,
+    headers: Vec<Header>,
+    /// Cells of each shown row, in the same order as the headers
+    cells: Vec<Vec<Cell>>,
+    body: Widget,
+    /// Header being dragged, and whether the column has moved since the drag started
+    dragging: Option<(usize, bool)>,
+    /// Width of the column being resized when the drag started
+    resize_start: f32,
+}
+
+impl<T: 'static> TableHandler<T> {
+    /// Updates the headers to match the order, widths and sorting of the columns
+    fn update_headers(&mut self) {
+        for (header, &column) in self.headers.iter_mut().zip(self.order.iter()) {
+            let column_data = &self.columns[column];
+            header.text.event(TextUpdated(column_data.title.clone()));
+            header.widget.layout().edit_width().set(column_data.width).strength(STRONG);
+            let indicator = match self.sort {
+                Some((sorted, true)) if sorted == column => "▲",
+                Some((sorted, false)) if sorted == column => "▼",
+                _ => "",
+            };
+            header.sort_indicator.event(TextUpdated(indicator.to_owned()));
+        }
+    }
+
+    fn build_row(&self, row_index: usize, table: &Widget) -> (Widget, Vec<Cell>) {
+        let mut row_widget = Widget::new("table_row");
+        row_widget
+            .set_draw_style(DrawStyle::from_class::<RectStyle>("table_row_rect"))
+            .enable_hover();
+        let mut cells = Vec::new();
+        for (header, &column) in self.headers.iter().zip(self.order.iter()) {
+            let mut cell = Widget::new("table_cell");
+            cell.layout().add(constraints![
+                bound_top(&row_widget),
+                bound_bottom(&row_widget),
+            ]);
+            let mut content = (self.columns[column].build_cell)(&self.rows[row_index]);
+            content.layout().add(constraints![
+                align_left(&cell).padding(5.0),
+                bound_top(&cell).padding(2.0),
+                bound_bottom(&cell).padding(2.0),
+                center_vertical(&cell),
+            ]);
+            cell.add_child(content);
+            row_widget.add_child(cell.clone());
+            let mut cell = Cell {
+                widget: cell,
+                header_constraints: Vec::new(),
+            };
+            cell.align_to(header);
+            cells.push(cell);
+        }
+        let table = table.clone();
+        row_widget.add_handler(move |_: &ItemSelected, _: EventArgs| {
+            table.event(TableEvent::RowSelected(row_index));
+        });
+        (row_widget, cells)
+    }
+
+    /// Replaces the rows of the body with rows in the current order
+    fn rebuild_rows(&mut self, table: &Widget) {
+        let mut body = self.body.clone();
+        for mut child in body.children() {
+            child.remove_widget();
+        }
+        let mut cells = Vec::new();
+        let rows: Vec<Widget> = self.row_order.iter().map(|&row_index| {
+            let (row_widget, row_cells) = self.build_row(row_index, table);
+            cells.push(row_cells);
+            row_widget
+        }).collect();
+        list::add_contents_to_list(&mut body, rows.into_iter(), |row_widget, _| row_widget);
+        self.cells = cells;
+        let selected = self.selected.and_then(|selected| self.row_order.iter().position(|&row| row == selected));
+        let selected = selected.and_then(|position| body.children().get(position).cloned());
+        if let Some(mut selected) = selected.clone() {
+            selected.add_prop(Property::Selected);
+        }
+        body.event(ListItemSelected { widget: selected });
+    }
+
+    fn sort(&mut self, column: usize, ascending: bool, table: &Widget) {
+        let compare = match self.columns.get(column).and_then(|column| column.compare.clone()) {
+            Some(compare) => compare,
+            None => return,
+        };
+        {
+            let rows = &self.rows;
+            self.row_order.sort_by(|&a, &b| {
+                let ordering = compare(&rows[a], &rows[b]);
+                if ascending { ordering } else { ordering.reverse() }
+            });
+        }
+        self.sort = Some((column, ascending));
+        self.update_headers();
+        self.rebuild_rows(table);
+        table.event(TableSorted {
+            column: column,
+            ascending: ascending,
+        });
+    }
+
+    /// Moves a column to a different position, the cells of the columns in between
+    /// are aligned with their new headers
+    fn move_column(&mut self, from: usize, to: usize, table: &Widget) {
+        let column = self.order.remove(from);
+        self.order.insert(to, column);
+        self.update_headers();
+        let (first, last) = (cmp::min(from, to), cmp::max(from, to));
+        for row_cells in &mut self.cells {
+            let cell = row_cells.remove(from);
+            row_cells.insert(to, cell);
+            for position in first..last + 1 {
+                row_cells[position].align_to(&self.headers[position]);
+            }
+        }
+        table.event(ColumnMoved {
+            from: from,
+            to: to,
+        });
+    }
+
+    fn header_dragged(&mut self, position: usize, drag: DragEvent, table: &Widget) {
+        match drag.state {
+            DragState::Start => self.dragging = Some((position, false)),
+            DragState::Moved => {
+                if let Some((dragging, _)) = self.dragging {
+                    let target = {
+                        let bounds = self.headers.iter().map(|header| header.widget.bounds());
+                        drop_target(bounds, dragging, drag.position.x)
+                    };
+                    if let Some(target) = target {
+                        self.move_column(dragging, target, table);
+                        self.dragging = Some((target, true));
+                    }
+                }
+            }
+            DragState::End => (),
+        }
+    }
+
+    fn resize_dragged(&mut self, position: usize, drag: DragEvent, table: &Widget) {
+        let column = self.order[position];
+        match drag.state {
+            DragState::Start => self.resize_start = self.columns[column].width,
+            DragState::Moved => {
+                let width = (self.resize_start + drag.offset.x).max(20.0);
+                self.columns[column].width = width;
+                self.headers[position].widget.layout().edit_width().set(width).strength(STRONG);
+            }
+            DragState::End => {
+                table.event(ColumnResized {
+                    column: column,
+                    width: self.columns[column].width,
+                });
+            }
+        }
+    }
+}
+
+impl<T: 'static> EventHandler<TableEvent> for TableHandler<T> {
+    fn handle(&mut self, event: &TableEvent, args: EventArgs) {
+        match *event {
+            TableEvent::Sort(column, ascending) => self.sort(column, ascending, &args.widget),
+            TableEvent::HeaderClicked(position) => {
+                // the click that ends dragging a header to a new position doesn't sort
+                if let Some((_, true)) = self.dragging {
+                    return;
+                }
+                let column = self.order[position];
+                let ascending = self.sort != Some((column, true));
+                self.sort(column, ascending, &args.widget);
+            }
+            TableEvent::HeaderDragged(position, drag) => self.header_dragged(position, drag, &args.widget),
+            TableEvent::ResizeDragged(position, drag) => self.resize_dragged(position, drag, &args.widget),
+            TableEvent::RowSelected(row) => {
+                if Some(row) != self.selected {
+                    self.selected = Some(row);
+                    args.widget.event(TableRowSelected(row));
+                }
+            }
+        }
+    }
+}
+
+fn build_header(position: usize, table: &Widget) -> Header {
+    let mut header = Widget::new("table_header");
+    header
+        .set_draw_style(DrawStyle::from_class::<RectStyle>("table_header_rect"))
+        .enable_hover()
+        .make_draggable();
+
+    let mut text = Widget::new("table_header_text");
+    text.set_draw_style(DrawStyle::from_class::<TextStyle>("table_header_text"));
+    StaticTextStyle::from_text("").component().apply(&mut text);
+    text.layout().add(constraints![
+        align_left(&header).padding(5.0),
+        bound_top(&header).padding(4.0),
+        bound_bottom(&header).padding(4.0),
+    ]);
+
+    let mut sort_indicator = Widget::new("table_header_sort");
+    sort_indicator.set_draw_style(DrawStyle::from_class::<TextStyle>("table_header_sort"));
+    StaticTextStyle::from_text("").component().apply(&mut sort_indicator);
+    sort_indicator.layout().add(constraints![
+        to_right_of(&text).padding(4.0),
+        center_vertical(&header),
+    ]);
+
+    // dragging the right edge of the header resizes the column
+    let mut resize_handle = Widget::new("table_header_resize");
+    resize_handle.set_draw_style(DrawStyle::from_class::<RectStyle>("table_header_resize"));
+    resize_handle.make_draggable();
+    resize_handle.layout().add(constraints![
+        width(6.0),
+        align_right(&header),
+        bound_top(&header),
+        bound_bottom(&header),
+    ]);
+    {
+        let table = table.clone();
+        resize_handle.add_handler(move |event: &DragEvent, _: EventArgs| {
+            table.event(TableEvent::ResizeDragged(position, *event));
+        });
+    }
+    // so the header doesn't also start a drag, or sort the table
+    resize_handle.add_handler(|_: &WidgetMouseButton, mut args: EventArgs| {
+        *args.handled = true;
+    });
+    resize_handle.add_handler(|_: &ClickEvent, mut args: EventArgs| {
+        *args.handled = true;
+    });
+
+    {
+        let table = table.clone();
+        header.add_handler(move |event: &DragEvent, _: EventArgs| {
+            table.event(TableEvent::HeaderDragged(position, *event));
+        });
+    }
+    {
+        let table = table.clone();
+        header.add_handler(move |_: &ClickEvent, mut args: EventArgs| {
+            table.event(TableEvent::HeaderClicked(position));
+            *args.handled = true;
+        });
+    }
+    header.layout().add(min_width(20.0));
+    header.add_child(text.clone());
+    header.add_child(sort_indicator.clone());
+    header.add_child(resize_handle);
+    Header {
+        widget: header,
+        text: text,
+        sort_indicator: sort_indicator,
+    }
+}
+
+impl<T: 'static> WidgetModifier for Table<T> {
+    fn apply(&self, widget: &mut Widget) {
+        let mut header_row = Widget::new("table_header_row");
+        header_row.linear_layout(LinearLayoutSettings::new(Orientation::Horizontal));
+        header_row.layout().add(constraints![
+            align_top(widget),
+            align_left(widget),
+            bound_right(widget),
+        ]);
+        let headers: Vec<Header> = (0..self.columns.len()).map(|position| build_header(position, widget)).collect();
+        for header in &headers {
+            header_row.add_child(header.widget.clone());
+        }
+
+        let mut body = Widget::from_modifier(List::default());
+        body.layout().add(constraints![
+            below(&header_row),
+            align_left(&header_row),
+            match_width(&header_row),
+            bound_bottom(widget),
+        ]);
+
+        let mut handler = TableHandler {
+            columns: self.columns.clone(),
+            rows: self.rows.clone(),
+            order: (0..self.columns.len()).collect(),
+            row_order: (0..self.rows.len()).collect(),
+            sort: None,
+            selected: None,
+            headers: headers,
+            cells: Vec::new(),
+            body: body.clone(),
+            dragging: None,
+            resize_start: 0.0,
+        };
+        handler.update_headers();
+        handler.rebuild_rows(widget);
+        widget
+            .add_handler(handler)
+            .add_handler(|event: &SortTable, args: EventArgs| {
+                args.widget.event(TableEvent::Sort(event.column, event.ascending));
+            });
+        widget.layout().add(shrink());
+        widget.add_child(header_row);
+        widget.add_child(body);
+    }
+}
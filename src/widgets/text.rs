@@ -1,5 +1,6 @@
 use prelude::*;
 use draw::prelude::*;
+use input::mouse::MouseOverEvent;
 use widgets::edit_text::TextUpdated;
 
 component_style!{pub struct StaticText<name="static_text", style=StaticTextStyle> {
@@ -25,6 +26,10 @@ impl WidgetModifier for StaticText {
     fn apply(&self, widget: &mut Widget) {
         widget.add_handler(TextSizeHandler::default());
         widget.add_handler(TextUpdateHandler::default());
+        widget.add_handler(LinkHandler::default());
+        LinkHandler::add_adapters(widget);
+        // handled directly rather than through the adapters, so a click on a link doesn't bubble further
+        widget.add_handler(LinkHandler::clicked);
         widget.add_handler(|_: &StyleUpdated, args: EventArgs| {
             args.widget.event(StaticTextUpdate::StyleUpdated);
         });
@@ -65,31 +70,107 @@ impl EventHandler<StaticTextUpdate> for TextUpdateHandler {
     }
 }
 
+/// Emitted, bubbling up from a text widget, when one of it's `TextLink`s is clicked.
+/// Contains the link target.
+#[derive(Debug, Clone)]
+pub struct LinkClicked(pub String);
+
+/// Tracks the link under the mouse, to update the hover style and cursor, and emits `LinkClicked`
+#[derive(Default)]
+struct LinkHandler {
+    hovered: Option<usize>,
+    /// The cursor to restore when the mouse leaves the links, set while a link is hovered
+    previous_cursor: Option<glutin::MouseCursor>,
+}
+
+impl LinkHandler {
+    fn link_at(widget: &mut Widget, point: Point) -> Option<usize> {
+        let bounds = widget.bounds();
+        let draw_state = widget.draw_state();
+        draw_state.downcast_ref::<TextState>().and_then(|state| state.link_at(bounds, point))
+    }
+    fn set_hovered(&mut self, hovered: Option<usize>, mut args: EventArgs) {
+        if hovered != self.hovered {
+            self.hovered = hovered;
+            args.widget.update(|state: &mut TextState| state.hovered_link = hovered);
+            if hovered.is_some() {
+                if self.previous_cursor.is_none() {
+                    self.previous_cursor = Some(args.ui.get_cursor());
+                    args.ui.set_cursor(glutin::MouseCursor::Hand);
+                }
+            } else if let Some(previous_cursor) = self.previous_cursor.take() {
+                // unless another widget has changed the cursor since
+                if args.ui.get_cursor() == glutin::MouseCursor::Hand {
+                    args.ui.set_cursor(previous_cursor);
+                }
+            }
+        }
+    }
+    fn mouse_moved(&mut self, event: &WidgetMouseMoved, mut args: EventArgs) {
+        let hovered = Self::link_at(&mut args.widget, event.0);
+        self.set_hovered(hovered, args);
+    }
+    fn mouse_over(&mut self, event: &MouseOverEvent, args: EventArgs) {
+        if let MouseOverEvent::Out = *event {
+            self.set_hovered(None, args);
+        }
+    }
+    fn clicked(event: &ClickEvent, mut args: EventArgs) {
+        let target = {
+            let bounds = args.widget.bounds();
+            let draw_state = args.widget.draw_state();
+            draw_state.downcast_ref::<TextState>().and_then(|state| {
+                state.link_at(bounds, event.position).map(|link| state.links[link].target.clone())
+            })
+        };
+        if let Some(target) = target {
+            args.widget.event_bubble_up(LinkClicked(target));
+            *args.handled = true;
+        }
+    }
+}
+
+multi_event!{impl EventHandler<LinkHandlerEvent> for LinkHandler {
+    WidgetMouseMoved => mouse_moved,
+    MouseOverEvent => mouse_over,
+}}
+
 #[derive(Default)]
 struct TextSizeHandler {
     measured_size: Option<Size>,
+    measured_baseline: Option<f32>,
     size_constraints: Vec<Constraint>,
 }
 
 impl EventHandler<StateUpdated> for TextSizeHandler {
     fn handle(&mut self, _: &StateUpdated, mut args: EventArgs) {
-        let text_size = {
+        let (text_size, baseline) = {
             let draw_state = args.widget.draw_state();
             if let Some(state) = draw_state.downcast_ref::<TextState>() {
-                state.measure()
+                (state.measure(), state.baseline())
             } else {
-                Size::zero()
+                (Size::zero(), None)
             }
         };
-        if self.measured_size.is_none() || self.measured_size.unwrap() != text_size {
-            let size_constraints = size(text_size).build(&args.widget.layout_vars());
+        if self.measured_size.is_none() || self.measured_size.unwrap() != text_size ||
+            self.measured_baseline != baseline {
             let mut layout = args.widget.layout();
             for constraint in self.size_constraints.drain(..) {
                 layout.remove_constraint(constraint);
             }
-            layout.add(size_constraints.clone());
-            self.size_constraints = size_constraints;
+            layout.set_measured_size(text_size);
+            // text arranged by a `DirectContainer` only needs it's measured size
+            if !layout.is_arranged() {
+                let mut size_constraints = size(text_size).build(&layout.vars);
+                if let Some(baseline) = baseline {
+                    let top = layout.vars.top;
+                    size_constraints.push(layout.baseline() | EQ(REQUIRED) | top + baseline);
+                }
+                layout.add(size_constraints.clone());
+                self.size_constraints = size_constraints;
+            }
             self.measured_size = Some(text_size);
+            self.measured_baseline = baseline;
         }
     }
 }
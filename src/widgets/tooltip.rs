@@ -0,0 +1,176 @@
+use std::rc::Rc;
+use std::time::Duration;
+
+use prelude::*;
+use draw::prelude::*;
+use input::mouse::MouseOverEvent;
+use widgets::text::StaticTextStyle;
+
+/// Time the cursor has to rest over a widget before its tooltip is shown, unless set with `set_tooltip_with_delay`
+pub const DEFAULT_TOOLTIP_DELAY: u64 = 500;
+
+/// Contents of a tooltip
+#[derive(Clone)]
+pub enum Tooltip {
+    /// Text drawn in a rect, styled with the "tooltip_text" and "tooltip_rect" classes
+    Text(String),
+    /// Creates a widget each time the tooltip is shown
+    Widget(Rc<Fn() -> Widget>),
+}
+
+impl Tooltip {
+    pub fn from_fn<F: Fn() -> Widget + 'static>(build: F) -> Self {
+        Tooltip::Widget(Rc::new(build))
+    }
+    fn build(&self) -> Widget {
+        match *self {
+            Tooltip::Text(ref text) => {
+                let mut rect_widget = Widget::new("tooltip");
+                rect_widget.set_draw_style(DrawStyle::from_class::<RectStyle>("tooltip_rect"));
+                let mut text_widget = Widget::new("tooltip_text");
+                text_widget.set_draw_style(DrawStyle::from_class::<TextStyle>("tooltip_text"));
+                StaticTextStyle::from_text(text).component().apply(&mut text_widget);
+                text_widget.layout().add(bound_by(&rect_widget).padding(5.0));
+                rect_widget.layout().add(shrink());
+                rect_widget.add_child(text_widget);
+                rect_widget
+            }
+            Tooltip::Widget(ref build) => (**build)(),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for Tooltip {
+    fn from(text: &'a str) -> Self {
+        Tooltip::Text(text.to_owned())
+    }
+}
+
+impl From<String> for Tooltip {
+    fn from(text: String) -> Self {
+        Tooltip::Text(text)
+    }
+}
+
+/// Adds tooltips to a `Widget`
+pub trait TooltipExt {
+    /// Show a tooltip near the cursor after it rests over this widget for the default delay
+    fn set_tooltip<T: Into<Tooltip>>(&mut self, tooltip: T) -> &mut Self;
+    /// Show a tooltip near the cursor after it rests over this widget for `delay`
+    fn set_tooltip_with_delay<T: Into<Tooltip>>(&mut self, tooltip: T, delay: Duration) -> &mut Self;
+}
+
+impl TooltipExt for Widget {
+    fn set_tooltip<T: Into<Tooltip>>(&mut self, tooltip: T) -> &mut Self {
+        self.set_tooltip_with_delay(tooltip, Duration::from_millis(DEFAULT_TOOLTIP_DELAY))
+    }
+    fn set_tooltip_with_delay<T: Into<Tooltip>>(&mut self, tooltip: T, delay: Duration) -> &mut Self {
+        self.add_handler(TooltipHandler {
+            tooltip: tooltip.into(),
+            delay: delay,
+            cursor: None,
+            hovering: false,
+            generation: 0,
+            shown: None,
+        });
+        TooltipHandler::add_adapters(self);
+        self
+    }
+}
+
+/// Sent to the widget after the delay, the tooltip is shown if the generation still matches,
+/// ie. the cursor hasn't moved or left the widget since
+#[derive(Debug, Copy, Clone)]
+struct ShowTooltip(usize);
+
+multi_event!{impl EventHandler<TooltipEvent> for TooltipHandler {
+    MouseOverEvent => mouse_over,
+    WidgetMouseMoved => mouse_moved,
+    WidgetMouseButton => mouse_button,
+    WidgetMouseWheel => mouse_wheel,
+    WidgetDetachedEvent => detached,
+    ShowTooltip => show,
+}}
+
+struct TooltipHandler {
+    tooltip: Tooltip,
+    delay: Duration,
+    /// Last position of the cursor over the widget
+    cursor: Option<Point>,
+    hovering: bool,
+    generation: usize,
+    shown: Option<Widget>,
+}
+
+impl TooltipHandler {
+    fn hide(&mut self) {
+        self.generation += 1;
+        if let Some(mut tooltip) = self.shown.take() {
+            tooltip.remove_widget();
+        }
+    }
+
+    /// Shows the tooltip after the delay, unless the cursor moves or leaves the widget first
+    fn schedule(&mut self, widget: &Widget) {
+        self.generation += 1;
+        widget.event_after(self.delay, ShowTooltip(self.generation));
+    }
+
+    fn mouse_over(&mut self, event: &MouseOverEvent, args: EventArgs) {
+        match *event {
+            MouseOverEvent::Over => {
+                self.hovering = true;
+                if self.shown.is_none() {
+                    self.schedule(&args.widget);
+                }
+            }
+            MouseOverEvent::Out => {
+                self.hovering = false;
+                self.hide();
+            }
+        }
+    }
+
+    fn mouse_moved(&mut self, event: &WidgetMouseMoved, args: EventArgs) {
+        self.cursor = Some(event.0);
+        // the delay restarts each time the cursor moves, so the tooltip shows once it rests
+        if self.hovering && self.shown.is_none() {
+            self.schedule(&args.widget);
+        }
+    }
+
+    fn mouse_button(&mut self, _: &WidgetMouseButton, _: EventArgs) {
+        self.hide();
+    }
+
+    fn mouse_wheel(&mut self, _: &WidgetMouseWheel, _: EventArgs) {
+        self.hide();
+    }
+
+    fn detached(&mut self, _: &WidgetDetachedEvent, _: EventArgs) {
+        self.hovering = false;
+        self.hide();
+    }
+
+    fn show(&mut self, event: &ShowTooltip, args: EventArgs) {
+        if event.0 != self.generation || !self.hovering || self.shown.is_some() {
+            return;
+        }
+        if let Some(cursor) = self.cursor {
+            let mut tooltip = self.tooltip.build();
+            // never under the cursor, so showing it doesn't move the cursor out of the widget
+            ignore_cursor(&mut tooltip);
+            // below the cursor, clear of the pointer
+            let position = cursor + Vector::new(0.0, 20.0);
+            args.ui.add_overlay(tooltip.clone(), OverlayAnchor::Point(position));
+            self.shown = Some(tooltip);
+        }
+    }
+}
+
+fn ignore_cursor(widget: &mut Widget) {
+    widget.set_cursor_hit_fn(|_, _| false);
+    for mut child in widget.children() {
+        ignore_cursor(&mut child);
+    }
+}
@@ -0,0 +1,439 @@
+use std::collections::HashSet;
+
+use prelude::*;
+use draw::prelude::*;
+use input::keyboard::WidgetKeyboardInput;
+use widgets::text::StaticTextStyle;
+use widgets::list::{self, ListItemSelected, ItemSelected};
+
+/// A node in a `TreeView`, identified by an id that is unique within the tree
+#[derive(Debug, Clone)]
+pub struct TreeNode {
+    id: String,
+    label: String,
+    children: Vec<TreeNode>,
+    /// False if the children are loaded when the node is first expanded
+    loaded: bool,
+}
+
+impl TreeNode {
+    /// A node without children
+    pub fn new(id: &str, label: &str) -> Self {
+        TreeNode {
+            id: id.to_owned(),
+            label: label.to_owned(),
+            children: Vec::new(),
+            loaded: true,
+        }
+    }
+    pub fn children(mut self, children: Vec<TreeNode>) -> Self {
+        self.children = children;
+        self.loaded = true;
+        self
+    }
+    /// The children are loaded when the node is first expanded, the tree emits `LoadTreeNodeChildren`
+    /// and expects a `SetTreeNodeChildren` in response
+    pub fn lazy(mut self) -> Self {
+        self.children.clear();
+        self.loaded = false;
+        self
+    }
+    fn expandable(&self) -> bool {
+        !self.loaded || !self.children.is_empty()
+    }
+}
+
+/// The path of the node with the given id, a list of indices into `nodes` and their children
+fn find_path(nodes: &[TreeNode], id: &str) -> Option<Vec<usize>> {
+    for (index, node) in nodes.iter().enumerate() {
+        if node.id == id {
+            return Some(vec![index]);
+        }
+        if let Some(mut path) = find_path(&node.children, id) {
+            path.insert(0, index);
+            return Some(path);
+        }
+    }
+    None
+}
+
+fn node_at<'a>(nodes: &'a [TreeNode], path: &[usize]) -> Option<&'a TreeNode> {
+    match path.split_first() {
+        Some((&index, rest)) => nodes.get(index).and_then(|node| {
+            if rest.is_empty() { Some(node) } else { node_at(&node.children, rest) }
+        }),
+        None => None,
+    }
+}
+
+fn node_at_mut<'a>(nodes: &'a mut [TreeNode], path: &[usize]) -> Option<&'a mut TreeNode> {
+    match path.split_first() {
+        Some((&index, rest)) => nodes.get_mut(index).and_then(|node| {
+            if rest.is_empty() { Some(node) } else { node_at_mut(&mut node.children, rest) }
+        }),
+        None => None,
+    }
+}
+
+/// Expands the node with the given id
+#[derive(Debug, Clone)]
+pub struct ExpandTreeNode(pub String);
+
+/// Collapses the node with the given id
+#[derive(Debug, Clone)]
+pub struct CollapseTreeNode(pub String);
+
+/// Sets the children of the node with the given id, eg. in response to `LoadTreeNodeChildren`
+#[derive(Debug, Clone)]
+pub struct SetTreeNodeChildren(pub String, pub Vec<TreeNode>);
+
+/// Emitted by a `TreeView` when a node is expanded
+#[derive(Debug, Clone)]
+pub struct TreeNodeExpanded(pub String);
+
+/// Emitted by a `TreeView` when a node is collapsed
+#[derive(Debug, Clone)]
+pub struct TreeNodeCollapsed(pub String);
+
+/// Emitted by a `TreeView` when a lazy node is expanded for the first time
+#[derive(Debug, Clone)]
+pub struct LoadTreeNodeChildren(pub String);
+
+/// Emitted by a `TreeView` when a different node is selected
+#[derive(Debug, Clone)]
+pub struct TreeNodeSelected(pub String);
+
+/// Emitted by a `TreeView` when the selection is cleared, by clicking the tree outside of it's rows
+/// or clicking the selected row
+#[derive(Debug, Clone)]
+pub struct TreeNodeDeselected(pub String);
+
+/// Emitted by a `TreeView` when Return is pressed with a node selected
+#[derive(Debug, Clone)]
+pub struct TreeNodeActivated(pub String);
+
+/// Emitted by a row when it's selected by the list
+#[derive(Debug, Clone)]
+struct TreeRowSelected(String);
+
+/// Emitted by a row when it's disclosure triangle is clicked
+#[derive(Debug, Clone)]
+struct TreeRowToggled(String);
+
+/// A visible node, the rows are the children of the tree's list, in the same order
+#[derive(Debug, Clone)]
+struct Row {
+    id: String,
+    label: String,
+    depth: usize,
+    expandable: bool,
+    expanded: bool,
+}
+
+multi_event!{impl EventHandler<TreeViewEvent> for TreeViewHandler {
+    TreeRowSelected => row_selected,
+    TreeRowToggled => row_toggled,
+    ExpandTreeNode => expand_node,
+    CollapseTreeNode => collapse_node,
+    SetTreeNodeChildren => set_children,
+    WidgetKeyboardInput => key_input,
+    ClickEvent => clicked,
+}}
+
+struct TreeViewHandler {
+    nodes: Vec<TreeNode>,
+    expanded: HashSet<String>,
+    selected: Option<String>,
+    rows: Vec<Row>,
+    indent: f32,
+}
+
+impl TreeViewHandler {
+    fn add_rows(&self, nodes: &[TreeNode], depth: usize, rows: &mut Vec<Row>) {
+        for node in nodes {
+            self.add_row(node, depth, rows);
+        }
+    }
+
+    /// Adds the row of a node, followed by the rows of it's visible descendants
+    fn add_row(&self, node: &TreeNode, depth: usize, rows: &mut Vec<Row>) {
+        let expanded = self.expanded.contains(&node.id);
+        rows.push(Row {
+            id: node.id.clone(),
+            label: node.label.clone(),
+            depth: depth,
+            expandable: node.expandable(),
+            expanded: expanded,
+        });
+        if expanded {
+            self.add_rows(&node.children, depth + 1, rows);
+        }
+    }
+
+    /// Adds the rows of the currently visible nodes to the list
+    fn build(&mut self, tree: &mut Widget) {
+        let mut rows = Vec::new();
+        self.add_rows(&self.nodes, 0, &mut rows);
+        self.rows = rows;
+        let indent = self.indent;
+        list::add_contents_to_list(tree, self.rows.clone().into_iter(), |row, list| build_row(row, indent, list));
+        let selected = self.selected.clone();
+        self.show_selected(selected, tree);
+    }
+
+    /// Replaces the row of the node with the given id and the rows of it's descendants,
+    /// after it's expanded or it's children change. The other rows stay in place.
+    fn update_rows(&mut self, id: &str, tree: &mut Widget) {
+        let index = match self.rows.iter().position(|row| row.id == id) {
+            Some(index) => index,
+            // the node is inside a collapsed node
+            None => return,
+        };
+        let depth = self.rows[index].depth;
+        let end = self.rows[index + 1..].iter().position(|row| row.depth <= depth)
+            .map_or(self.rows.len(), |len| index + 1 + len);
+        let rows = {
+            let node = find_path(&self.nodes, id).and_then(|path| node_at(&self.nodes, &path));
+            let mut rows = Vec::new();
+            if let Some(node) = node {
+                self.add_row(node, depth, &mut rows);
+            }
+            rows
+        };
+        for mut row_widget in tree.children()[index..end].iter().cloned() {
+            row_widget.remove_widget();
+        }
+        let indent = self.indent;
+        list::insert_contents_into_list(tree, index, rows.clone().into_iter(), |row, list| build_row(row, indent, list));
+        let rest = self.rows.split_off(end);
+        self.rows.truncate(index);
+        self.rows.extend(rows);
+        self.rows.extend(rest);
+        let selected = self.selected.clone();
+        self.show_selected(selected, tree);
+    }
+
+    /// Sets `Property::Selected` on the row of the selected node, if it's visible
+    fn show_selected(&mut self, id: Option<String>, tree: &Widget) {
+        let row = id.as_ref().and_then(|id| self.rows.iter().position(|row| row.id == *id));
+        let row_widget = row.and_then(|index| tree.children().get(index).cloned());
+        if let Some(mut row_widget) = row_widget.clone() {
+            if !row_widget.props().contains(&Property::Selected) {
+                row_widget.add_prop(Property::Selected);
+            }
+        }
+        tree.event(ListItemSelected { widget: row_widget });
+        self.selected = id;
+    }
+
+    fn select(&mut self, id: String, tree: &Widget) {
+        if Some(&id) != self.selected.as_ref() {
+            self.show_selected(Some(id.clone()), tree);
+            tree.event(TreeNodeSelected(id));
+        }
+    }
+
+    fn set_expanded(&mut self, id: &str, expanded: bool, tree: &mut Widget) {
+        let path = match find_path(&self.nodes, id) {
+            Some(path) => path,
+            None => return,
+        };
+        let (expandable, loaded) = match node_at(&self.nodes, &path) {
+            Some(node) => (node.expandable(), node.loaded),
+            None => return,
+        };
+        if !expandable || expanded == self.expanded.contains(id) {
+            return;
+        }
+        if expanded {
+            self.expanded.insert(id.to_owned());
+            tree.event(TreeNodeExpanded(id.to_owned()));
+            if !loaded {
+                tree.event(LoadTreeNodeChildren(id.to_owned()));
+            }
+        } else {
+            self.expanded.remove(id);
+            tree.event(TreeNodeCollapsed(id.to_owned()));
+            // the selection moves to the collapsed node if it's inside it
+            let selected_inside = self.selected.as_ref()
+                .and_then(|selected| find_path(&self.nodes, selected))
+                .map_or(false, |selected| selected.len() > path.len() && selected.starts_with(&path));
+            if selected_inside {
+                self.selected = Some(id.to_owned());
+                tree.event(TreeNodeSelected(id.to_owned()));
+            }
+        }
+        self.update_rows(id, tree);
+    }
+
+    fn row_selected(&mut self, event: &TreeRowSelected, args: EventArgs) {
+        if Some(&event.0) != self.selected.as_ref() {
+            self.selected = Some(event.0.clone());
+            args.widget.event(TreeNodeSelected(event.0.clone()));
+        }
+    }
+
+    fn row_toggled(&mut self, event: &TreeRowToggled, mut args: EventArgs) {
+        let expanded = self.expanded.contains(&event.0);
+        self.set_expanded(&event.0, !expanded, &mut args.widget);
+    }
+
+    fn expand_node(&mut self, event: &ExpandTreeNode, mut args: EventArgs) {
+        self.set_expanded(&event.0, true, &mut args.widget);
+    }
+
+    fn collapse_node(&mut self, event: &CollapseTreeNode, mut args: EventArgs) {
+        self.set_expanded(&event.0, false, &mut args.widget);
+    }
+
+    fn set_children(&mut self, event: &SetTreeNodeChildren, mut args: EventArgs) {
+        let SetTreeNodeChildren(ref id, ref children) = *event;
+        if let Some(path) = find_path(&self.nodes, id) {
+            if let Some(node) = node_at_mut(&mut self.nodes, &path) {
+                node.children = children.clone();
+                node.loaded = true;
+            }
+            self.update_rows(id, &mut args.widget);
+        }
+    }
+
+    fn key_input(&mut self, event: &WidgetKeyboardInput, mut args: EventArgs) {
+        let &WidgetKeyboardInput(input) = event;
+        if input.state != glutin::ElementState::Pressed || self.rows.is_empty() {
+            return;
+        }
+        let current = self.selected.as_ref().and_then(|id| self.rows.iter().position(|row| row.id == *id));
+        let next = match (input.virtual_keycode, current) {
+            (Some(glutin::VirtualKeyCode::Down), Some(current)) => Some((current + 1).min(self.rows.len() - 1)),
+            (Some(glutin::VirtualKeyCode::Up), Some(current)) => Some(current.saturating_sub(1)),
+            (Some(glutin::VirtualKeyCode::Down), None) | (Some(glutin::VirtualKeyCode::Up), None) => Some(0),
+            (Some(glutin::VirtualKeyCode::Right), Some(current)) => {
+                let row = self.rows[current].clone();
+                if row.expandable && !row.expanded {
+                    self.set_expanded(&row.id, true, &mut args.widget);
+                    None
+                } else if row.expanded && self.rows.get(current + 1).map_or(false, |next| next.depth > row.depth) {
+                    Some(current + 1)
+                } else {
+                    None
+                }
+            }
+            (Some(glutin::VirtualKeyCode::Left), Some(current)) => {
+                let row = self.rows[current].clone();
+                if row.expanded {
+                    self.set_expanded(&row.id, false, &mut args.widget);
+                    None
+                } else {
+                    // the parent is the closest row above with a smaller depth
+                    self.rows[..current].iter().rposition(|parent| parent.depth < row.depth)
+                }
+            }
+            (Some(glutin::VirtualKeyCode::Return), Some(current)) => {
+                args.widget.event(TreeNodeActivated(self.rows[current].id.clone()));
+                None
+            }
+            _ => None,
+        };
+        if let Some(next) = next {
+            let id = self.rows[next].id.clone();
+            self.select(id, &args.widget);
+        }
+    }
+
+    fn clicked(&mut self, _: &ClickEvent, args: EventArgs) {
+        // the list clears it's selection when clicked outside of a row
+        if let Some(id) = self.selected.take() {
+            args.widget.event(TreeNodeDeselected(id));
+        }
+    }
+}
+
+fn build_row(row: Row, indent: f32, list: &mut Widget) -> Widget {
+    let mut row_widget = Widget::new("tree_view_row");
+    row_widget
+        .set_draw_style(DrawStyle::from_class::<RectStyle>("tree_view_row_rect"))
+        .enable_hover();
+
+    // space for the disclosure triangle is reserved for leaf nodes too, so labels at the same depth line up
+    let mut disclosure = Widget::new("tree_view_disclosure");
+    disclosure.layout().add(constraints![
+        width(16.0),
+        align_left(&row_widget).padding(5.0 + row.depth as f32 * indent),
+        bound_top(&row_widget),
+        bound_bottom(&row_widget),
+    ]);
+    if row.expandable {
+        let mut triangle = Widget::new("tree_view_disclosure_triangle");
+        triangle.set_draw_style(DrawStyle::from_class::<TextStyle>("tree_view_disclosure"));
+        StaticTextStyle::from_text(if row.expanded { "▼" } else { "►" }).component().apply(&mut triangle);
+        triangle.layout().add(center(&disclosure));
+        disclosure.add_child(triangle);
+        let id = row.id.clone();
+        disclosure.add_handler(move |_: &ClickEvent, mut args: EventArgs| {
+            args.widget.event_bubble_up(TreeRowToggled(id.clone()));
+            *args.handled = true;
+        });
+    }
+
+    let mut label = Widget::new("tree_view_text");
+    label.set_draw_style(DrawStyle::from_class::<TextStyle>("tree_view_text"));
+    StaticTextStyle::from_text(&row.label).component().apply(&mut label);
+    label.layout().add(constraints![
+        to_right_of(&disclosure).padding(5.0),
+        bound_top(&row_widget).padding(2.0),
+        bound_bottom(&row_widget).padding(2.0),
+        bound_right(&row_widget),
+    ]);
+
+    {
+        let tree = list.clone();
+        row_widget.add_handler(move |_: &ClickEvent, args: EventArgs| {
+            args.ui.event(KeyboardInputEvent::FocusChange(Some(tree.clone())));
+        });
+    }
+    let id = row.id;
+    row_widget.add_handler(move |_: &ItemSelected, args: EventArgs| {
+        args.widget.event_bubble_up(TreeRowSelected(id.clone()));
+    });
+    row_widget.layout().add(match_width(list));
+    row_widget.add_child(disclosure);
+    row_widget.add_child(label);
+    row_widget
+}
+
+component_style!{pub struct TreeView<name="tree_view", style=TreeViewStyle> {
+    nodes: Vec<TreeNode> = Vec::new(),
+    indent: f32 = 16.0,
+}}
+
+impl TreeViewStyle {
+    pub fn from_nodes(nodes: Vec<TreeNode>) -> Self {
+        Self {
+            nodes: Some(nodes),
+            ..Self::default()
+        }
+    }
+}
+
+impl WidgetModifier for TreeView {
+    fn apply(&self, widget: &mut Widget) {
+        List::default().apply(widget);
+        widget
+            .add_handler(|_: &WidgetAttachedEvent, args: EventArgs| {
+                args.ui.event(KeyboardInputEvent::AddFocusable(args.widget));
+            })
+            .add_handler(|_: &WidgetDetachedEvent, args: EventArgs| {
+                args.ui.event(KeyboardInputEvent::RemoveFocusable(args.widget));
+            });
+        let mut handler = TreeViewHandler {
+            nodes: self.nodes.clone(),
+            expanded: HashSet::new(),
+            selected: None,
+            rows: Vec::new(),
+            indent: self.indent,
+        };
+        handler.build(widget);
+        widget.add_handler(handler);
+        TreeViewHandler::add_adapters(widget);
+    }
+}
@@ -46,6 +46,7 @@ pub mod line;
 use std::f32;
 use rusttype::Scale;
 use self::line::{LineRects, LineInfo, LineInfos};
+use self::glyph::GlyphRectsPerLine;
 use self::types::*;
 
 
@@ -119,24 +120,40 @@ pub fn get_positioned_glyphs(text: &str,
                              line_wrap: Wrap,
                              align: Align)
                              -> Vec<PositionedGlyph>
+{
+    get_indexed_glyphs(text, rect, font, font_size, line_height, line_wrap, align)
+        .into_iter().map(|(_, glyph)| glyph).collect()
+}
+
+/// Same as `get_positioned_glyphs`, but each glyph is paired with the byte index
+/// of the character in `text` it was produced from.
+pub fn get_indexed_glyphs(text: &str,
+                          rect: Rect,
+                          font: &Font,
+                          font_size: f32,
+                          line_height: f32,
+                          line_wrap: Wrap,
+                          align: Align)
+                          -> Vec<(usize, PositionedGlyph)>
 {
     let line_infos: Vec<LineInfo> = LineInfos::new(text, font, font_size, line_wrap, rect.width())
         .collect();
     let line_infos = line_infos.iter().cloned();
+    let line_starts = line_infos.clone().map(|info| info.start_byte);
     let line_texts = line_infos.clone().map(|info| &text[info.byte_range()]);
     let line_rects = LineRects::new(line_infos, font_size, rect, align, line_height);
     let scale = Scale::uniform(font_size);
 
     let mut positioned_glyphs = Vec::new();
-    for (line_text, line_rect) in line_texts.zip(line_rects) {
+    for ((line_start, line_text), line_rect) in line_starts.zip(line_texts).zip(line_rects) {
         // point specifies bottom left corner of text line
         let point = rusttype::Point {
             x: line_rect.left(),
             y: line_rect.top() + font_size,
         };
 
-        positioned_glyphs.extend(font.glyphs_for(line_text.chars())
-            .scan((None, 0.0), |state, g| {
+        positioned_glyphs.extend(font.glyphs_for(line_text.chars()).zip(line_text.char_indices())
+            .scan((None, 0.0), |state, (g, (byte, _))| {
                 let &mut (last, x) = state;
                 let g = g.scaled(scale);
 
@@ -145,12 +162,40 @@ pub fn get_positioned_glyphs(text: &str,
 
                 let next = g.positioned(point + rusttype::vector(x, 0.0));
                 *state = (Some(next.id()), x + width + kern);
-                Some(next.standalone())
+                Some((line_start + byte, next.standalone()))
             }));
     }
     positioned_glyphs
 }
 
+/// Find the byte index of the character whose glyph contains `point`, if any,
+/// for text laid out in `rect`.
+pub fn get_byte_index_at(text: &str,
+                         rect: Rect,
+                         font: &Font,
+                         font_size: f32,
+                         line_height: f32,
+                         line_wrap: Wrap,
+                         align: Align,
+                         point: Point)
+                         -> Option<usize>
+{
+    let line_infos: Vec<LineInfo> = LineInfos::new(text, font, font_size, line_wrap, rect.width())
+        .collect();
+    let line_rects = LineRects::new(line_infos.iter().cloned(), font_size, rect, align, line_height);
+    let lines_with_rects = line_infos.iter().map(|info| &text[info.byte_range()]).zip(line_rects);
+    let glyph_rects_per_line = GlyphRectsPerLine::new(lines_with_rects, font, font_size);
+    for (line_info, glyph_rects) in line_infos.iter().zip(glyph_rects_per_line) {
+        let line_text = &text[line_info.byte_range()];
+        for ((byte, _), glyph_rect) in line_text.char_indices().zip(glyph_rects) {
+            if glyph_rect.contains(&point) {
+                return Some(line_info.start_byte + byte);
+            }
+        }
+    }
+    None
+}
+
 /// An iterator yielding each line within the given `text` as a new `&str`, where the start and end
 /// indices into each line are provided by the given iterator.
 #[derive(Clone)]
@@ -196,3 +241,44 @@ pub fn px_to_pt(font_size_in_px: f32) -> f32 {
 pub fn pt_to_scale(font_size_in_points: f32) -> Scale {
     Scale::uniform(font_size_in_points)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn byte_at(text: &str, wrap: Wrap, width: f32, x: f32, y: f32) -> Option<usize> {
+        let data: &'static [u8] = include_bytes!("../../assets/fonts/Hack/Hack-Regular.ttf");
+        let font = rusttype::FontCollection::from_bytes(data).into_font().unwrap();
+        let rect = Rect::new(Point::new(0.0, 0.0), Size::new(width, 100.0));
+        // 20px lines, Hack is monospaced, with glyphs about 10px wide
+        get_byte_index_at(text, rect, &font, 20.0, 20.0, wrap, Align::Start, Point::new(x, y))
+    }
+
+    #[test]
+    fn byte_index_in_line() {
+        assert_eq!(byte_at("link", Wrap::NoWrap, 200.0, 5.0, 10.0), Some(0));
+        assert_eq!(byte_at("link", Wrap::NoWrap, 200.0, 36.0, 10.0), Some(3));
+        assert_eq!(byte_at("link", Wrap::NoWrap, 200.0, 100.0, 10.0), None);
+        assert_eq!(byte_at("link", Wrap::NoWrap, 200.0, 5.0, 30.0), None);
+    }
+
+    #[test]
+    fn byte_index_in_later_lines() {
+        // byte indices on later lines are offset by the start of the line
+        assert_eq!(byte_at("one\ntwo", Wrap::NoWrap, 200.0, 5.0, 30.0), Some(4));
+        assert_eq!(byte_at("one\ntwo", Wrap::NoWrap, 200.0, 25.0, 30.0), Some(6));
+        assert_eq!(byte_at("one two", Wrap::Whitespace, 50.0, 5.0, 30.0), Some(4));
+    }
+
+    #[test]
+    fn byte_index_in_multi_line_span() {
+        // a link from "link" to "text" is hit on both lines
+        let text = "see link\ntext";
+        let (start, end) = (4, 13);
+        let first = byte_at(text, Wrap::NoWrap, 200.0, 46.0, 10.0).unwrap();
+        let last = byte_at(text, Wrap::NoWrap, 200.0, 35.0, 30.0).unwrap();
+        assert_eq!((first, last), (4, 12));
+        assert!(start <= first && last < end);
+        assert_eq!(byte_at(text, Wrap::NoWrap, 200.0, 25.0, 10.0), Some(2));
+    }
+}